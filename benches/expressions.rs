@@ -0,0 +1,67 @@
+//! Times every expression in the built-in templates over 300 frames, re-parsed each
+//! frame versus compiled once. Run with `cargo bench --bench expressions`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use termcad::scene::{evaluate_expression, templates, CompiledExpression, ExpressionContext};
+
+const FRAMES: u32 = 300;
+
+// Every string in the scene that evaluates as an expression; colors and geometry
+// names don't
+fn collect(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => out.push(s.clone()),
+        serde_json::Value::Array(items) => items.iter().for_each(|v| collect(v, out)),
+        serde_json::Value::Object(map) => map.values().for_each(|v| collect(v, out)),
+        _ => {}
+    }
+}
+
+fn main() {
+    let mut strings = Vec::new();
+    for scene in [
+        templates::spinning_cube(),
+        templates::grid_flythrough(),
+        templates::text_terminal(),
+        templates::spinning_cube_orbit(),
+        templates::orbit_showcase(),
+    ] {
+        if let Ok(value) = serde_json::to_value(scene) {
+            collect(&value, &mut strings);
+        }
+    }
+    let first = ExpressionContext::new(0, FRAMES);
+    let sources: Vec<String> = strings
+        .into_iter()
+        .filter(|s| evaluate_expression(s, &first).is_ok())
+        .collect();
+    let frames = (0..FRAMES).map(|frame| ExpressionContext::new(frame, FRAMES));
+
+    let started = Instant::now();
+    for ctx in frames.clone() {
+        for source in &sources {
+            let _ = black_box(evaluate_expression(source, &ctx));
+        }
+    }
+    let reparsed = started.elapsed();
+
+    let compiled: Vec<CompiledExpression> =
+        sources.iter().map(|s| CompiledExpression::new(s.as_str())).collect();
+    let started = Instant::now();
+    for ctx in frames {
+        for expr in &compiled {
+            let _ = black_box(expr.evaluate(&ctx));
+        }
+    }
+    let reused = started.elapsed();
+
+    println!(
+        "{} expressions x {} frames: reparsed {:?}, compiled once {:?}",
+        sources.len(),
+        FRAMES,
+        reparsed,
+        reused
+    );
+}
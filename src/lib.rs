@@ -0,0 +1,14 @@
+//! Terminal CAD aesthetic GIF generator.
+//!
+//! Scenes are parsed into [`Scene`], checked with [`Scene::validate`], rendered
+//! frame by frame with [`Renderer`], and written out with [`write_frames`] or
+//! [`assemble_gif`].
+
+pub mod output;
+pub mod primitives;
+pub mod render;
+pub mod scene;
+
+pub use output::{assemble_gif, write_frames, Dither, FrameWriteError, GifError, GifOptions};
+pub use render::{RenderBackend, RenderError, RenderProgress, Renderer};
+pub use scene::{Scene, ValidationError};
@@ -1,13 +1,12 @@
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
-mod output;
-mod primitives;
-mod render;
-mod scene;
+mod watch;
 
-use scene::Scene;
+use termcad::{output, render, scene};
+
+use scene::{strip_jsonc, ExpressionContext, Scene, Turntable, TurntableAxis};
 
 #[derive(Parser)]
 #[command(name = "termcad")]
@@ -22,49 +21,184 @@ struct Cli {
 enum Commands {
     /// Render a scene to GIF or PNG frames
     Render {
-        /// Scene JSON file
+        /// Scene file (JSON with comments allowed, or YAML with the `yaml` feature)
         scene: PathBuf,
 
-        /// Output file (GIF) or directory (with --frames)
-        #[arg(short, long)]
-        output: Option<PathBuf>,
+        #[command(flatten)]
+        output: OutputArgs,
 
-        /// Output PNG frames to directory instead of GIF
+        /// Render in 16-bit float and tonemap to 8-bit (overrides canvas.hdr)
         #[arg(long)]
-        frames: bool,
+        hdr: bool,
+
+        /// Render a single frame with this expression `frame` (requires --context-total)
+        #[arg(long, requires = "context_total")]
+        context_frame: Option<u32>,
+
+        /// Expression `total_frames` for --context-frame, independent of duration/fps
+        #[arg(long, requires = "context_frame")]
+        context_total: Option<u32>,
 
-        /// Output JSON progress/status
+        /// First frame to render (default: 0)
+        #[arg(long, value_name = "FRAME", conflicts_with = "context_frame")]
+        start: Option<u32>,
+
+        /// Frame to stop before, exclusive (default: total frames)
+        #[arg(long, value_name = "FRAME", conflicts_with = "context_frame")]
+        end: Option<u32>,
+
+        /// Re-render whenever the scene file changes, until Ctrl-C
         #[arg(long)]
-        json: bool,
+        watch: bool,
+    },
+
+    /// Render a 360 degree camera orbit of a scene, ignoring its static camera
+    Turntable {
+        /// Scene file (JSON with comments allowed, or YAML with the `yaml` feature)
+        scene: PathBuf,
+
+        /// Axis to orbit around
+        #[arg(long, value_enum, default_value = "y")]
+        axis: TurntableAxis,
+
+        /// Number of full orbits over the scene duration
+        #[arg(long, default_value_t = 1.0)]
+        revolutions: f32,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+
+    /// Render a single poster frame of a scene to PNG
+    Thumbnail {
+        /// Scene file (JSON with comments allowed, or YAML with the `yaml` feature)
+        scene: PathBuf,
+
+        /// Output PNG file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Point in the timeline to capture, as a 0-1 fraction
+        #[arg(long, default_value_t = 0.5)]
+        at: f32,
     },
 
     /// Validate a scene file without rendering
     Validate {
-        /// Scene JSON file
+        /// Scene file (JSON with comments allowed, or YAML with the `yaml` feature)
         scene: PathBuf,
     },
 
     /// Generate a starter scene
     Init {
-        /// Template name (spinning-cube, grid-flythrough, text-terminal)
+        /// Template name (spinning-cube, spinning-cube-orbit, grid-flythrough, text-terminal,
+        /// orbit-showcase)
         #[arg(long)]
         template: Option<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "json")]
+        format: SceneFormat,
     },
 
+    /// Print a JSON Schema for scene files, for editor validation via `"$schema"`
+    Schema,
+
     /// List available primitives and their parameters
     Primitives {
         /// Specific primitive to show details for
         name: Option<String>,
     },
 
-    /// Show tool info and capabilities
+    /// Show tool info, capabilities, and the GPU adapter renders will use
     Info {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+
+        #[command(flatten)]
+        gpu: GpuArgs,
     },
 }
 
+/// Output and diagnostics options shared by the rendering commands.
+#[derive(Args, Clone)]
+struct OutputArgs {
+    /// Output file (GIF, WebP by extension, or PNG with --sheet) or directory (with --frames)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Output PNG frames to directory instead of GIF
+    #[arg(long)]
+    frames: bool,
+
+    /// Output one PNG sprite sheet with this many columns instead of GIF
+    #[arg(
+        long,
+        value_name = "COLS",
+        conflicts_with = "frames",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    sheet: Option<u32>,
+
+    /// Output JSON progress/status
+    #[arg(long)]
+    json: bool,
+
+    /// Print per-frame diagnostics (e.g. frames with no vertices) to stderr
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Abort with a render error if rendering takes longer than this
+    #[arg(long, value_name = "SECS")]
+    timeout: Option<u64>,
+
+    /// Print a 64-bit perceptual hash of each rendered frame for regression checks
+    #[arg(long)]
+    phash: bool,
+
+    /// GIF dithering: none for flat colors, sierra for smooth gradients
+    #[arg(long, value_enum, default_value = "bayer")]
+    dither: output::Dither,
+
+    /// Maximum GIF palette size
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 256,
+        value_parser = clap::value_parser!(u32).range(4..=256)
+    )]
+    max_colors: u32,
+
+    /// Rasterize on the GPU or CPU; auto uses the CPU only when no GPU adapter works
+    #[arg(long, value_enum, default_value = "auto")]
+    backend: render::RenderBackend,
+
+    #[command(flatten)]
+    gpu: GpuArgs,
+}
+
+/// GPU adapter selection, for when the default adapter is the wrong one.
+#[derive(Args, Clone)]
+struct GpuArgs {
+    /// Only consider adapters on this graphics API (default: WGPU_BACKEND, else any)
+    #[arg(long, value_enum)]
+    gpu_backend: Option<render::GpuBackend>,
+
+    /// Prefer an integrated (low) or discrete (high) GPU
+    #[arg(long, value_enum, default_value = "high")]
+    power: render::PowerPreference,
+}
+
+impl GpuArgs {
+    fn adapter_options(&self) -> render::AdapterOptions {
+        render::AdapterOptions {
+            backend: self.gpu_backend,
+            power: self.power,
+        }
+    }
+}
+
 fn main() -> ExitCode {
     let cli = Cli::parse();
 
@@ -72,13 +206,32 @@ fn main() -> ExitCode {
         Commands::Render {
             scene,
             output,
-            frames,
-            json,
-        } => cmd_render(scene, output, frames, json),
+            hdr,
+            context_frame,
+            context_total,
+            start,
+            end,
+            watch,
+        } => {
+            let frames = match context_frame.zip(context_total) {
+                Some((frame, total)) => FrameSelection::Single { frame, total },
+                None if start.is_some() || end.is_some() => FrameSelection::Range { start, end },
+                None => FrameSelection::All,
+            };
+            cmd_render(scene, output, hdr, frames, watch)
+        }
+        Commands::Turntable {
+            scene,
+            axis,
+            revolutions,
+            output,
+        } => cmd_turntable(scene, axis, revolutions, output),
+        Commands::Thumbnail { scene, output, at } => cmd_thumbnail(scene, output, at),
         Commands::Validate { scene } => cmd_validate(scene),
-        Commands::Init { template } => cmd_init(template),
+        Commands::Init { template, format } => cmd_init(template, format),
+        Commands::Schema => cmd_schema(),
         Commands::Primitives { name } => cmd_primitives(name),
-        Commands::Info { json } => cmd_info(json),
+        Commands::Info { json, gpu } => cmd_info(json, gpu),
     };
 
     match result {
@@ -90,8 +243,9 @@ fn main() -> ExitCode {
     }
 }
 
-use output::{FrameWriteError, GifError};
-use render::RenderError;
+use output::{FrameWriteError, GifError, WebpError};
+use watch::WatchError;
+use render::{RenderBackend, RenderError, RenderProgress};
 use scene::ValidationError;
 use thiserror::Error;
 
@@ -100,8 +254,21 @@ enum TermcadError {
     #[error("Scene validation failed: {0}")]
     Validation(#[from] ValidationError),
 
+    /// `pointer` quotes the offending source line, or is empty when there is no position.
+    #[error("Failed to parse scene: {error}{pointer}")]
+    Parse {
+        #[source]
+        error: serde_json::Error,
+        pointer: String,
+    },
+
+    #[cfg(feature = "yaml")]
     #[error("Failed to parse scene: {0}")]
-    Parse(#[source] serde_json::Error),
+    ParseYaml(#[source] serde_yaml::Error),
+
+    #[cfg(not(feature = "yaml"))]
+    #[error("YAML support is not enabled; rebuild with `--features yaml`")]
+    YamlDisabled,
 
     #[error("Render failed: {0}")]
     Render(#[from] RenderError),
@@ -115,41 +282,265 @@ enum TermcadError {
     #[error("{0}")]
     FrameWrite(#[from] FrameWriteError),
 
+    #[error("{0}")]
+    Webp(#[from] WebpError),
+
     #[error("Failed to serialize: {0}")]
     Serialization(#[source] serde_json::Error),
 
-    #[error("Unknown template: {0}. Available: spinning-cube, grid-flythrough, text-terminal")]
+    #[cfg(feature = "yaml")]
+    #[error("Failed to serialize: {0}")]
+    YamlSerialization(#[source] serde_yaml::Error),
+
+    #[error(
+        "Unknown template: {0}. Available: spinning-cube, spinning-cube-orbit, grid-flythrough, \
+         text-terminal, orbit-showcase"
+    )]
     UnknownTemplate(String),
 
     #[error("Unknown primitive: {0}")]
     UnknownPrimitive(String),
+
+    #[error("{0}")]
+    Watch(#[from] WatchError),
 }
 
 impl TermcadError {
     fn exit_code(&self) -> u8 {
         match self {
-            TermcadError::Validation(_) | TermcadError::Parse(_) => 1,
+            TermcadError::Validation(_) | TermcadError::Parse { .. } => 1,
+            #[cfg(feature = "yaml")]
+            TermcadError::ParseYaml(_) => 1,
+            #[cfg(not(feature = "yaml"))]
+            TermcadError::YamlDisabled => 1,
             TermcadError::Render(_) => 2,
-            TermcadError::Io(_) | TermcadError::FrameWrite(_) => 3,
+            TermcadError::Io(_) | TermcadError::FrameWrite(_) | TermcadError::Webp(_) => 3,
             TermcadError::Gif(GifError::FfmpegNotFound) => 4,
             TermcadError::Gif(_) => 3,
             TermcadError::Serialization(_) => 5,
+            #[cfg(feature = "yaml")]
+            TermcadError::YamlSerialization(_) => 5,
             TermcadError::UnknownTemplate(_) | TermcadError::UnknownPrimitive(_) => 1,
+            TermcadError::Watch(_) => 3,
+        }
+    }
+}
+
+/// Scene file formats, selected by file extension when loading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SceneFormat {
+    Json,
+    Yaml,
+}
+
+impl SceneFormat {
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") => {
+                SceneFormat::Yaml
+            }
+            _ => SceneFormat::Json,
         }
     }
 }
 
+fn load_scene(path: &Path) -> Result<Scene, TermcadError> {
+    let scene_str = std::fs::read_to_string(path)?;
+
+    let mut scene: Scene = match SceneFormat::from_path(path) {
+        SceneFormat::Json => serde_json::from_str(&strip_jsonc(&scene_str)).map_err(|error| {
+            let pointer = error_pointer(path, &scene_str, error.line(), error.column());
+            TermcadError::Parse { error, pointer }
+        }),
+        #[cfg(feature = "yaml")]
+        SceneFormat::Yaml => serde_yaml::from_str(&scene_str).map_err(TermcadError::ParseYaml),
+        #[cfg(not(feature = "yaml"))]
+        SceneFormat::Yaml => Err(TermcadError::YamlDisabled),
+    }?;
+
+    // Model paths are relative to the scene file, not the working directory
+    scene.load_assets(path.parent().unwrap_or(Path::new("")))?;
+    Ok(scene)
+}
+
+/// Quote the source line at a 1-based `line` and `column` with a caret under the column,
+/// rustc style. Empty when the position is outside the source, as for serde errors that
+/// have none.
+fn error_pointer(path: &Path, source: &str, line: usize, column: usize) -> String {
+    let Some(text) = line.checked_sub(1).and_then(|index| source.lines().nth(index)) else {
+        return String::new();
+    };
+
+    // Columns count bytes; keep tabs so the caret lines up under them
+    let indent: String = text
+        .char_indices()
+        .take_while(|&(i, _)| i + 1 < column)
+        .map(|(_, c)| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let number = line.to_string();
+    let gutter = " ".repeat(number.len());
+
+    format!(
+        "\n{gutter}--> {}:{line}:{column}\n{gutter} |\n{number} | {text}\n{gutter} | {indent}^",
+        path.display()
+    )
+}
+
+/// Which frames of the timeline a render produces.
+#[derive(Debug, Clone, Copy)]
+enum FrameSelection {
+    All,
+    /// Frames `start..end` of the scene timeline; missing bounds default to the full range
+    Range {
+        start: Option<u32>,
+        end: Option<u32>,
+    },
+    /// One frame at an external (frame, total) timeline
+    Single { frame: u32, total: u32 },
+}
+
 fn cmd_render(
     scene_path: PathBuf,
-    output: Option<PathBuf>,
-    frames_mode: bool,
-    json_output: bool,
+    output: OutputArgs,
+    hdr: bool,
+    frames: FrameSelection,
+    watch: bool,
+) -> Result<(), TermcadError> {
+    if !watch {
+        return render_path(&scene_path, output, hdr, frames);
+    }
+
+    let json_output = output.json;
+    let render = || {
+        // Keep watching through bad edits so the next save can fix them
+        if let Err(e) = render_path(&scene_path, output.clone(), hdr, frames) {
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::json!({"status": "error", "message": e.to_string()})
+                );
+            } else {
+                eprintln!("{}", e);
+            }
+        }
+        if json_output {
+            println!("{}", serde_json::json!({"status": "watching"}));
+        } else {
+            println!("Watching {} for changes (Ctrl-C to stop)", scene_path.display());
+        }
+    };
+
+    render();
+    watch::watch_file(&scene_path, std::time::Duration::from_millis(200), render)?;
+    Ok(())
+}
+
+fn render_path(
+    scene_path: &Path,
+    output: OutputArgs,
+    hdr: bool,
+    frames: FrameSelection,
 ) -> Result<(), TermcadError> {
     // Load and parse scene
-    let scene_str = std::fs::read_to_string(&scene_path)?;
+    let mut scene = load_scene(scene_path)?;
 
-    let scene: Scene =
-        serde_json::from_str(&scene_str).map_err(TermcadError::Parse)?;
+    if hdr {
+        scene.canvas.hdr = true;
+    }
+
+    match frames {
+        FrameSelection::Single { frame, total } if frame >= total => {
+            return Err(ValidationError::InvalidValue(
+                "--context-frame must be less than --context-total".to_string(),
+            )
+            .into());
+        }
+        FrameSelection::Range { start, end } => {
+            let total = scene.total_frames();
+            let (start, end) = (start.unwrap_or(0), end.unwrap_or(total));
+            if start >= end || end > total {
+                return Err(ValidationError::InvalidValue(format!(
+                    "--start/--end must satisfy start < end <= {} (got {}..{})",
+                    total, start, end
+                ))
+                .into());
+            }
+        }
+        _ => {}
+    }
+
+    render_scene(scene_path, &scene, output, frames)
+}
+
+fn cmd_turntable(
+    scene_path: PathBuf,
+    axis: TurntableAxis,
+    revolutions: f32,
+    output: OutputArgs,
+) -> Result<(), TermcadError> {
+    let mut scene = load_scene(&scene_path)?;
+    scene.camera.turntable = Some(Turntable { axis, revolutions });
+
+    render_scene(&scene_path, &scene, output, FrameSelection::All)
+}
+
+fn cmd_thumbnail(
+    scene_path: PathBuf,
+    output: Option<PathBuf>,
+    at: f32,
+) -> Result<(), TermcadError> {
+    if !(0.0..=1.0).contains(&at) {
+        return Err(ValidationError::InvalidValue(format!(
+            "--at must be between 0 and 1 (got {})",
+            at
+        ))
+        .into());
+    }
+
+    let scene = load_scene(&scene_path)?;
+    scene.validate()?;
+
+    let output_path = output.unwrap_or_else(|| {
+        let stem = scene_path.file_stem().unwrap_or_default();
+        default_output_dir().join(format!("{}.png", stem.to_string_lossy()))
+    });
+
+    let renderer = render::Renderer::new(&scene)?;
+    let frame = renderer.render_frame_at(at)?;
+    frame
+        .save(&output_path)
+        .map_err(|e| FrameWriteError::WriteError(format!("{}: {}", output_path.display(), e)))?;
+
+    println!("Wrote {}", output_path.display());
+    Ok(())
+}
+
+/// Default output directory: Videos first, then Downloads, then the current directory.
+fn default_output_dir() -> PathBuf {
+    dirs::video_dir()
+        .or_else(dirs::download_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn render_scene(
+    scene_path: &Path,
+    scene: &Scene,
+    output: OutputArgs,
+    selection: FrameSelection,
+) -> Result<(), TermcadError> {
+    let OutputArgs {
+        output,
+        frames: frames_mode,
+        sheet,
+        json: json_output,
+        verbose,
+        timeout,
+        phash,
+        dither,
+        max_colors,
+        backend,
+        gpu,
+    } = output;
 
     // Validate scene
     scene.validate()?;
@@ -159,16 +550,13 @@ fn cmd_render(
         let stem = scene_path.file_stem().unwrap_or_default();
         let filename = if frames_mode {
             format!("{}_frames", stem.to_string_lossy())
+        } else if sheet.is_some() {
+            format!("{}_sheet.png", stem.to_string_lossy())
         } else {
             format!("{}.gif", stem.to_string_lossy())
         };
 
-        // Try Videos first, then Downloads, then current directory
-        let base_dir = dirs::video_dir()
-            .or_else(dirs::download_dir)
-            .unwrap_or_else(|| PathBuf::from("."));
-
-        base_dir.join(filename)
+        default_output_dir().join(filename)
     });
 
     // Render
@@ -179,12 +567,57 @@ fn cmd_render(
         );
     }
 
-    let renderer = render::Renderer::new(&scene)?;
-    let frames = renderer.render_all(json_output)?;
+    let mut renderer = render::Renderer::with_backend(scene, backend, &gpu.adapter_options())?;
+    if backend == RenderBackend::Auto && renderer.backend() == RenderBackend::Cpu {
+        eprintln!("warning: no usable GPU adapter; rendering on the CPU without post effects");
+    }
+    renderer.set_verbose(verbose);
+    renderer.set_timeout(timeout.map(std::time::Duration::from_secs));
+
+    let print_progress = |progress: RenderProgress| {
+        if json_output {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "status": "rendering",
+                    "frame": progress.frame + 1,
+                    "total": progress.total
+                })
+            );
+        }
+    };
+
+    let (mut frames, first_frame) = match selection {
+        FrameSelection::All => (renderer.render_all(print_progress)?, 0),
+        FrameSelection::Range { start, end } => {
+            let start = start.unwrap_or(0);
+            let end = end.unwrap_or(scene.total_frames());
+            (renderer.render_range(start, end, print_progress)?, start as usize)
+        }
+        // External timeline: render exactly one frame at the given (frame, total)
+        FrameSelection::Single { frame, total } => {
+            let ctx = ExpressionContext::new(frame, total);
+            (vec![renderer.render_frame(&ctx)?], 0)
+        }
+    };
+
+    if phash {
+        for (i, frame) in (first_frame..).zip(&frames) {
+            let hash = format!("{:016x}", output::phash(frame));
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::json!({"status": "phash", "frame": i, "hash": hash})
+                );
+            } else {
+                println!("frame {}: {}", i, hash);
+            }
+        }
+    }
 
     if frames_mode {
         // Output PNG frames
-        output::write_frames(&output_path, &frames)?;
+        output::write_frames(&output_path, &frames, first_frame)?;
 
         if json_output {
             println!(
@@ -202,13 +635,66 @@ fn cmd_render(
                 output_path.display()
             );
         }
+    } else if let Some(cols) = sheet {
+        // Output all frames tiled into one PNG
+        output::write_sprite_sheet(&output_path, &frames, cols)?;
+
+        if json_output {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "status": "complete",
+                    "output": output_path.to_string_lossy(),
+                    "frames": frames.len()
+                })
+            );
+        } else {
+            println!(
+                "Wrote {} frames as a sprite sheet to {}",
+                frames.len(),
+                output_path.display()
+            );
+        }
+    } else if is_webp(&output_path) {
+        // Animated WebP keeps full color and alpha, so no palette or flattening
+        if json_output {
+            println!("{}", serde_json::json!({"status": "assembling"}));
+        }
+
+        let size_bytes = output::assemble_webp(&output_path, &frames, scene.fps, scene.repeats())?;
+
+        if json_output {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "status": "complete",
+                    "output": output_path.to_string_lossy(),
+                    "frames": frames.len(),
+                    "size_bytes": size_bytes
+                })
+            );
+        } else {
+            println!("Wrote {} ({} frames)", output_path.display(), frames.len());
+        }
     } else {
         // Assemble GIF
         if json_output {
             println!("{}", serde_json::json!({"status": "assembling"}));
         }
 
-        let size_bytes = output::assemble_gif(&output_path, &frames, scene.fps)?;
+        // GIF has no partial alpha, so transparent canvases flatten onto the background
+        if scene.canvas.transparent {
+            let background =
+                scene.canvas.background.base_color().unwrap_or([0.04, 0.04, 0.04, 1.0]);
+            output::flatten_frames(&mut frames, background);
+        }
+
+        let gif_options = output::GifOptions {
+            dither,
+            max_colors,
+            repeats: scene.repeats(),
+        };
+        let size_bytes = output::assemble_gif(&output_path, &frames, scene.fps, gif_options)?;
 
         if json_output {
             println!(
@@ -228,45 +714,92 @@ fn cmd_render(
     Ok(())
 }
 
-fn cmd_validate(scene_path: PathBuf) -> Result<(), TermcadError> {
-    let scene_str = std::fs::read_to_string(&scene_path)?;
+/// Whether an output path asks for an animated WebP instead of a GIF.
+fn is_webp(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("webp"))
+}
 
-    let scene: Scene =
-        serde_json::from_str(&scene_str).map_err(TermcadError::Parse)?;
+fn cmd_validate(scene_path: PathBuf) -> Result<(), TermcadError> {
+    let scene = load_scene(&scene_path)?;
 
     scene.validate()?;
 
     println!("Scene is valid");
     println!("  Canvas: {}x{}", scene.canvas.width, scene.canvas.height);
+    if let Some(viewport) = scene.canvas.viewport {
+        println!(
+            "  Viewport: {}x{} at ({}, {})",
+            viewport.width, viewport.height, viewport.x, viewport.y
+        );
+    }
     println!("  Duration: {}s @ {} fps", scene.duration, scene.fps);
     println!("  Total frames: {}", scene.total_frames());
-    println!("  Elements: {}", scene.elements.len());
+
+    // total_frames() rounds up, which can stretch playback past the requested duration
+    let effective = scene.effective_duration();
+    if (effective - scene.duration).abs() > 1e-4 {
+        println!(
+            "  Note: {} frames @ {} fps play for {:.3}s (requested {}s); frame count is rounded up",
+            scene.total_frames(),
+            scene.fps,
+            effective,
+            scene.duration
+        );
+    }
+
+    let disabled = scene.elements.iter().filter(|e| !e.is_enabled()).count();
+    if disabled > 0 {
+        println!("  Elements: {} ({} disabled)", scene.elements.len(), disabled);
+    } else {
+        println!("  Elements: {}", scene.elements.len());
+    }
 
     Ok(())
 }
 
-fn cmd_init(template: Option<String>) -> Result<(), TermcadError> {
+fn cmd_init(template: Option<String>, format: SceneFormat) -> Result<(), TermcadError> {
     let scene = match template.as_deref() {
         Some("spinning-cube") | None => scene::templates::spinning_cube(),
         Some("grid-flythrough") => scene::templates::grid_flythrough(),
         Some("text-terminal") => scene::templates::text_terminal(),
+        Some("spinning-cube-orbit") => scene::templates::spinning_cube_orbit(),
+        Some("orbit-showcase") => scene::templates::orbit_showcase(),
         Some(name) => {
             return Err(TermcadError::UnknownTemplate(name.to_string()));
         }
     };
 
-    let json = serde_json::to_string_pretty(&scene).map_err(TermcadError::Serialization)?;
-    println!("{}", json);
+    let output = match format {
+        SceneFormat::Json => {
+            serde_json::to_string_pretty(&scene).map_err(TermcadError::Serialization)?
+        }
+        #[cfg(feature = "yaml")]
+        SceneFormat::Yaml => serde_yaml::to_string(&scene).map_err(TermcadError::YamlSerialization)?,
+        #[cfg(not(feature = "yaml"))]
+        SceneFormat::Yaml => return Err(TermcadError::YamlDisabled),
+    };
+    println!("{}", output);
+    Ok(())
+}
+
+fn cmd_schema() -> Result<(), TermcadError> {
+    let output =
+        serde_json::to_string_pretty(&scene_schema()).map_err(TermcadError::Serialization)?;
+    println!("{}", output);
     Ok(())
 }
 
+fn scene_schema() -> schemars::schema::RootSchema {
+    schemars::schema_for!(Scene)
+}
+
 fn cmd_primitives(name: Option<String>) -> Result<(), TermcadError> {
     match name.as_deref() {
         None => {
             println!("Available primitives:");
             println!();
             println!("  grid        Infinite perspective plane");
-            println!("  wireframe   Edge-only geometry (cube, sphere, torus, ico, cylinder)");
+            println!("  wireframe   Edge-only geometry (cube, sphere, torus, ico, cylinder, ...)");
             println!("  glyph       Monospace text in 3D space");
             println!("  line        Vector path with glow");
             println!("  particles   Scattered point field");
@@ -280,19 +813,40 @@ fn cmd_primitives(name: Option<String>) -> Result<(), TermcadError> {
             println!("Parameters:");
             println!("  divisions       Number of grid lines (default: 20)");
             println!("  fade_distance   Distance at which grid fades out (default: 50.0)");
-            println!("  color           Hex color (default: \"#00ff41\")");
+            println!("  color           Hex/name or {{ r, g, b }} (default: \"#00ff41\")");
             println!("  opacity         0.0 to 1.0 (default: 0.5)");
+            println!("  style           rect or polar rings and spokes (default: rect)");
+            println!("  plane           xz floor, xy or yz wall (default: xz)");
+            println!("  jitter          Random endpoint wobble in world units (default: 0.0)");
+            println!("  seed            Jitter pattern seed (default: 0)");
         }
         Some("wireframe") => {
             println!("wireframe - Edge-only geometry");
             println!();
             println!("Parameters:");
-            println!("  geometry    Shape: cube, sphere, torus, ico, cylinder");
+            println!("  geometry    Shape: cube, sphere, torus, ico, cylinder,");
+            println!("              tetrahedron, octahedron, dodecahedron,");
+            println!("              or {{ \"obj\": {{ \"path\": \"model.obj\" }} }}");
+            println!("              or {{ \"custom\": {{ \"vertices\": [...], \"edges\": [...] }} }}");
             println!("  scale       Uniform scale or [x, y, z] (default: 1.0)");
-            println!("  color       Hex color (default: \"#00ff41\")");
+            println!("  color       Hex/name or {{ r, g, b }} (default: \"#00ff41\")");
             println!("  thickness   Line width in pixels (default: 2.0)");
             println!("  position    [x, y, z] (default: [0, 0, 0])");
             println!("  rotation    {{ x, y, z }} in degrees, supports expressions");
+            println!("              or {{ \"axis\": [x, y, z], \"angle\": degrees }}");
+            println!("  rotation_order  Axis order: xyz, xzy, yxz, yzx, zxy, zyx (default: yxz)");
+            println!("  segments    Sphere/cylinder segments (default: 16)");
+            println!("  rings       Sphere rings (default: 12)");
+            println!("  tube_segments    Torus tube segments (default: 24)");
+            println!("  radial_segments  Torus ring segments (default: 12)");
+            println!("  major_radius     Torus ring radius (default: 1.0)");
+            println!("  minor_radius     Torus tube radius (default: 0.3)");
+            println!("  caps        Draw cylinder end-cap spokes (default: false)");
+            println!("  hidden_line Dim edges on the far side of the shape (default: false)");
+            println!("  hidden_line_opacity  Opacity of hidden edges, 0 removes (default: 0.15)");
+            println!("  edge_colors [{{ edge, color }}] per-edge color overrides (default: [])");
+            println!("  repeat      {{ count, offset, rotation_step }} draws stepped copies;");
+            println!("              expressions see the copy as `instance`");
         }
         Some("glyph") => {
             println!("glyph - Monospace text in 3D space");
@@ -301,8 +855,13 @@ fn cmd_primitives(name: Option<String>) -> Result<(), TermcadError> {
             println!("  text        Text string to display");
             println!("  font_size   Size in world units (default: 1.0)");
             println!("  position    [x, y, z] (default: [0, 0, 0])");
-            println!("  color       Hex color (default: \"#00ff41\")");
+            println!("  color       Hex/name or {{ r, g, b }} (default: \"#00ff41\")");
             println!("  animation   \"type\", \"flicker\", or \"none\" (default: \"none\")");
+            println!("  curve_segments  Segments per curved stroke (default: 1)");
+            println!("  line_spacing    Multi-line text spacing x font_size (default: 1.4)");
+            println!("  align       \"left\", \"center\", or \"right\" (default: \"center\")");
+            println!("  gradient    {{ from, to }} hex colors ramped per character (optional)");
+            println!("  billboard   Always face the camera (default: false)");
         }
         Some("line") => {
             println!("line - Vector path with glow");
@@ -312,7 +871,11 @@ fn cmd_primitives(name: Option<String>) -> Result<(), TermcadError> {
             println!("  closed      Connect last point to first (default: false)");
             println!("  thickness   Line width in pixels (default: 2.0)");
             println!("  glow        Glow intensity 0.0-1.0 (default: 0.5)");
-            println!("  color       Hex color (default: \"#00ff41\")");
+            println!("  smooth_segments  Catmull-Rom subdivisions per span (default: 0, sharp)");
+            println!("  draw        Fraction of the path revealed, e.g. \"t\" (default: 1.0)");
+            println!("  dash        {{ \"on\", \"off\" }} lengths in world units (default: solid)");
+            println!("  color       Hex/name or {{ r, g, b }} (default: \"#00ff41\")");
+            println!("  colors      One color per point, blended along the path (optional)");
         }
         Some("particles") => {
             println!("particles - Scattered point field");
@@ -320,18 +883,22 @@ fn cmd_primitives(name: Option<String>) -> Result<(), TermcadError> {
             println!("Parameters:");
             println!("  count       Number of particles (default: 100)");
             println!("  bounds      [x, y, z] extents (default: [10, 10, 10])");
-            println!("  size        Particle size in pixels (default: 2.0)");
+            println!("  size        On-screen size in pixels at any depth (default: 2.0)");
             println!("  depth_fade  Fade based on depth (default: true)");
-            println!("  color       Hex color (default: \"#00ff41\")");
+            println!("  shape       cross, dot, square, or star (default: cross)");
+            println!("  distribution  box, sphere, or disk of radius bounds[0] (default: box)");
+            println!("  color       Hex/name or {{ r, g, b }} (default: \"#00ff41\")");
         }
         Some("axes") => {
             println!("axes - XYZ indicator");
             println!();
             println!("Parameters:");
             println!("  length      Axis length (default: 1.0)");
-            println!("  colors      {{ x, y, z }} hex colors");
+            println!("  colors      {{ x, y, z }} hex or named colors");
             println!("  position    [x, y, z] (default: [0, 0, 0])");
             println!("  thickness   Line width in pixels (default: 2.0)");
+            println!("  arrow_min   Minimum arrowhead size in world units (default: 0.05)");
+            println!("  arrow_max   Maximum arrowhead size in world units (default: 0.5)");
         }
         Some(name) => {
             return Err(TermcadError::UnknownPrimitive(name.to_string()));
@@ -340,21 +907,61 @@ fn cmd_primitives(name: Option<String>) -> Result<(), TermcadError> {
     Ok(())
 }
 
-fn cmd_info(json: bool) -> Result<(), TermcadError> {
+fn cmd_info(json: bool, gpu: GpuArgs) -> Result<(), TermcadError> {
+    // A missing GPU is worth reporting here, not failing on
+    let selected = render::selected_adapter(&gpu.adapter_options());
+    let adapters = render::available_adapters();
+
     if json {
+        let describe = |adapter: &render::AdapterSummary| {
+            serde_json::json!({
+                "name": adapter.name,
+                "backend": adapter.backend,
+                "device_type": adapter.device_type,
+                "driver": adapter.driver,
+            })
+        };
         println!(
             "{}",
             serde_json::json!({
                 "name": "termcad",
                 "version": env!("CARGO_PKG_VERSION"),
                 "primitives": ["grid", "wireframe", "glyph", "line", "particles", "axes"],
-                "geometries": ["cube", "sphere", "torus", "ico", "cylinder"],
-                "post_effects": ["bloom", "scanlines", "chromatic_aberration", "noise", "vignette", "crt_curvature"],
-                "output_formats": ["gif", "png"],
+                "geometries": [
+                    "cube",
+                    "sphere",
+                    "torus",
+                    "ico",
+                    "cylinder",
+                    "tetrahedron",
+                    "octahedron",
+                    "dodecahedron"
+                ],
+                "post_effects": [
+                    "bloom",
+                    "scanlines",
+                    "chromatic_aberration",
+                    "noise",
+                    "vignette",
+                    "crt_curvature",
+                    "lens_distortion",
+                    "dot_mask",
+                    "glitch",
+                    "persistence",
+                    "pixelate",
+                    "color_grading"
+                ],
+                "output_formats": ["gif", "webp", "png", "sprite_sheet"],
                 "features": {
                     "animation_expressions": true,
                     "json_output": true,
-                    "headless_rendering": true
+                    "headless_rendering": true,
+                    "hdr": true
+                },
+                "gpu": {
+                    "adapter": selected.as_ref().ok().map(describe),
+                    "error": selected.as_ref().err().map(|e| e.to_string()),
+                    "adapters": adapters.iter().map(describe).collect::<Vec<_>>(),
                 }
             })
         );
@@ -364,9 +971,30 @@ fn cmd_info(json: bool) -> Result<(), TermcadError> {
         println!("Terminal CAD aesthetic GIF generator");
         println!();
         println!("Primitives: grid, wireframe, glyph, line, particles, axes");
-        println!("Geometries: cube, sphere, torus, ico, cylinder");
-        println!("Post-effects: bloom, scanlines, chromatic_aberration, noise, vignette");
-        println!("Output: GIF, PNG frames");
+        println!(
+            "Geometries: cube, sphere, torus, ico, cylinder, tetrahedron, octahedron, dodecahedron"
+        );
+        println!(
+            "Post-effects: bloom, scanlines, chromatic_aberration, noise, vignette, crt_curvature,"
+        );
+        println!(
+            "              lens_distortion, dot_mask, glitch, persistence, pixelate, color_grading"
+        );
+        println!("Output: GIF, WebP, PNG frames, PNG sprite sheet");
+        println!();
+        match &selected {
+            Ok(adapter) => println!(
+                "GPU: {} ({}, {})",
+                adapter.name, adapter.backend, adapter.device_type
+            ),
+            Err(e) => println!("GPU: none ({})", e),
+        }
+        for adapter in &adapters {
+            println!(
+                "  available: {} ({}, {}) {}",
+                adapter.name, adapter.backend, adapter.device_type, adapter.driver
+            );
+        }
     }
     Ok(())
 }
@@ -386,10 +1014,173 @@ mod tests {
     #[test]
     fn test_parse_error_exit_code() {
         let json_err = serde_json::from_str::<Scene>("invalid").unwrap_err();
-        let err = TermcadError::Parse(json_err);
+        let err = TermcadError::Parse {
+            error: json_err,
+            pointer: String::new(),
+        };
         assert_eq!(err.exit_code(), 1);
     }
 
+    #[test]
+    fn test_error_pointer_marks_column() {
+        let source = "{\n  \"canvas\": {},\n  \"fps\": 30\n  \"duration\": 2\n}";
+        let error = serde_json::from_str::<Scene>(source).unwrap_err();
+        let pointer = error_pointer(Path::new("scene.json"), source, error.line(), error.column());
+
+        let lines: Vec<&str> = pointer.lines().collect();
+        assert_eq!(lines[1], format!(" --> scene.json:4:{}", error.column()));
+        assert_eq!(lines[3], "4 |   \"duration\": 2");
+        assert_eq!(lines[4], format!("  | {}^", " ".repeat(error.column() - 1)));
+    }
+
+    #[test]
+    fn test_error_pointer_without_position() {
+        assert_eq!(error_pointer(Path::new("s.json"), "{}", 0, 0), "");
+        assert_eq!(error_pointer(Path::new("s.json"), "{}", 5, 1), "");
+    }
+
+    #[test]
+    fn test_render_timeout_exit_code() {
+        let err = TermcadError::Render(RenderError::Timeout(std::time::Duration::from_secs(5)));
+        assert_eq!(err.exit_code(), 2);
+        assert!(format!("{}", err).contains("5s"));
+    }
+
+    #[test]
+    fn test_frame_range_args() {
+        let args = ["termcad", "render", "s.json", "--start", "10", "--end", "20"];
+        let cli = Cli::try_parse_from(args).expect("range args should parse");
+        match cli.command {
+            Commands::Render { start, end, .. } => assert_eq!((start, end), (Some(10), Some(20))),
+            _ => panic!("Expected render command"),
+        }
+
+        let conflicting = Cli::try_parse_from([
+            "termcad",
+            "render",
+            "s.json",
+            "--start",
+            "1",
+            "--context-frame",
+            "0",
+            "--context-total",
+            "10",
+        ]);
+        assert!(conflicting.is_err());
+    }
+
+    #[test]
+    fn test_gif_palette_args() {
+        let args = ["termcad", "render", "s.json", "--dither", "sierra", "--max-colors", "64"];
+        let cli = Cli::try_parse_from(args).expect("palette args should parse");
+        match cli.command {
+            Commands::Render { output: args, .. } => {
+                assert_eq!(args.dither, output::Dither::Sierra);
+                assert_eq!(args.max_colors, 64);
+            }
+            _ => panic!("Expected render command"),
+        }
+
+        let too_few = Cli::try_parse_from(["termcad", "render", "s.json", "--max-colors", "2"]);
+        assert!(too_few.is_err());
+    }
+
+    #[test]
+    fn test_backend_arg() {
+        let cli = Cli::try_parse_from(["termcad", "render", "s.json", "--backend", "cpu"])
+            .expect("backend should parse");
+        match cli.command {
+            Commands::Render { output: args, .. } => {
+                assert_eq!(args.backend, RenderBackend::Cpu);
+            }
+            _ => panic!("Expected render command"),
+        }
+
+        let cli = Cli::try_parse_from(["termcad", "render", "s.json"]).unwrap();
+        match cli.command {
+            Commands::Render { output: args, .. } => {
+                assert_eq!(args.backend, RenderBackend::Auto);
+            }
+            _ => panic!("Expected render command"),
+        }
+    }
+
+    #[test]
+    fn test_gpu_adapter_args() {
+        let args = ["termcad", "render", "s.json", "--gpu-backend", "vulkan", "--power", "low"];
+        let cli = Cli::try_parse_from(args).expect("adapter args should parse");
+        match cli.command {
+            Commands::Render { output: args, .. } => {
+                let options = args.gpu.adapter_options();
+                assert_eq!(options.backend, Some(render::GpuBackend::Vulkan));
+                assert_eq!(options.power, render::PowerPreference::Low);
+            }
+            _ => panic!("Expected render command"),
+        }
+
+        let cli = Cli::try_parse_from(["termcad", "info", "--power", "low"]).unwrap();
+        match cli.command {
+            Commands::Info { gpu, .. } => {
+                assert_eq!(gpu.adapter_options().backend, None);
+                assert_eq!(gpu.power, render::PowerPreference::Low);
+            }
+            _ => panic!("Expected info command"),
+        }
+    }
+
+    #[test]
+    fn test_sheet_args() {
+        let cli = Cli::try_parse_from(["termcad", "render", "s.json", "--sheet", "8"])
+            .expect("sheet args should parse");
+        match cli.command {
+            Commands::Render { output: args, .. } => assert_eq!(args.sheet, Some(8)),
+            _ => panic!("Expected render command"),
+        }
+
+        let zero = Cli::try_parse_from(["termcad", "render", "s.json", "--sheet", "0"]);
+        assert!(zero.is_err());
+        let with_frames =
+            Cli::try_parse_from(["termcad", "render", "s.json", "--sheet", "4", "--frames"]);
+        assert!(with_frames.is_err());
+    }
+
+    #[test]
+    fn test_thumbnail_args() {
+        let cli = Cli::try_parse_from(["termcad", "thumbnail", "s.json"])
+            .expect("thumbnail args should parse");
+        match cli.command {
+            Commands::Thumbnail { at, output, .. } => {
+                assert_eq!(at, 0.5);
+                assert!(output.is_none());
+            }
+            _ => panic!("Expected thumbnail command"),
+        }
+
+        let cli = Cli::try_parse_from(["termcad", "thumbnail", "s.json", "--at", "0.25"])
+            .expect("thumbnail args should parse");
+        match cli.command {
+            Commands::Thumbnail { at, .. } => assert_eq!(at, 0.25),
+            _ => panic!("Expected thumbnail command"),
+        }
+    }
+
+    #[test]
+    fn test_scene_schema_covers_elements() {
+        let schema = serde_json::to_value(scene_schema()).unwrap();
+        assert!(schema["properties"]["elements"].is_object());
+
+        // Internally tagged, so each element's fields are inlined under its `type` tag
+        let variants = schema["definitions"]["Element"]["oneOf"].as_array().unwrap();
+        let tags: Vec<&str> = variants
+            .iter()
+            .filter_map(|variant| variant["properties"]["type"]["enum"][0].as_str())
+            .collect();
+        assert_eq!(
+            tags,
+            vec!["grid", "wireframe", "glyph", "line", "particles", "axes"]
+        );
+    }
+
     #[test]
     fn test_io_error_exit_code() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
@@ -452,6 +1243,32 @@ mod tests {
         assert!(matches!(termcad_err, TermcadError::Gif(_)));
     }
 
+    #[test]
+    fn test_is_webp() {
+        assert!(is_webp(Path::new("out.webp")));
+        assert!(is_webp(Path::new("out.WebP")));
+        assert!(!is_webp(Path::new("out.gif")));
+        assert!(!is_webp(Path::new("webp")));
+    }
+
+    #[test]
+    fn test_scene_format_from_extension() {
+        assert_eq!(SceneFormat::from_path(Path::new("scene.json")), SceneFormat::Json);
+        assert_eq!(SceneFormat::from_path(Path::new("scene.yaml")), SceneFormat::Yaml);
+        assert_eq!(SceneFormat::from_path(Path::new("scene.YML")), SceneFormat::Yaml);
+        assert_eq!(SceneFormat::from_path(Path::new("scene")), SceneFormat::Json);
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_parse_yaml_scene() {
+        let yaml = "canvas:\n  width: 320\n  height: 240\nelements:\n  - type: wireframe\n    geometry: ico\n    rotation:\n      y: t * 360\n";
+        let scene: Scene = serde_yaml::from_str(yaml).expect("yaml scene should parse");
+        assert_eq!(scene.canvas.width, 320);
+        assert_eq!(scene.elements.len(), 1);
+        assert!(scene.validate().is_ok());
+    }
+
     #[test]
     fn test_error_from_frame_write() {
         let frame_err = FrameWriteError::DirectoryError("test".to_string());
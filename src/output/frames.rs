@@ -1,3 +1,4 @@
+use rayon::prelude::*;
 use std::path::Path;
 use thiserror::Error;
 
@@ -10,24 +11,112 @@ pub enum FrameWriteError {
     WriteError(String),
 }
 
+/// Write `frames` as numbered PNGs, numbering from `first_frame` so partial
+/// renders keep their absolute frame indices.
 pub fn write_frames(
     output_dir: &Path,
     frames: &[image::RgbaImage],
+    first_frame: usize,
 ) -> Result<(), FrameWriteError> {
+    if frames.is_empty() {
+        return Err(FrameWriteError::WriteError("no frames to write".to_string()));
+    }
+
     // Create output directory
     std::fs::create_dir_all(output_dir)
         .map_err(|e| FrameWriteError::DirectoryError(e.to_string()))?;
 
-    let num_digits = (frames.len() as f32).log10().ceil() as usize;
+    let num_digits = num_digits((first_frame + frames.len()).saturating_sub(1));
 
-    for (i, frame) in frames.iter().enumerate() {
-        let filename = format!("frame_{:0width$}.png", i, width = num_digits);
+    // Each frame encodes to its own file, so PNG compression runs on every core
+    frames.par_iter().enumerate().try_for_each(|(i, frame)| {
+        let filename = format!("frame_{:0width$}.png", first_frame + i, width = num_digits);
         let path = output_dir.join(filename);
 
         frame
             .save(&path)
-            .map_err(|e| FrameWriteError::WriteError(format!("{}: {}", path.display(), e)))?;
+            .map_err(|e| FrameWriteError::WriteError(format!("{}: {}", path.display(), e)))
+    })
+}
+
+/// Zero-padded width for frame numbers up to `max_index`, at least one digit.
+pub(crate) fn num_digits(max_index: usize) -> usize {
+    max_index.max(1).ilog10() as usize + 1
+}
+
+/// Composite frames with transparency onto an opaque background, for formats
+/// like GIF that have no partial alpha.
+pub fn flatten_frames(frames: &mut [image::RgbaImage], background: [f32; 4]) {
+    let bg = background.map(|c| c.clamp(0.0, 1.0) * 255.0);
+
+    for frame in frames {
+        for pixel in frame.pixels_mut() {
+            let alpha = pixel[3] as f32 / 255.0;
+            for (channel, bg) in pixel.0[..3].iter_mut().zip(bg) {
+                *channel = (*channel as f32 * alpha + bg * (1.0 - alpha)).round() as u8;
+            }
+            pixel[3] = 255;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_num_digits() {
+        assert_eq!(num_digits(0), 1);
+        assert_eq!(num_digits(9), 1);
+        assert_eq!(num_digits(10), 2);
+        assert_eq!(num_digits(99), 2);
+        assert_eq!(num_digits(999), 3);
     }
 
-    Ok(())
+    #[test]
+    fn test_write_frames_rejects_empty() {
+        let dir = std::env::temp_dir().join(format!("termcad_empty_{}", std::process::id()));
+        let result = write_frames(&dir, &[], 0);
+        assert!(
+            matches!(result, Err(FrameWriteError::WriteError(msg)) if msg.contains("no frames"))
+        );
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_write_frames_numbering() {
+        let frame = image::RgbaImage::new(1, 1);
+        for (count, first, last) in [
+            (1, "frame_0.png", "frame_0.png"),
+            (10, "frame_0.png", "frame_9.png"),
+            (100, "frame_00.png", "frame_99.png"),
+            (1000, "frame_099.png", "frame_999.png"),
+        ] {
+            let dir = std::env::temp_dir()
+                .join(format!("termcad_numbering_{}_{}", std::process::id(), count));
+            let frames = vec![frame.clone(); count];
+            write_frames(&dir, &frames, 0).unwrap();
+
+            let files = std::fs::read_dir(&dir).unwrap().count();
+            assert_eq!(files, count);
+            assert!(dir.join(first).exists(), "missing {}", first);
+            assert!(dir.join(last).exists(), "missing {}", last);
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_flatten_frames_composites_alpha() {
+        let mut frames = vec![image::RgbaImage::from_raw(
+            3,
+            1,
+            vec![0, 0, 0, 0, 255, 255, 255, 255, 255, 0, 0, 128],
+        )
+        .unwrap()];
+        flatten_frames(&mut frames, [0.0, 0.0, 1.0, 1.0]);
+
+        assert_eq!(frames[0].get_pixel(0, 0).0, [0, 0, 255, 255]);
+        assert_eq!(frames[0].get_pixel(1, 0).0, [255, 255, 255, 255]);
+        assert_eq!(frames[0].get_pixel(2, 0).0, [128, 0, 127, 255]);
+    }
 }
@@ -2,6 +2,8 @@ use std::path::Path;
 use std::process::Command;
 use thiserror::Error;
 
+use super::frames::num_digits;
+
 #[derive(Debug, Error)]
 pub enum GifError {
     #[error("ffmpeg not found. Please install ffmpeg and ensure it's in your PATH")]
@@ -21,6 +23,68 @@ pub enum GifError {
 
     #[error("Invalid path (contains non-UTF8 characters): {0}")]
     InvalidPath(String),
+
+    #[error("Failed to write output file: {0}")]
+    OutputWriteError(String),
+
+    #[error("Failed to retime GIF: {0}")]
+    RetimeError(String),
+}
+
+/// Dithering applied when mapping frames onto the GIF palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Dither {
+    /// Nearest palette color; cleanest for flat-color scenes
+    None,
+    /// Ordered 8x8 Bayer pattern
+    #[default]
+    Bayer,
+    /// Sierra-2-4A error diffusion; smoothest on gradients
+    Sierra,
+}
+
+/// Palette settings for [`assemble_gif`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GifOptions {
+    pub dither: Dither,
+    /// Palette size, 4 to 256 colors
+    pub max_colors: u32,
+    /// Extra plays after the first; `Some(0)` repeats forever and `None` plays once,
+    /// stopping on the last frame
+    pub repeats: Option<u32>,
+}
+
+impl Default for GifOptions {
+    fn default() -> Self {
+        Self {
+            dither: Dither::Bayer,
+            max_colors: 256,
+            repeats: Some(0),
+        }
+    }
+}
+
+impl GifOptions {
+    /// ffmpeg filter for the palette generation pass
+    fn palettegen_filter(&self) -> String {
+        format!("palettegen=stats_mode=full:max_colors={}", self.max_colors)
+    }
+
+    /// ffmpeg filter for the palette mapping pass
+    fn paletteuse_filter(&self) -> String {
+        let dither = match self.dither {
+            Dither::None => "none",
+            Dither::Bayer => "bayer:bayer_scale=5",
+            Dither::Sierra => "sierra2_4a",
+        };
+        format!("paletteuse=dither={}:diff_mode=rectangle", dither)
+    }
+
+    /// ffmpeg GIF muxer `-loop` value: 0 repeats forever, N repeats N more times,
+    /// and -1 plays once
+    fn loop_count(&self) -> String {
+        self.repeats.map_or_else(|| "-1".to_string(), |count| count.to_string())
+    }
 }
 
 fn path_to_str(path: &Path) -> Result<&str, GifError> {
@@ -28,11 +92,20 @@ fn path_to_str(path: &Path) -> Result<&str, GifError> {
         .ok_or_else(|| GifError::InvalidPath(path.to_string_lossy().into_owned()))
 }
 
+/// Assemble frames into a GIF using ffmpeg's palette pipeline, looping per `options`.
+///
+/// Frame delays are rewritten afterwards with [`frame_delays`] so the total
+/// playback time matches `frames.len() / fps` as closely as GIF allows.
 pub fn assemble_gif(
     output_path: &Path,
     frames: &[image::RgbaImage],
     fps: u32,
+    options: GifOptions,
 ) -> Result<u64, GifError> {
+    if frames.is_empty() {
+        return Err(GifError::FrameWriteError("no frames to render".to_string()));
+    }
+
     // Check if ffmpeg is available
     let ffmpeg_check = Command::new("ffmpeg").arg("-version").output();
 
@@ -45,8 +118,36 @@ pub fn assemble_gif(
     std::fs::create_dir_all(&temp_dir)
         .map_err(|e| GifError::TempDirError(e.to_string()))?;
 
+    // Always remove the temp frames, even when encoding fails part-way
+    let result = encode_gif(&temp_dir, output_path, frames, fps, options);
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    result?;
+
+    // Replace ffmpeg's frame delays with exact centisecond timing
+    let gif_data =
+        std::fs::read(output_path).map_err(|e| GifError::OutputReadError(e.to_string()))?;
+    let delays = frame_delays(frames.len(), fps);
+    let gif_data = retime_gif(&gif_data, &delays).ok_or_else(|| {
+        GifError::RetimeError(format!(
+            "ffmpeg output is not a well-formed GIF with {} frames",
+            delays.len()
+        ))
+    })?;
+    std::fs::write(output_path, &gif_data)
+        .map_err(|e| GifError::OutputWriteError(e.to_string()))?;
+
+    Ok(gif_data.len() as u64)
+}
+
+fn encode_gif(
+    temp_dir: &Path,
+    output_path: &Path,
+    frames: &[image::RgbaImage],
+    fps: u32,
+    options: GifOptions,
+) -> Result<(), GifError> {
     // Write frames as PNGs
-    let num_digits = (frames.len() as f32).log10().ceil() as usize;
+    let num_digits = num_digits(frames.len().saturating_sub(1));
     for (i, frame) in frames.iter().enumerate() {
         let filename = format!("frame_{:0width$}.png", i, width = num_digits);
         let path = temp_dir.join(&filename);
@@ -71,7 +172,7 @@ pub fn assemble_gif(
             "-i",
             path_to_str(&frame_pattern)?,
             "-vf",
-            "palettegen=stats_mode=full",
+            &options.palettegen_filter(),
             path_to_str(&palette_path)?,
         ])
         .output()
@@ -96,9 +197,9 @@ pub fn assemble_gif(
             "-i",
             path_to_str(&palette_path)?,
             "-lavfi",
-            "paletteuse=dither=bayer:bayer_scale=5:diff_mode=rectangle",
+            &options.paletteuse_filter(),
             "-loop",
-            "0",
+            &options.loop_count(),
             path_to_str(output_path)?,
         ])
         .output()
@@ -109,12 +210,219 @@ pub fn assemble_gif(
         return Err(GifError::FfmpegError(format!("GIF creation failed: {}", stderr)));
     }
 
-    // Clean up temp directory
-    let _ = std::fs::remove_dir_all(&temp_dir);
+    Ok(())
+}
+
+/// Per-frame GIF delays in centiseconds for `frame_count` frames at `fps`.
+///
+/// GIF delays are whole centiseconds, so most frame rates can't be represented
+/// exactly (30 fps is 3.33cs). Each delay is the difference between rounded
+/// cumulative timestamps, which distributes the fractional remainder across
+/// frames and keeps the total within half a centisecond of `frame_count / fps`.
+pub fn frame_delays(frame_count: usize, fps: u32) -> Vec<u16> {
+    let fps = fps.max(1) as u64;
+    let timestamp = |frame: u64| (frame * 100 + fps / 2) / fps;
+
+    (0..frame_count as u64)
+        .map(|frame| (timestamp(frame + 1) - timestamp(frame)) as u16)
+        .collect()
+}
+
+/// Rewrite the delay of every frame in an encoded GIF.
+///
+/// Walks the GIF block structure and overwrites the delay field of each
+/// Graphic Control Extension in order. Returns `None` if the data isn't a
+/// well-formed GIF or its frame count doesn't match `delays`.
+fn retime_gif(data: &[u8], delays: &[u16]) -> Option<Vec<u8>> {
+    let mut out = data.to_vec();
+    if out.len() < 13 || &out[0..3] != b"GIF" {
+        return None;
+    }
 
-    // Get file size
-    let metadata = std::fs::metadata(output_path)
-        .map_err(|e| GifError::OutputReadError(e.to_string()))?;
+    // Header and logical screen descriptor, then the optional global color table
+    let mut pos = 13;
+    let flags = out[10];
+    if flags & 0x80 != 0 {
+        pos += 3 * (1 << ((flags & 0x07) + 1));
+    }
+
+    let mut frame = 0;
+    loop {
+        match *out.get(pos)? {
+            0x21 => {
+                if *out.get(pos + 1)? == 0xF9 {
+                    // Graphic Control Extension: size, packed fields, delay (LE u16), ...
+                    let [lo, hi] = delays.get(frame)?.to_le_bytes();
+                    *out.get_mut(pos + 4)? = lo;
+                    *out.get_mut(pos + 5)? = hi;
+                    frame += 1;
+                }
+                pos = skip_sub_blocks(&out, pos + 2)?;
+            }
+            0x2C => {
+                let packed = *out.get(pos + 9)?;
+                pos += 10;
+                if packed & 0x80 != 0 {
+                    pos += 3 * (1 << ((packed & 0x07) + 1));
+                }
+                // Skip the LZW minimum code size byte, then the image data
+                pos = skip_sub_blocks(&out, pos + 1)?;
+            }
+            0x3B => break,
+            _ => return None,
+        }
+    }
+
+    (frame == delays.len()).then_some(out)
+}
+
+fn skip_sub_blocks(data: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        let len = *data.get(pos)? as usize;
+        pos += 1;
+        if len == 0 {
+            return Some(pos);
+        }
+        pos += len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minimal_gif(frames: usize) -> Vec<u8> {
+        let mut data = b"GIF89a".to_vec();
+        // 1x1 logical screen, no global color table
+        data.extend_from_slice(&[1, 0, 1, 0, 0, 0, 0]);
+        for _ in 0..frames {
+            data.extend_from_slice(&[0x21, 0xF9, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00]);
+            data.extend_from_slice(&[0x2C, 0, 0, 0, 0, 1, 0, 1, 0, 0x00]);
+            data.extend_from_slice(&[0x02, 0x02, 0x4C, 0x01, 0x00]);
+        }
+        data.push(0x3B);
+        data
+    }
+
+    #[test]
+    fn test_frame_delays_total_matches_duration() {
+        // 2 seconds at 30 fps
+        let delays = frame_delays(60, 30);
+        assert_eq!(delays.iter().map(|&d| d as u32).sum::<u32>(), 200);
+
+        // 2 seconds at 7 fps
+        let delays = frame_delays(14, 7);
+        assert_eq!(delays.iter().map(|&d| d as u32).sum::<u32>(), 200);
+    }
+
+    #[test]
+    fn test_frame_delays_distributes_remainder() {
+        assert_eq!(frame_delays(6, 30), vec![3, 4, 3, 3, 4, 3]);
+        assert_eq!(frame_delays(4, 25), vec![4, 4, 4, 4]);
+    }
+
+    #[test]
+    fn test_assemble_gif_rejects_empty() {
+        let path = std::env::temp_dir().join("termcad_empty.gif");
+        let result = assemble_gif(&path, &[], 30, GifOptions::default());
+        assert!(matches!(result, Err(GifError::FrameWriteError(msg)) if msg.contains("no frames")));
+    }
+
+    #[test]
+    fn test_default_filters_match_original_pipeline() {
+        let options = GifOptions::default();
+        assert_eq!(
+            options.palettegen_filter(),
+            "palettegen=stats_mode=full:max_colors=256"
+        );
+        assert_eq!(
+            options.paletteuse_filter(),
+            "paletteuse=dither=bayer:bayer_scale=5:diff_mode=rectangle"
+        );
+    }
+
+    #[test]
+    fn test_filters_follow_options() {
+        let options = GifOptions {
+            dither: Dither::None,
+            max_colors: 16,
+            repeats: Some(0),
+        };
+        assert_eq!(options.palettegen_filter(), "palettegen=stats_mode=full:max_colors=16");
+        assert_eq!(options.paletteuse_filter(), "paletteuse=dither=none:diff_mode=rectangle");
+
+        let options = GifOptions {
+            dither: Dither::Sierra,
+            ..GifOptions::default()
+        };
+        assert!(options.paletteuse_filter().contains("dither=sierra2_4a"));
+    }
+
+    #[test]
+    fn test_loop_count_follows_repeats() {
+        assert_eq!(GifOptions::default().loop_count(), "0");
+        for (repeats, expected) in [(None, "-1"), (Some(3), "3")] {
+            let options = GifOptions {
+                repeats,
+                ..GifOptions::default()
+            };
+            assert_eq!(options.loop_count(), expected);
+        }
+    }
+
+    #[test]
+    fn test_retime_gif_rewrites_delays() {
+        let gif = minimal_gif(2);
+        let retimed = retime_gif(&gif, &[3, 0x0104]).expect("gif should parse");
 
-    Ok(metadata.len())
+        assert_eq!(&retimed[17..19], &[3, 0]);
+        assert_eq!(&retimed[40..42], &[0x04, 0x01]);
+    }
+
+    #[test]
+    fn test_retime_gif_encoded_animation_sums_delays() {
+        use image::AnimationDecoder;
+        use image::codecs::gif::{GifDecoder, GifEncoder};
+
+        let mut encoded = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut encoded);
+            for shade in [0u8, 85, 170, 255] {
+                let pixels = image::RgbaImage::from_pixel(8, 8, image::Rgba([shade, 0, 0, 255]));
+                let frame = image::Frame::from_parts(
+                    pixels,
+                    0,
+                    0,
+                    image::Delay::from_numer_denom_ms(100, 1),
+                );
+                encoder.encode_frame(frame).expect("frame should encode");
+            }
+        }
+
+        let delays = frame_delays(4, 30);
+        let retimed = retime_gif(&encoded, &delays).expect("encoded gif should parse");
+
+        let frames = GifDecoder::new(std::io::Cursor::new(retimed))
+            .expect("retimed gif should decode")
+            .into_frames()
+            .collect_frames()
+            .expect("frames should decode");
+        assert_eq!(frames.len(), 4);
+        let total_ms: u32 = frames
+            .iter()
+            .map(|frame| {
+                let (numer, denom) = frame.delay().numer_denom_ms();
+                numer / denom
+            })
+            .sum();
+        let expected_cs: u32 = delays.iter().map(|&d| d as u32).sum();
+        assert_eq!(total_ms, expected_cs * 10);
+    }
+
+    #[test]
+    fn test_retime_gif_frame_count_mismatch() {
+        assert!(retime_gif(&minimal_gif(2), &[3]).is_none());
+        assert!(retime_gif(&minimal_gif(2), &[3, 3, 3]).is_none());
+        assert!(retime_gif(b"not a gif", &[3]).is_none());
+    }
 }
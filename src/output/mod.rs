@@ -1,5 +1,11 @@
 mod frames;
 mod gif;
+mod phash;
+mod sheet;
+mod webp;
 
-pub use frames::{write_frames, FrameWriteError};
-pub use gif::{assemble_gif, GifError};
+pub use frames::{flatten_frames, write_frames, FrameWriteError};
+pub use gif::{assemble_gif, Dither, GifError, GifOptions};
+pub use phash::{hamming_distance, phash};
+pub use sheet::write_sprite_sheet;
+pub use self::webp::{assemble_webp, WebpError};
@@ -0,0 +1,103 @@
+use image::imageops::{self, FilterType};
+
+const SAMPLE_SIZE: usize = 32;
+const HASH_SIZE: usize = 8;
+
+/// Compute a 64-bit perceptual hash (DCT pHash) of a frame.
+///
+/// The frame is reduced to a 32x32 grayscale sample, transformed with a 2D DCT,
+/// and each of the 8x8 lowest-frequency coefficients becomes one bit depending
+/// on whether it is above the median. Small rendering differences (driver
+/// rasterization, dithering) leave most bits unchanged, so hashes are compared
+/// with [`hamming_distance`] against a tolerance rather than for equality.
+pub fn phash(frame: &image::RgbaImage) -> u64 {
+    let gray = imageops::grayscale(frame);
+    let sample = imageops::resize(
+        &gray,
+        SAMPLE_SIZE as u32,
+        SAMPLE_SIZE as u32,
+        FilterType::Triangle,
+    );
+
+    let pixels: Vec<f32> = sample.pixels().map(|p| p.0[0] as f32).collect();
+    let coefficients = dct_low_frequencies(&pixels);
+
+    // Median excludes the DC term, which only encodes overall brightness
+    let mut sorted = coefficients[1..].to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let median = sorted[sorted.len() / 2];
+
+    coefficients
+        .iter()
+        .enumerate()
+        .fold(0u64, |hash, (i, &c)| if c > median { hash | (1 << i) } else { hash })
+}
+
+/// Number of differing bits between two perceptual hashes.
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+// Separable 2D DCT-II over a SAMPLE_SIZE square, keeping only the
+// HASH_SIZE x HASH_SIZE low-frequency block in row-major order
+fn dct_low_frequencies(pixels: &[f32]) -> Vec<f32> {
+    let basis = |k: usize, n: usize| {
+        (std::f32::consts::PI / SAMPLE_SIZE as f32 * (n as f32 + 0.5) * k as f32).cos()
+    };
+
+    // Transform rows: rows[y][u]
+    let rows: Vec<[f32; HASH_SIZE]> = pixels
+        .chunks(SAMPLE_SIZE)
+        .map(|row| {
+            std::array::from_fn(|u| row.iter().enumerate().map(|(x, &p)| p * basis(u, x)).sum::<f32>())
+        })
+        .collect();
+
+    // Transform columns of the row output
+    (0..HASH_SIZE)
+        .flat_map(|v| {
+            let rows = &rows;
+            (0..HASH_SIZE).map(move |u| {
+                rows.iter()
+                    .enumerate()
+                    .map(|(y, row)| row[u] * basis(v, y))
+                    .sum::<f32>()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(width: u32, height: u32, invert: bool) -> image::RgbaImage {
+        image::RgbaImage::from_fn(width, height, |x, y| {
+            let v = (((x * 7 + y * 13) % 31) * 8) as u8;
+            let v = if invert { 255 - v } else { v };
+            image::Rgba([v, v, v, 255])
+        })
+    }
+
+    #[test]
+    fn test_phash_identical_frames() {
+        let a = pattern(64, 48, false);
+        let b = pattern(64, 48, false);
+        assert_eq!(phash(&a), phash(&b));
+    }
+
+    #[test]
+    fn test_phash_detects_changes() {
+        // Inverting the image negates every AC coefficient, flipping nearly all bits
+        let a = phash(&pattern(64, 48, false));
+        let b = phash(&pattern(64, 48, true));
+        assert!(hamming_distance(a, b) > 48);
+    }
+
+    #[test]
+    fn test_hamming_distance() {
+        assert_eq!(hamming_distance(0, 0), 0);
+        assert_eq!(hamming_distance(0b1011, 0b0001), 2);
+        assert_eq!(hamming_distance(0, u64::MAX), 64);
+    }
+}
@@ -0,0 +1,73 @@
+use image::GenericImage;
+use std::path::Path;
+
+use super::FrameWriteError;
+
+/// Tile `frames` left-to-right, top-to-bottom into a single PNG with `cols` columns.
+///
+/// Every cell is the size of the first frame; the last row is left transparent
+/// past the final frame.
+pub fn write_sprite_sheet(
+    path: &Path,
+    frames: &[image::RgbaImage],
+    cols: u32,
+) -> Result<(), FrameWriteError> {
+    let sheet = sprite_sheet(frames, cols)?;
+    sheet
+        .save(path)
+        .map_err(|e| FrameWriteError::WriteError(format!("{}: {}", path.display(), e)))
+}
+
+fn sprite_sheet(
+    frames: &[image::RgbaImage],
+    cols: u32,
+) -> Result<image::RgbaImage, FrameWriteError> {
+    let first = frames
+        .first()
+        .ok_or_else(|| FrameWriteError::WriteError("no frames to write".to_string()))?;
+    let (frame_width, frame_height) = first.dimensions();
+
+    // Never lay out more columns than there are frames
+    let count = frames.len() as u32;
+    let cols = cols.clamp(1, count);
+    let rows = count.div_ceil(cols);
+
+    let mut sheet = image::RgbaImage::new(frame_width * cols, frame_height * rows);
+    for (i, frame) in (0..).zip(frames) {
+        let (x, y) = (i % cols * frame_width, i / cols * frame_height);
+        sheet
+            .copy_from(frame, x, y)
+            .map_err(|e| FrameWriteError::WriteError(format!("frame {}: {}", i, e)))?;
+    }
+
+    Ok(sheet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sprite_sheet_layout() {
+        let frames: Vec<_> = (0..5u8)
+            .map(|i| image::RgbaImage::from_pixel(2, 3, image::Rgba([i, 0, 0, 255])))
+            .collect();
+        let sheet = sprite_sheet(&frames, 2).unwrap();
+
+        assert_eq!(sheet.dimensions(), (4, 9));
+        assert_eq!(sheet.get_pixel(0, 0)[0], 0);
+        assert_eq!(sheet.get_pixel(2, 0)[0], 1);
+        assert_eq!(sheet.get_pixel(0, 3)[0], 2);
+        assert_eq!(sheet.get_pixel(3, 5)[0], 3);
+        assert_eq!(sheet.get_pixel(1, 8)[0], 4);
+        // The unused cell after the last frame stays empty
+        assert_eq!(sheet.get_pixel(3, 8)[3], 0);
+    }
+
+    #[test]
+    fn test_sprite_sheet_clamps_columns() {
+        let frames = vec![image::RgbaImage::new(2, 2); 3];
+        assert_eq!(sprite_sheet(&frames, 10).unwrap().dimensions(), (6, 2));
+        assert!(sprite_sheet(&[], 2).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use ::webp::{AnimEncoder, AnimFrame, WebPConfig};
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum WebpError {
+    #[error("WebP encoding failed: {0}")]
+    EncodeError(String),
+
+    #[error("Failed to write output file: {0}")]
+    OutputWriteError(String),
+}
+
+/// Encode frames as a lossless animated WebP, avoiding GIF's 256-color palette.
+///
+/// `repeats` counts extra plays after the first as for GIF: `Some(0)` repeats
+/// forever and `None` plays once. Returns the size of the written file in bytes.
+pub fn assemble_webp(
+    output_path: &Path,
+    frames: &[image::RgbaImage],
+    fps: u32,
+    repeats: Option<u32>,
+) -> Result<u64, WebpError> {
+    let first = frames
+        .first()
+        .ok_or_else(|| WebpError::EncodeError("no frames to render".to_string()))?;
+    let (width, height) = first.dimensions();
+
+    let mut config = WebPConfig::new()
+        .map_err(|_| WebpError::EncodeError("failed to create encoder config".to_string()))?;
+    config.lossless = 1;
+
+    let mut encoder = AnimEncoder::new(width, height, &config);
+    encoder.set_loop_count(loop_count(repeats));
+    for (frame, timestamp) in frames.iter().zip(frame_timestamps(frames.len(), fps)) {
+        encoder.add_frame(AnimFrame::from_rgba(frame.as_raw(), width, height, timestamp));
+    }
+
+    let data = encoder
+        .try_encode()
+        .map_err(|e| WebpError::EncodeError(format!("{:?}", e)))?;
+    std::fs::write(output_path, &*data)
+        .map_err(|e| WebpError::OutputWriteError(e.to_string()))?;
+
+    Ok(data.len() as u64)
+}
+
+/// WebP loop count, which counts total plays with 0 meaning forever.
+fn loop_count(repeats: Option<u32>) -> i32 {
+    match repeats {
+        Some(0) => 0,
+        Some(count) => count as i32 + 1,
+        None => 1,
+    }
+}
+
+/// Start time of each frame in milliseconds, rounded from the exact `frame / fps`
+/// so rounding error doesn't accumulate over long animations.
+fn frame_timestamps(frame_count: usize, fps: u32) -> Vec<i32> {
+    let fps = fps.max(1) as u64;
+    (0..frame_count as u64)
+        .map(|frame| ((frame * 1000 + fps / 2) / fps) as i32)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_timestamps() {
+        assert_eq!(frame_timestamps(4, 30), vec![0, 33, 67, 100]);
+        assert_eq!(frame_timestamps(3, 25), vec![0, 40, 80]);
+    }
+
+    #[test]
+    fn test_loop_count() {
+        assert_eq!(loop_count(Some(0)), 0);
+        assert_eq!(loop_count(None), 1);
+        assert_eq!(loop_count(Some(2)), 3);
+    }
+
+    #[test]
+    fn test_assemble_webp_rejects_empty() {
+        let path = std::env::temp_dir().join("termcad_empty.webp");
+        let result = assemble_webp(&path, &[], 30, Some(0));
+        assert!(matches!(result, Err(WebpError::EncodeError(msg)) if msg.contains("no frames")));
+    }
+}
@@ -1,9 +1,14 @@
 use super::{LineVertex, Primitive};
-use crate::scene::{parse_hex_color, AnimatedValue, AxesElement, ExpressionContext};
+use crate::scene::{parse_color, AnimatedPoint, AnimatedValue, AxesElement, ExpressionContext};
+
+/// Three axis lines and two arrowhead strokes per axis
+pub(super) const VERTEX_COUNT: u64 = 3 * 3 * 2;
 
 pub struct AxesPrimitive {
-    position: [f32; 3],
+    position: AnimatedPoint,
     length: f32,
+    arrow_min: f32,
+    arrow_max: f32,
     base_color_x: [f32; 4],
     base_color_y: [f32; 4],
     base_color_z: [f32; 4],
@@ -12,13 +17,15 @@ pub struct AxesPrimitive {
 
 impl AxesPrimitive {
     pub fn from_element(element: &AxesElement) -> Self {
-        let base_color_x = parse_hex_color(&element.colors.x).unwrap_or([1.0, 0.0, 0.0, 1.0]);
-        let base_color_y = parse_hex_color(&element.colors.y).unwrap_or([0.0, 1.0, 0.0, 1.0]);
-        let base_color_z = parse_hex_color(&element.colors.z).unwrap_or([0.0, 0.0, 1.0, 1.0]);
+        let base_color_x = parse_color(&element.colors.x).unwrap_or([1.0, 0.0, 0.0, 1.0]);
+        let base_color_y = parse_color(&element.colors.y).unwrap_or([0.0, 1.0, 0.0, 1.0]);
+        let base_color_z = parse_color(&element.colors.z).unwrap_or([0.0, 0.0, 1.0, 1.0]);
 
         Self {
-            position: element.position,
+            position: element.position.clone(),
             length: element.length,
+            arrow_min: element.arrow_min,
+            arrow_max: element.arrow_max,
             base_color_x,
             base_color_y,
             base_color_z,
@@ -34,7 +41,7 @@ impl Primitive for AxesPrimitive {
         // Evaluate opacity at render time and clamp to valid range
         let opacity = self.opacity.evaluate(ctx).clamp(0.0, 1.0);
 
-        let [ox, oy, oz] = self.position;
+        let [ox, oy, oz] = self.position.evaluate(ctx);
         let l = self.length;
 
         // X axis (red)
@@ -67,8 +74,9 @@ impl Primitive for AxesPrimitive {
         vertices.push(LineVertex::new([ox, oy, oz], cz));
         vertices.push(LineVertex::new([ox, oy, oz + l], cz));
 
-        // Arrow heads (small lines at the end of each axis)
-        let arrow_size = l * 0.15;
+        // Arrow heads (small lines at the end of each axis), clamped to world-unit
+        // bounds and never longer than half the axis so short gizmos stay readable
+        let arrow_size = (l * 0.15).clamp(self.arrow_min, self.arrow_max).min(l * 0.5);
 
         // X arrow
         vertices.push(LineVertex::new([ox + l, oy, oz], cx));
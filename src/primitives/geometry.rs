@@ -1,18 +1,39 @@
-use crate::scene::GeometryType;
+use crate::scene::{GeometryParams, GeometryType};
 use std::f32::consts::PI;
 
+#[derive(Debug, Clone, Default)]
 pub struct GeometryData {
     pub vertices: Vec<[f32; 3]>,
     pub edges: Vec<(usize, usize)>,
+    /// Surface polygons as vertex loops wound counterclockwise seen from outside, used
+    /// for hidden-line removal. Empty for bare edge lists with no surface.
+    pub faces: Vec<Vec<usize>>,
 }
 
-pub fn generate_geometry(geometry_type: &GeometryType) -> GeometryData {
+pub fn generate_geometry(geometry_type: &GeometryType, params: &GeometryParams) -> GeometryData {
     match geometry_type {
         GeometryType::Cube => generate_cube(),
-        GeometryType::Sphere => generate_sphere(16, 12),
-        GeometryType::Torus => generate_torus(24, 12, 1.0, 0.3),
+        GeometryType::Sphere => generate_sphere(params.segments as usize, params.rings as usize),
+        GeometryType::Torus => generate_torus(
+            params.tube_segments as usize,
+            params.radial_segments as usize,
+            params.major_radius,
+            params.minor_radius,
+        ),
         GeometryType::Ico => generate_icosahedron(),
-        GeometryType::Cylinder => generate_cylinder(16, 1.0, 2.0),
+        GeometryType::Cylinder => {
+            generate_cylinder(params.segments as usize, 1.0, 2.0, params.caps)
+        }
+        GeometryType::Tetrahedron => generate_tetrahedron(),
+        GeometryType::Octahedron => generate_octahedron(),
+        GeometryType::Dodecahedron => generate_dodecahedron(),
+        // A model never passed through `Scene::load_assets` draws nothing
+        GeometryType::Obj { mesh, .. } => mesh.as_deref().cloned().unwrap_or_default(),
+        GeometryType::Custom { vertices, edges } => GeometryData {
+            vertices: vertices.clone(),
+            edges: edges.iter().map(|&[a, b]| (a, b)).collect(),
+            faces: Vec::new(),
+        },
     }
 }
 
@@ -47,7 +68,12 @@ fn generate_cube() -> GeometryData {
         (3, 7),
     ];
 
-    GeometryData { vertices, edges }
+    let faces = convex_faces(&vertices);
+    GeometryData {
+        vertices,
+        edges,
+        faces,
+    }
 }
 
 fn generate_sphere(segments: usize, rings: usize) -> GeometryData {
@@ -86,7 +112,22 @@ fn generate_sphere(segments: usize, rings: usize) -> GeometryData {
         }
     }
 
-    GeometryData { vertices, edges }
+    // One quad per grid cell; cells touching a pole collapse to triangles
+    let mut faces = Vec::new();
+    for ring in 0..rings {
+        for seg in 0..segments {
+            let next = (seg + 1) % segments;
+            let (top, bottom) = (ring * segments, (ring + 1) * segments);
+            faces.push(vec![top + seg, top + next, bottom + next, bottom + seg]);
+        }
+    }
+    orient_faces(&vertices, &mut faces, |_| [0.0; 3]);
+
+    GeometryData {
+        vertices,
+        edges,
+        faces,
+    }
 }
 
 fn generate_torus(
@@ -127,7 +168,32 @@ fn generate_torus(
         }
     }
 
-    GeometryData { vertices, edges }
+    let mut faces = Vec::new();
+    for radial in 0..radial_segments {
+        let next_radial = (radial + 1) % radial_segments;
+        for tube in 0..tube_segments {
+            let next_tube = (tube + 1) % tube_segments;
+            faces.push(vec![
+                radial * tube_segments + tube,
+                radial * tube_segments + next_tube,
+                next_radial * tube_segments + next_tube,
+                next_radial * tube_segments + tube,
+            ]);
+        }
+    }
+
+    // Faces point away from the tube's center circle rather than the origin
+    let center_radius = major_radius * 0.5;
+    orient_faces(&vertices, &mut faces, |c| {
+        let scale = center_radius / (c[0] * c[0] + c[2] * c[2]).sqrt().max(1e-6);
+        [c[0] * scale, 0.0, c[2] * scale]
+    });
+
+    GeometryData {
+        vertices,
+        edges,
+        faces,
+    }
 }
 
 fn generate_icosahedron() -> GeometryData {
@@ -135,18 +201,18 @@ fn generate_icosahedron() -> GeometryData {
     let s = 0.3; // Scale factor
 
     let vertices = vec![
-        [-1.0 * s, phi * s, 0.0],
-        [1.0 * s, phi * s, 0.0],
-        [-1.0 * s, -phi * s, 0.0],
-        [1.0 * s, -phi * s, 0.0],
-        [0.0, -1.0 * s, phi * s],
-        [0.0, 1.0 * s, phi * s],
-        [0.0, -1.0 * s, -phi * s],
-        [0.0, 1.0 * s, -phi * s],
-        [phi * s, 0.0, -1.0 * s],
-        [phi * s, 0.0, 1.0 * s],
-        [-phi * s, 0.0, -1.0 * s],
-        [-phi * s, 0.0, 1.0 * s],
+        [-s, phi * s, 0.0],
+        [s, phi * s, 0.0],
+        [-s, -phi * s, 0.0],
+        [s, -phi * s, 0.0],
+        [0.0, -s, phi * s],
+        [0.0, s, phi * s],
+        [0.0, -s, -phi * s],
+        [0.0, s, -phi * s],
+        [phi * s, 0.0, -s],
+        [phi * s, 0.0, s],
+        [-phi * s, 0.0, -s],
+        [-phi * s, 0.0, s],
     ];
 
     let edges = vec![
@@ -182,10 +248,116 @@ fn generate_icosahedron() -> GeometryData {
         (10, 11),
     ];
 
-    GeometryData { vertices, edges }
+    let faces = convex_faces(&vertices);
+    GeometryData {
+        vertices,
+        edges,
+        faces,
+    }
+}
+
+fn generate_tetrahedron() -> GeometryData {
+    let s = 0.3; // Scale factor
+
+    // Alternate corners of a cube
+    let vertices = vec![
+        [s, s, s],
+        [s, -s, -s],
+        [-s, s, -s],
+        [-s, -s, s],
+    ];
+
+    let edges = vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+
+    let faces = convex_faces(&vertices);
+    GeometryData {
+        vertices,
+        edges,
+        faces,
+    }
+}
+
+fn generate_octahedron() -> GeometryData {
+    let s = 0.5;
+
+    let vertices = vec![
+        [s, 0.0, 0.0],
+        [-s, 0.0, 0.0],
+        [0.0, s, 0.0],
+        [0.0, -s, 0.0],
+        [0.0, 0.0, s],
+        [0.0, 0.0, -s],
+    ];
+
+    // Every vertex connects to all others except its opposite
+    let edges = vec![
+        (0, 2),
+        (0, 3),
+        (0, 4),
+        (0, 5),
+        (1, 2),
+        (1, 3),
+        (1, 4),
+        (1, 5),
+        (2, 4),
+        (2, 5),
+        (3, 4),
+        (3, 5),
+    ];
+
+    let faces = convex_faces(&vertices);
+    GeometryData {
+        vertices,
+        edges,
+        faces,
+    }
 }
 
-fn generate_cylinder(segments: usize, radius: f32, height: f32) -> GeometryData {
+fn generate_dodecahedron() -> GeometryData {
+    let phi = (1.0 + 5.0_f32.sqrt()) / 2.0;
+    let inv = 1.0 / phi;
+    let s = 0.3; // Scale factor
+
+    let mut vertices = Vec::with_capacity(20);
+
+    // Cube corners
+    for x in [-1.0, 1.0] {
+        for y in [-1.0, 1.0] {
+            for z in [-1.0, 1.0] {
+                vertices.push([x * s, y * s, z * s]);
+            }
+        }
+    }
+
+    // Golden-ratio rectangles in each coordinate plane
+    for a in [-1.0, 1.0] {
+        for b in [-1.0, 1.0] {
+            vertices.push([0.0, a * inv * s, b * phi * s]);
+            vertices.push([a * inv * s, b * phi * s, 0.0]);
+            vertices.push([a * phi * s, 0.0, b * inv * s]);
+        }
+    }
+
+    // Edges join vertices exactly one edge length (2 / phi) apart
+    let edge_length = 2.0 * inv * s;
+    let mut edges = Vec::with_capacity(30);
+    for i in 0..vertices.len() {
+        for j in (i + 1)..vertices.len() {
+            if (distance(vertices[i], vertices[j]) - edge_length).abs() < 1e-4 {
+                edges.push((i, j));
+            }
+        }
+    }
+
+    let faces = convex_faces(&vertices);
+    GeometryData {
+        vertices,
+        edges,
+        faces,
+    }
+}
+
+fn generate_cylinder(segments: usize, radius: f32, height: f32, caps: bool) -> GeometryData {
     let mut vertices = Vec::new();
     let mut edges = Vec::new();
 
@@ -221,5 +393,344 @@ fn generate_cylinder(segments: usize, radius: f32, height: f32) -> GeometryData
         edges.push((seg, segments + seg));
     }
 
-    GeometryData { vertices, edges }
+    // Side quads, plus solid ends so the far wall hides behind them
+    let mut faces: Vec<Vec<usize>> = (0..segments)
+        .map(|seg| {
+            let next = (seg + 1) % segments;
+            vec![seg, next, segments + next, segments + seg]
+        })
+        .collect();
+
+    // Cap spokes from each circle's center to its rim
+    if caps {
+        let bottom_center = vertices.len();
+        vertices.push([0.0, -half_height, 0.0]);
+        let top_center = vertices.len();
+        vertices.push([0.0, half_height, 0.0]);
+
+        for seg in 0..segments {
+            let next = (seg + 1) % segments;
+            edges.push((bottom_center, seg));
+            edges.push((top_center, segments + seg));
+            faces.push(vec![bottom_center, seg, next]);
+            faces.push(vec![top_center, segments + seg, segments + next]);
+        }
+    } else {
+        faces.push((0..segments).collect());
+        faces.push((segments..2 * segments).collect());
+    }
+    orient_faces(&vertices, &mut faces, |_| [0.0; 3]);
+
+    GeometryData {
+        vertices,
+        edges,
+        faces,
+    }
+}
+
+/// Number of edges `generate_geometry` would produce, without building them. Only OBJ
+/// files are read to find out.
+pub fn edge_count(geometry_type: &GeometryType, params: &GeometryParams) -> u64 {
+    let segments = params.segments as u64;
+    match geometry_type {
+        GeometryType::Cube | GeometryType::Octahedron => 12,
+        GeometryType::Tetrahedron => 6,
+        GeometryType::Ico | GeometryType::Dodecahedron => 30,
+        // Rings include both poles, plus a meridian edge between each pair of rings
+        GeometryType::Sphere => {
+            let rings = params.rings as u64;
+            segments * (rings + 1) + segments * rings
+        }
+        GeometryType::Torus => 2 * params.tube_segments as u64 * params.radial_segments as u64,
+        GeometryType::Cylinder => segments * if params.caps { 5 } else { 3 },
+        GeometryType::Obj { mesh, .. } => mesh.as_ref().map_or(0, |m| m.edges.len() as u64),
+        GeometryType::Custom { edges, .. } => edges.len() as u64,
+    }
+}
+
+/// Faces of a convex solid centered anywhere: each plane through three vertices with
+/// every other vertex behind it, with its coplanar vertices sorted counterclockwise
+/// around the outward normal.
+fn convex_faces(vertices: &[[f32; 3]]) -> Vec<Vec<usize>> {
+    const EPSILON: f32 = 1e-4;
+    let n = vertices.len();
+    let mut faces = Vec::new();
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            for k in (j + 1)..n {
+                let normal = cross(sub(vertices[j], vertices[i]), sub(vertices[k], vertices[i]));
+                let length = dot(normal, normal).sqrt();
+                if length < EPSILON {
+                    continue;
+                }
+                let height = |v: [f32; 3]| dot(normal, sub(v, vertices[i])) / length;
+
+                let heights: Vec<f32> = vertices.iter().map(|&v| height(v)).collect();
+                let ahead = heights.iter().any(|&h| h > EPSILON);
+                let behind = heights.iter().any(|&h| h < -EPSILON);
+                if ahead && behind {
+                    continue;
+                }
+
+                // Visit each face once, from its three lowest vertex indices
+                let face: Vec<usize> = (0..n).filter(|&m| heights[m].abs() <= EPSILON).collect();
+                if face[..3] != [i, j, k] {
+                    continue;
+                }
+
+                let outward = if ahead { scale(normal, -1.0) } else { normal };
+                faces.push(sort_around(vertices, face, outward));
+            }
+        }
+    }
+
+    faces
+}
+
+/// Order a planar face's vertices counterclockwise when viewed from `normal`'s side.
+fn sort_around(vertices: &[[f32; 3]], mut face: Vec<usize>, normal: [f32; 3]) -> Vec<usize> {
+    let center = centroid(vertices, &face);
+    let u = sub(vertices[face[0]], center);
+    let w = cross(normal, u);
+    let angle = |&index: &usize| {
+        let d = sub(vertices[index], center);
+        dot(d, w).atan2(dot(d, u))
+    };
+    face.sort_by(|a, b| angle(a).total_cmp(&angle(b)));
+    face
+}
+
+/// Reverse any face whose normal points toward `inside(centroid)`, for surfaces whose
+/// generators don't produce a consistent winding.
+fn orient_faces(
+    vertices: &[[f32; 3]],
+    faces: &mut [Vec<usize>],
+    inside: impl Fn([f32; 3]) -> [f32; 3],
+) {
+    for face in faces {
+        let center = centroid(vertices, face);
+        if dot(face_normal(vertices, face), sub(center, inside(center))) < 0.0 {
+            face.reverse();
+        }
+    }
+}
+
+/// Unnormalized face normal by Newell's method, which tolerates the repeated vertices
+/// of faces collapsed at a sphere's poles.
+pub(super) fn face_normal(vertices: &[[f32; 3]], face: &[usize]) -> [f32; 3] {
+    let mut normal = [0.0; 3];
+    for (k, &index) in face.iter().enumerate() {
+        let a = vertices[index];
+        let b = vertices[face[(k + 1) % face.len()]];
+        normal[0] += (a[1] - b[1]) * (a[2] + b[2]);
+        normal[1] += (a[2] - b[2]) * (a[0] + b[0]);
+        normal[2] += (a[0] - b[0]) * (a[1] + b[1]);
+    }
+    normal
+}
+
+pub(super) fn centroid(vertices: &[[f32; 3]], face: &[usize]) -> [f32; 3] {
+    let sum = face.iter().fold([0.0; 3], |acc, &index| {
+        let v = vertices[index];
+        [acc[0] + v[0], acc[1] + v[1], acc[2] + v[2]]
+    });
+    scale(sum, 1.0 / face.len() as f32)
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+pub(super) fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn scale(v: [f32; 3], factor: f32) -> [f32; 3] {
+    [v[0] * factor, v[1] * factor, v[2] * factor]
+}
+
+fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge_lengths(geometry: &GeometryData) -> Vec<f32> {
+        geometry
+            .edges
+            .iter()
+            .map(|&(i, j)| distance(geometry.vertices[i], geometry.vertices[j]))
+            .collect()
+    }
+
+    #[test]
+    fn test_platonic_solid_counts() {
+        for (geometry_type, vertices, edges) in [
+            (GeometryType::Tetrahedron, 4, 6),
+            (GeometryType::Octahedron, 6, 12),
+            (GeometryType::Dodecahedron, 20, 30),
+        ] {
+            let geometry = generate_geometry(&geometry_type, &GeometryParams::default());
+            assert_eq!(geometry.vertices.len(), vertices, "{:?}", geometry_type);
+            assert_eq!(geometry.edges.len(), edges, "{:?}", geometry_type);
+
+            // Regular solids have a single edge length
+            let lengths = edge_lengths(&geometry);
+            assert!(lengths.iter().all(|l| (l - lengths[0]).abs() < 1e-4));
+        }
+    }
+
+    #[test]
+    fn test_dodecahedron_vertices_have_degree_three() {
+        let geometry = generate_geometry(&GeometryType::Dodecahedron, &GeometryParams::default());
+        let mut degree = vec![0; geometry.vertices.len()];
+        for &(i, j) in &geometry.edges {
+            degree[i] += 1;
+            degree[j] += 1;
+        }
+        assert!(degree.iter().all(|&d| d == 3));
+    }
+
+    #[test]
+    fn test_subdivision_params() {
+        let params = GeometryParams {
+            segments: 8,
+            rings: 4,
+            tube_segments: 6,
+            radial_segments: 5,
+            ..GeometryParams::default()
+        };
+
+        let sphere = generate_geometry(&GeometryType::Sphere, &params);
+        assert_eq!(sphere.vertices.len(), 8 * 5);
+        assert_eq!(sphere.edges.len(), 8 * 5 + 8 * 4);
+
+        let torus = generate_geometry(&GeometryType::Torus, &params);
+        assert_eq!(torus.vertices.len(), 6 * 5);
+        assert_eq!(torus.edges.len(), 2 * 6 * 5);
+
+        let cylinder = generate_geometry(&GeometryType::Cylinder, &params);
+        assert_eq!(cylinder.vertices.len(), 16);
+    }
+
+    #[test]
+    fn test_custom_geometry_passthrough() {
+        let custom = GeometryType::Custom {
+            vertices: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            edges: vec![[0, 1], [1, 2]],
+        };
+        let geometry = generate_geometry(&custom, &GeometryParams::default());
+        assert_eq!(geometry.vertices[2], [0.0, 1.0, 0.0]);
+        assert_eq!(geometry.edges, vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn test_cylinder_without_caps() {
+        let geometry = generate_geometry(&GeometryType::Cylinder, &GeometryParams::default());
+        assert_eq!(geometry.vertices.len(), 32);
+        assert_eq!(geometry.edges.len(), 48);
+    }
+
+    #[test]
+    fn test_cylinder_with_caps() {
+        let params = GeometryParams {
+            caps: true,
+            ..GeometryParams::default()
+        };
+        let geometry = generate_geometry(&GeometryType::Cylinder, &params);
+        assert_eq!(geometry.vertices.len(), 34);
+        assert_eq!(geometry.edges.len(), 80);
+        assert_eq!(geometry.vertices[32], [0.0, -0.5, 0.0]);
+        assert_eq!(geometry.vertices[33], [0.0, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn test_solid_faces_satisfy_euler() {
+        for (geometry_type, faces) in [
+            (GeometryType::Cube, 6),
+            (GeometryType::Tetrahedron, 4),
+            (GeometryType::Octahedron, 8),
+            (GeometryType::Ico, 20),
+            (GeometryType::Dodecahedron, 12),
+        ] {
+            let geometry = generate_geometry(&geometry_type, &GeometryParams::default());
+            assert_eq!(geometry.faces.len(), faces, "{:?}", geometry_type);
+            let euler = geometry.vertices.len() + geometry.faces.len() - geometry.edges.len();
+            assert_eq!(euler, 2, "{:?}", geometry_type);
+        }
+    }
+
+    #[test]
+    fn test_face_normals_point_outward() {
+        let params = GeometryParams {
+            caps: true,
+            ..GeometryParams::default()
+        };
+        for geometry_type in [
+            GeometryType::Cube,
+            GeometryType::Ico,
+            GeometryType::Dodecahedron,
+            GeometryType::Sphere,
+            GeometryType::Cylinder,
+        ] {
+            let geometry = generate_geometry(&geometry_type, &params);
+            for face in &geometry.faces {
+                let normal = face_normal(&geometry.vertices, face);
+                let center = centroid(&geometry.vertices, face);
+                if dot(normal, normal) > 1e-8 {
+                    assert!(dot(normal, center) > 0.0, "{:?} {:?}", geometry_type, face);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_edge_count_matches_generated_edges() {
+        let params = GeometryParams {
+            segments: 9,
+            rings: 5,
+            tube_segments: 7,
+            radial_segments: 4,
+            caps: true,
+            ..GeometryParams::default()
+        };
+        for geometry_type in [
+            GeometryType::Cube,
+            GeometryType::Sphere,
+            GeometryType::Torus,
+            GeometryType::Ico,
+            GeometryType::Cylinder,
+            GeometryType::Tetrahedron,
+            GeometryType::Octahedron,
+            GeometryType::Dodecahedron,
+        ] {
+            let geometry = generate_geometry(&geometry_type, &params);
+            assert_eq!(
+                edge_count(&geometry_type, &params),
+                geometry.edges.len() as u64,
+                "{:?}",
+                geometry_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_custom_geometry_has_no_faces() {
+        let custom = GeometryType::Custom {
+            vertices: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]],
+            edges: vec![[0, 1]],
+        };
+        assert!(generate_geometry(&custom, &GeometryParams::default()).faces.is_empty());
+    }
 }
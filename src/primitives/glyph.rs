@@ -1,28 +1,61 @@
 use super::{LineVertex, Primitive};
-use crate::scene::{parse_hex_color, ExpressionContext, GlyphAnimation, GlyphElement};
+use crate::scene::{parse_color, ExpressionContext, GlyphAlign, GlyphAnimation, GlyphElement};
+
+const DEFAULT_COLOR: [f32; 4] = [0.0, 1.0, 0.25, 1.0];
 
 pub struct GlyphPrimitive {
     element: GlyphElement,
-    base_color: [f32; 4],
+    gradient: Option<([f32; 4], [f32; 4])>,
 }
 
 impl GlyphPrimitive {
     pub fn from_element(element: &GlyphElement) -> Self {
-        let base_color = parse_hex_color(&element.color).unwrap_or([0.0, 1.0, 0.25, 1.0]);
+        let gradient = element.gradient.as_ref().map(|g| {
+            (
+                parse_color(&g.from).unwrap_or(DEFAULT_COLOR),
+                parse_color(&g.to).unwrap_or(DEFAULT_COLOR),
+            )
+        });
 
         Self {
             element: element.clone(),
-            base_color,
+            gradient,
         }
     }
 
+    /// RGB for the `index`th drawn character, ramping across the full text so
+    /// colors stay put while the type animation reveals it.
+    fn char_rgb(&self, index: usize, base_color: [f32; 4]) -> [f32; 3] {
+        let Some((from, to)) = self.gradient else {
+            return [base_color[0], base_color[1], base_color[2]];
+        };
+
+        let count = self.element.text.chars().filter(|&c| c != '\n').count();
+        let f = if count > 1 {
+            index as f32 / (count - 1) as f32
+        } else {
+            0.0
+        };
+        [
+            from[0] + (to[0] - from[0]) * f,
+            from[1] + (to[1] - from[1]) * f,
+            from[2] + (to[2] - from[2]) * f,
+        ]
+    }
+
     fn get_visible_text(&self, ctx: &ExpressionContext) -> &str {
         match self.element.animation {
             GlyphAnimation::None => &self.element.text,
             GlyphAnimation::Type => {
-                let total_chars = self.element.text.len();
+                let text = &self.element.text;
+                let total_chars = text.chars().count();
                 let visible_chars = ((ctx.t * total_chars as f32).floor() as usize).min(total_chars);
-                &self.element.text[..visible_chars]
+                // Slice on a char boundary so multi-byte characters are never split
+                let end = text
+                    .char_indices()
+                    .nth(visible_chars)
+                    .map_or(text.len(), |(i, _)| i);
+                &text[..end]
             }
             GlyphAnimation::Flicker => &self.element.text,
         }
@@ -47,38 +80,56 @@ impl Primitive for GlyphPrimitive {
     fn vertices(&self, ctx: &ExpressionContext) -> Vec<LineVertex> {
         let text = self.get_visible_text(ctx);
         let opacity = self.get_opacity(ctx);
-        let color = [
-            self.base_color[0],
-            self.base_color[1],
-            self.base_color[2],
-            opacity,
-        ];
+
+        let position = self.element.position.evaluate(ctx);
+        let base_color = self.element.color.evaluate(ctx).unwrap_or(DEFAULT_COLOR);
+
+        // Billboards keep the same layout but span the camera's screen plane
+        let place = |x: f32, y: f32| -> [f32; 3] {
+            if self.element.billboard {
+                let (dx, dy) = (x - position[0], y - position[1]);
+                std::array::from_fn(|i| {
+                    position[i] + ctx.camera_right[i] * dx + ctx.camera_up[i] * dy
+                })
+            } else {
+                [x, y, position[2]]
+            }
+        };
 
         let mut vertices = Vec::new();
+        let mut char_index = 0;
         let char_width = self.element.font_size * 0.6;
         let char_height = self.element.font_size;
+        let line_height = self.element.font_size * self.element.line_spacing;
+
+        for (row, text_line) in text.split('\n').enumerate() {
+            // Align each line on its own so ragged lines still share a margin or center
+            let line_width = text_line.chars().count() as f32 * char_width;
+            let start_x = match self.element.align {
+                GlyphAlign::Left => position[0],
+                GlyphAlign::Center => position[0] - line_width / 2.0,
+                GlyphAlign::Right => position[0] - line_width,
+            };
+            let y = position[1] - row as f32 * line_height;
+
+            for (i, ch) in text_line.chars().enumerate() {
+                let x = start_x + i as f32 * char_width;
+                let [r, g, b] = self.char_rgb(char_index, base_color);
+                let color = [r, g, b, base_color[3] * opacity];
+                char_index += 1;
 
-        // Calculate starting position to center text
-        let total_width = text.len() as f32 * char_width;
-        let start_x = self.element.position[0] - total_width / 2.0;
-
-        for (i, ch) in text.chars().enumerate() {
-            let x = start_x + i as f32 * char_width;
-            let y = self.element.position[1];
-            let z = self.element.position[2];
-
-            // Generate simple line-based character representation
-            let char_lines = get_char_lines(ch, char_width, char_height);
-
-            for line in char_lines {
-                vertices.push(LineVertex::new(
-                    [x + line.0[0], y + line.0[1], z],
-                    color,
-                ));
-                vertices.push(LineVertex::new(
-                    [x + line.1[0], y + line.1[1], z],
-                    color,
-                ));
+                // Generate simple line-based character representation
+                let char_lines = get_char_lines(ch, char_width, char_height);
+                let char_lines = if has_curves(ch) {
+                    smooth_strokes(&char_lines, self.element.curve_segments)
+                } else {
+                    char_lines
+                };
+
+                for line in char_lines {
+                    vertices.push(LineVertex::new(place(x + line.0[0], y + line.0[1]), color));
+                    vertices.push(LineVertex::new(place(x + line.1[0], y + line.1[1]), color));
+                }
             }
         }
 
@@ -86,12 +137,165 @@ impl Primitive for GlyphPrimitive {
     }
 }
 
+pub(super) fn estimate_vertices(element: &GlyphElement) -> u64 {
+    element
+        .text
+        .chars()
+        .map(|ch| {
+            let strokes = get_char_lines(ch, 1.0, 1.0).len() as u64;
+            // Smoothing turns each stroke into at most `curve_segments` pieces
+            let pieces = if has_curves(ch) {
+                element.curve_segments as u64
+            } else {
+                1
+            };
+            strokes * pieces * 2
+        })
+        .sum()
+}
+
+type Stroke = ([f32; 2], [f32; 2]);
+
+/// Turns sharper than ~70 degrees are real corners (e.g. where a bowl meets a
+/// stem) and are never smoothed.
+const CORNER_COS: f32 = 0.34;
+
+/// Characters whose strokes approximate curves and can be smoothed.
+fn has_curves(ch: char) -> bool {
+    matches!(
+        ch,
+        'B' | 'C'
+            | 'D'
+            | 'G'
+            | 'J'
+            | 'O'
+            | 'P'
+            | 'Q'
+            | 'R'
+            | 'S'
+            | 'U'
+            | '0'
+            | '2'
+            | '3'
+            | '5'
+            | '6'
+            | '8'
+            | '9'
+            | '('
+            | ')'
+            | 'a'
+            | 'b'
+            | 'c'
+            | 'd'
+            | 'e'
+            | 'g'
+            | 'h'
+            | 'j'
+            | 'm'
+            | 'n'
+            | 'o'
+            | 'p'
+            | 'q'
+            | 'r'
+            | 's'
+            | 'u'
+    )
+}
+
+/// Replace connected, gently-turning strokes with a Catmull-Rom curve through
+/// the same points, using `subdivisions` segments per original stroke.
+fn smooth_strokes(lines: &[Stroke], subdivisions: u32) -> Vec<Stroke> {
+    if subdivisions <= 1 {
+        return lines.to_vec();
+    }
+
+    stroke_runs(lines)
+        .iter()
+        .flat_map(|run| {
+            let closed = run.len() > 3
+                && points_eq(run[0], run[run.len() - 1])
+                && is_gentle_turn(run[run.len() - 2], run[0], run[1]);
+            catmull_rom(run, closed, subdivisions)
+        })
+        .collect()
+}
+
+/// Group consecutive strokes that share an endpoint and turn gently into polylines.
+fn stroke_runs(lines: &[Stroke]) -> Vec<Vec<[f32; 2]>> {
+    let mut runs: Vec<Vec<[f32; 2]>> = Vec::new();
+
+    for &(start, end) in lines {
+        match runs.last_mut() {
+            Some(run)
+                if points_eq(run[run.len() - 1], start)
+                    && is_gentle_turn(run[run.len() - 2], start, end) =>
+            {
+                run.push(end);
+            }
+            _ => runs.push(vec![start, end]),
+        }
+    }
+
+    runs
+}
+
+fn catmull_rom(points: &[[f32; 2]], closed: bool, subdivisions: u32) -> Vec<Stroke> {
+    let last = points.len() as isize - 1;
+    let at = |i: isize| {
+        if closed {
+            // First and last points coincide, so wrap over the distinct ones
+            points[i.rem_euclid(last) as usize]
+        } else {
+            points[i.clamp(0, last) as usize]
+        }
+    };
+
+    let mut strokes = Vec::with_capacity(last as usize * subdivisions as usize);
+    for i in 0..last {
+        let (p0, p1, p2, p3) = (at(i - 1), at(i), at(i + 1), at(i + 2));
+        let mut prev = p1;
+        for step in 1..=subdivisions {
+            let t = step as f32 / subdivisions as f32;
+            let next = [
+                catmull_rom_1d(p0[0], p1[0], p2[0], p3[0], t),
+                catmull_rom_1d(p0[1], p1[1], p2[1], p3[1], t),
+            ];
+            strokes.push((prev, next));
+            prev = next;
+        }
+    }
+
+    strokes
+}
+
+fn catmull_rom_1d(p0: f32, p1: f32, p2: f32, p3: f32, t: f32) -> f32 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * (2.0 * p1
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+fn points_eq(a: [f32; 2], b: [f32; 2]) -> bool {
+    (a[0] - b[0]).abs() < 1e-5 && (a[1] - b[1]).abs() < 1e-5
+}
+
+fn is_gentle_turn(prev: [f32; 2], joint: [f32; 2], next: [f32; 2]) -> bool {
+    let a = [joint[0] - prev[0], joint[1] - prev[1]];
+    let b = [next[0] - joint[0], next[1] - joint[1]];
+    let len = (a[0] * a[0] + a[1] * a[1]).sqrt() * (b[0] * b[0] + b[1] * b[1]).sqrt();
+    len > 0.0 && (a[0] * b[0] + a[1] * b[1]) / len > CORNER_COS
+}
+
 // Simple vector font - returns line segments for each character
-fn get_char_lines(ch: char, w: f32, h: f32) -> Vec<([f32; 2], [f32; 2])> {
+fn get_char_lines(ch: char, w: f32, h: f32) -> Vec<Stroke> {
     let w = w * 0.8; // Character width with spacing
     let h2 = h / 2.0;
+    let xh = h * 0.6; // Lowercase x-height
+    let d = -h * 0.3; // Descender depth below the baseline
 
-    match ch.to_ascii_uppercase() {
+    match ch {
         'A' => vec![
             ([0.0, 0.0], [w / 2.0, h]),
             ([w / 2.0, h], [w, 0.0]),
@@ -262,6 +466,197 @@ fn get_char_lines(ch: char, w: f32, h: f32) -> Vec<([f32; 2], [f32; 2])> {
             ([w, h], [0.0, 0.0]),
             ([0.0, 0.0], [w, 0.0]),
         ],
+        'a' => vec![
+            ([w, xh * 0.7], [w * 0.6, xh]),
+            ([w * 0.6, xh], [w * 0.3, xh]),
+            ([w * 0.3, xh], [0.0, xh * 0.7]),
+            ([0.0, xh * 0.7], [0.0, xh * 0.3]),
+            ([0.0, xh * 0.3], [w * 0.3, 0.0]),
+            ([w * 0.3, 0.0], [w * 0.6, 0.0]),
+            ([w * 0.6, 0.0], [w, xh * 0.3]),
+            ([w, xh], [w, 0.0]),
+        ],
+        'b' => vec![
+            ([0.0, h], [0.0, 0.0]),
+            ([0.0, xh * 0.7], [w * 0.4, xh]),
+            ([w * 0.4, xh], [w * 0.7, xh]),
+            ([w * 0.7, xh], [w, xh * 0.7]),
+            ([w, xh * 0.7], [w, xh * 0.3]),
+            ([w, xh * 0.3], [w * 0.7, 0.0]),
+            ([w * 0.7, 0.0], [w * 0.4, 0.0]),
+            ([w * 0.4, 0.0], [0.0, xh * 0.3]),
+        ],
+        'c' => vec![
+            ([w, xh * 0.8], [w * 0.6, xh]),
+            ([w * 0.6, xh], [w * 0.3, xh]),
+            ([w * 0.3, xh], [0.0, xh * 0.7]),
+            ([0.0, xh * 0.7], [0.0, xh * 0.3]),
+            ([0.0, xh * 0.3], [w * 0.3, 0.0]),
+            ([w * 0.3, 0.0], [w * 0.6, 0.0]),
+            ([w * 0.6, 0.0], [w, xh * 0.2]),
+        ],
+        'd' => vec![
+            ([w, h], [w, 0.0]),
+            ([w, xh * 0.7], [w * 0.6, xh]),
+            ([w * 0.6, xh], [w * 0.3, xh]),
+            ([w * 0.3, xh], [0.0, xh * 0.7]),
+            ([0.0, xh * 0.7], [0.0, xh * 0.3]),
+            ([0.0, xh * 0.3], [w * 0.3, 0.0]),
+            ([w * 0.3, 0.0], [w * 0.6, 0.0]),
+            ([w * 0.6, 0.0], [w, xh * 0.3]),
+        ],
+        'e' => vec![
+            ([0.0, xh * 0.5], [w, xh * 0.5]),
+            ([w, xh * 0.5], [w, xh * 0.7]),
+            ([w, xh * 0.7], [w * 0.7, xh]),
+            ([w * 0.7, xh], [w * 0.3, xh]),
+            ([w * 0.3, xh], [0.0, xh * 0.7]),
+            ([0.0, xh * 0.7], [0.0, xh * 0.3]),
+            ([0.0, xh * 0.3], [w * 0.3, 0.0]),
+            ([w * 0.3, 0.0], [w, 0.0]),
+        ],
+        'f' => vec![
+            ([w * 0.9, h], [w * 0.6, h]),
+            ([w * 0.6, h], [w * 0.3, h * 0.85]),
+            ([w * 0.3, h * 0.85], [w * 0.3, 0.0]),
+            ([0.0, xh], [w * 0.7, xh]),
+        ],
+        'g' => vec![
+            ([w, xh * 0.7], [w * 0.6, xh]),
+            ([w * 0.6, xh], [w * 0.3, xh]),
+            ([w * 0.3, xh], [0.0, xh * 0.7]),
+            ([0.0, xh * 0.7], [0.0, xh * 0.3]),
+            ([0.0, xh * 0.3], [w * 0.3, 0.0]),
+            ([w * 0.3, 0.0], [w * 0.6, 0.0]),
+            ([w * 0.6, 0.0], [w, xh * 0.3]),
+            ([w, xh], [w, d * 0.6]),
+            ([w, d * 0.6], [w * 0.6, d]),
+            ([w * 0.6, d], [w * 0.2, d]),
+            ([w * 0.2, d], [0.0, d * 0.7]),
+        ],
+        'h' => vec![
+            ([0.0, h], [0.0, 0.0]),
+            ([0.0, xh * 0.7], [w * 0.4, xh]),
+            ([w * 0.4, xh], [w * 0.7, xh]),
+            ([w * 0.7, xh], [w, xh * 0.7]),
+            ([w, xh * 0.7], [w, 0.0]),
+        ],
+        'i' => vec![
+            ([w * 0.5, xh], [w * 0.5, 0.0]),
+            ([w * 0.5, xh * 1.25], [w * 0.5, xh * 1.35]),
+        ],
+        'j' => vec![
+            ([w * 0.6, xh], [w * 0.6, d * 0.6]),
+            ([w * 0.6, d * 0.6], [w * 0.3, d]),
+            ([w * 0.3, d], [0.0, d * 0.7]),
+            ([w * 0.6, xh * 1.25], [w * 0.6, xh * 1.35]),
+        ],
+        'k' => vec![
+            ([0.0, h], [0.0, 0.0]),
+            ([w * 0.9, xh], [0.0, xh * 0.35]),
+            ([w * 0.3, xh * 0.5], [w, 0.0]),
+        ],
+        'l' => vec![
+            ([w * 0.4, h], [w * 0.4, xh * 0.15]),
+            ([w * 0.4, xh * 0.15], [w * 0.7, 0.0]),
+        ],
+        'm' => vec![
+            ([0.0, xh], [0.0, 0.0]),
+            ([0.0, xh * 0.8], [w * 0.25, xh]),
+            ([w * 0.25, xh], [w * 0.5, xh * 0.8]),
+            ([w * 0.5, xh * 0.8], [w * 0.5, 0.0]),
+            ([w * 0.5, xh * 0.8], [w * 0.75, xh]),
+            ([w * 0.75, xh], [w, xh * 0.8]),
+            ([w, xh * 0.8], [w, 0.0]),
+        ],
+        'n' => vec![
+            ([0.0, xh], [0.0, 0.0]),
+            ([0.0, xh * 0.7], [w * 0.4, xh]),
+            ([w * 0.4, xh], [w * 0.7, xh]),
+            ([w * 0.7, xh], [w, xh * 0.7]),
+            ([w, xh * 0.7], [w, 0.0]),
+        ],
+        'o' => vec![
+            ([w * 0.3, 0.0], [w * 0.7, 0.0]),
+            ([w * 0.7, 0.0], [w, xh * 0.3]),
+            ([w, xh * 0.3], [w, xh * 0.7]),
+            ([w, xh * 0.7], [w * 0.7, xh]),
+            ([w * 0.7, xh], [w * 0.3, xh]),
+            ([w * 0.3, xh], [0.0, xh * 0.7]),
+            ([0.0, xh * 0.7], [0.0, xh * 0.3]),
+            ([0.0, xh * 0.3], [w * 0.3, 0.0]),
+        ],
+        'p' => vec![
+            ([0.0, xh], [0.0, d]),
+            ([0.0, xh * 0.7], [w * 0.4, xh]),
+            ([w * 0.4, xh], [w * 0.7, xh]),
+            ([w * 0.7, xh], [w, xh * 0.7]),
+            ([w, xh * 0.7], [w, xh * 0.3]),
+            ([w, xh * 0.3], [w * 0.7, 0.0]),
+            ([w * 0.7, 0.0], [w * 0.4, 0.0]),
+            ([w * 0.4, 0.0], [0.0, xh * 0.3]),
+        ],
+        'q' => vec![
+            ([w, xh], [w, d]),
+            ([w, xh * 0.7], [w * 0.6, xh]),
+            ([w * 0.6, xh], [w * 0.3, xh]),
+            ([w * 0.3, xh], [0.0, xh * 0.7]),
+            ([0.0, xh * 0.7], [0.0, xh * 0.3]),
+            ([0.0, xh * 0.3], [w * 0.3, 0.0]),
+            ([w * 0.3, 0.0], [w * 0.6, 0.0]),
+            ([w * 0.6, 0.0], [w, xh * 0.3]),
+        ],
+        'r' => vec![
+            ([0.0, xh], [0.0, 0.0]),
+            ([0.0, xh * 0.6], [w * 0.4, xh]),
+            ([w * 0.4, xh], [w, xh * 0.85]),
+        ],
+        's' => vec![
+            ([w, xh * 0.85], [w * 0.6, xh]),
+            ([w * 0.6, xh], [w * 0.2, xh]),
+            ([w * 0.2, xh], [0.0, xh * 0.75]),
+            ([0.0, xh * 0.75], [w * 0.2, xh * 0.5]),
+            ([w * 0.2, xh * 0.5], [w * 0.8, xh * 0.5]),
+            ([w * 0.8, xh * 0.5], [w, xh * 0.25]),
+            ([w, xh * 0.25], [w * 0.8, 0.0]),
+            ([w * 0.8, 0.0], [w * 0.4, 0.0]),
+            ([w * 0.4, 0.0], [0.0, xh * 0.15]),
+        ],
+        't' => vec![
+            ([w * 0.4, h * 0.85], [w * 0.4, xh * 0.15]),
+            ([w * 0.4, xh * 0.15], [w * 0.7, 0.0]),
+            ([0.0, xh], [w * 0.8, xh]),
+        ],
+        'u' => vec![
+            ([0.0, xh], [0.0, xh * 0.3]),
+            ([0.0, xh * 0.3], [w * 0.3, 0.0]),
+            ([w * 0.3, 0.0], [w * 0.6, 0.0]),
+            ([w * 0.6, 0.0], [w, xh * 0.3]),
+            ([w, xh], [w, 0.0]),
+        ],
+        'v' => vec![
+            ([0.0, xh], [w / 2.0, 0.0]),
+            ([w / 2.0, 0.0], [w, xh]),
+        ],
+        'w' => vec![
+            ([0.0, xh], [w * 0.25, 0.0]),
+            ([w * 0.25, 0.0], [w / 2.0, xh * 0.6]),
+            ([w / 2.0, xh * 0.6], [w * 0.75, 0.0]),
+            ([w * 0.75, 0.0], [w, xh]),
+        ],
+        'x' => vec![
+            ([0.0, xh], [w, 0.0]),
+            ([w, xh], [0.0, 0.0]),
+        ],
+        'y' => vec![
+            ([0.0, xh], [w / 2.0, 0.0]),
+            ([w, xh], [w * 0.25, d]),
+        ],
+        'z' => vec![
+            ([0.0, xh], [w, xh]),
+            ([w, xh], [0.0, 0.0]),
+            ([0.0, 0.0], [w, 0.0]),
+        ],
         '0' => vec![
             ([w * 0.3, 0.0], [w * 0.7, 0.0]),
             ([w * 0.7, 0.0], [w, h * 0.3]),
@@ -424,3 +819,164 @@ fn get_char_lines(ch: char, w: f32, h: f32) -> Vec<([f32; 2], [f32; 2])> {
         ],
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::{AnimatedPoint, GlyphGradient};
+
+    fn y_range(lines: &[Stroke]) -> (f32, f32) {
+        lines
+            .iter()
+            .flat_map(|(a, b)| [a[1], b[1]])
+            .fold((f32::MAX, f32::MIN), |(lo, hi), y| (lo.min(y), hi.max(y)))
+    }
+
+    #[test]
+    fn test_lowercase_is_distinct() {
+        assert_ne!(get_char_lines('a', 0.6, 1.0), get_char_lines('A', 0.6, 1.0));
+
+        // x-height letters stay below cap height and sit on the baseline
+        let (lo, hi) = y_range(&get_char_lines('o', 0.6, 1.0));
+        assert_eq!(lo, 0.0);
+        assert!(hi <= 0.6 + 1e-6);
+    }
+
+    #[test]
+    fn test_descenders_drop_below_baseline() {
+        for ch in ['g', 'j', 'p', 'q', 'y'] {
+            let (lo, _) = y_range(&get_char_lines(ch, 0.6, 1.0));
+            assert!(lo < 0.0, "'{}' should descend below the baseline", ch);
+        }
+    }
+
+    #[test]
+    fn test_type_animation_multibyte_text() {
+        let glyph = GlyphPrimitive::from_element(&GlyphElement {
+            text: "café →".to_string(),
+            animation: GlyphAnimation::Type,
+            ..GlyphElement::default()
+        });
+
+        // 6 chars over 7 frames: t = frame / 6 reveals one char per frame
+        let visible: Vec<&str> = (0..7)
+            .map(|frame| glyph.get_visible_text(&ExpressionContext::new(frame, 7)))
+            .collect();
+        assert_eq!(visible, ["", "c", "ca", "caf", "café", "café ", "café →"]);
+    }
+
+    #[test]
+    fn test_multiline_text_stacks_lines() {
+        let glyph = GlyphPrimitive::from_element(&GlyphElement {
+            text: "--\n-".to_string(),
+            line_spacing: 2.0,
+            ..GlyphElement::default()
+        });
+        let vertices = glyph.vertices(&ExpressionContext::new(0, 1));
+        assert_eq!(vertices.len(), 6);
+
+        // Second line sits font_size * line_spacing lower and is centered on its own
+        let second = &vertices[4..];
+        assert!((second[0].position[1] - (0.5 - 2.0)).abs() < 1e-6);
+        let first_mid = (vertices[0].position[0] + vertices[3].position[0]) / 2.0;
+        let second_mid = (second[0].position[0] + second[1].position[0]) / 2.0;
+        assert!((first_mid - second_mid).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_align_sets_line_anchor() {
+        let first_x = |align| {
+            let glyph = GlyphPrimitive::from_element(&GlyphElement {
+                text: "L".to_string(),
+                position: AnimatedPoint::Static([1.0, 0.0, 0.0]),
+                align,
+                ..GlyphElement::default()
+            });
+            glyph.vertices(&ExpressionContext::new(0, 1))[0].position[0]
+        };
+
+        // 'L' starts at its cell's left edge; cells are font_size * 0.6 wide
+        assert!((first_x(GlyphAlign::Left) - 1.0).abs() < 1e-6);
+        assert!((first_x(GlyphAlign::Center) - 0.7).abs() < 1e-6);
+        assert!((first_x(GlyphAlign::Right) - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_billboard_follows_camera_plane() {
+        let element = GlyphElement {
+            text: "-".to_string(),
+            position: AnimatedPoint::Static([0.0, 0.0, 2.0]),
+            billboard: true,
+            ..GlyphElement::default()
+        };
+        let glyph = GlyphPrimitive::from_element(&element);
+
+        // Viewed from +X, screen right is -Z, so the dash runs along Z
+        let ctx = ExpressionContext {
+            camera_right: [0.0, 0.0, -1.0],
+            camera_up: [0.0, 1.0, 0.0],
+            ..ExpressionContext::new(0, 1)
+        };
+        let vertices = glyph.vertices(&ctx);
+        assert!(vertices.iter().all(|v| v.position[0].abs() < 1e-6));
+        assert!((vertices[0].position[2] - vertices[1].position[2]).abs() > 0.1);
+
+        // The default context keeps the original XY layout
+        let flat = GlyphPrimitive::from_element(&GlyphElement {
+            billboard: false,
+            ..element
+        });
+        let defaults = ExpressionContext::new(0, 1);
+        let billboard = glyph.vertices(&defaults);
+        for (a, b) in flat.vertices(&defaults).iter().zip(&billboard) {
+            for i in 0..3 {
+                assert!((a.position[i] - b.position[i]).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_gradient_ramps_per_character() {
+        let glyph = GlyphPrimitive::from_element(&GlyphElement {
+            text: "---".to_string(),
+            gradient: Some(GlyphGradient {
+                from: "#000000".to_string(),
+                to: "#ff0000".to_string(),
+            }),
+            ..GlyphElement::default()
+        });
+        let vertices = glyph.vertices(&ExpressionContext::new(0, 1));
+
+        // One stroke (two vertices) per '-': red ramps 0 -> 0.5 -> 1
+        let reds: Vec<f32> = vertices.iter().step_by(2).map(|v| v.color[0]).collect();
+        assert_eq!(reds, [0.0, 0.5, 1.0]);
+        assert_eq!(vertices[0].color[3], 1.0);
+    }
+
+    #[test]
+    fn test_smooth_strokes_single_segment_is_identity() {
+        let lines = get_char_lines('O', 0.6, 1.0);
+        assert_eq!(smooth_strokes(&lines, 1), lines);
+    }
+
+    #[test]
+    fn test_smooth_strokes_closed_curve() {
+        let lines = get_char_lines('O', 0.6, 1.0);
+        let smoothed = smooth_strokes(&lines, 4);
+        assert_eq!(smoothed.len(), lines.len() * 4);
+
+        // The curve still passes through every original point
+        for (i, line) in lines.iter().enumerate() {
+            assert!(points_eq(smoothed[i * 4].0, line.0));
+        }
+    }
+
+    #[test]
+    fn test_smooth_strokes_keeps_corners() {
+        // 'L' is a single right angle and must stay two straight strokes
+        let lines = get_char_lines('L', 0.6, 1.0);
+        let smoothed = smooth_strokes(&lines, 4);
+        assert_eq!(smoothed.len(), 8);
+        assert!(smoothed.iter().all(|(a, b)| a[0] == b[0] || a[1] == b[1]));
+    }
+}
@@ -1,66 +1,252 @@
 use super::{LineVertex, Primitive};
-use crate::scene::{parse_hex_color, AnimatedValue, ExpressionContext, GridElement};
+use crate::scene::{
+    AnimatedColor, AnimatedValue, ExpressionContext, GridElement, GridPlane, GridStyle,
+};
+use std::f32::consts::TAU;
+
+/// Radial spokes drawn by the polar style, one every 30 degrees
+const POLAR_SPOKES: u32 = 12;
+/// Line segments used to approximate each polar ring
+const RING_SEGMENTS: u32 = 64;
 
 pub struct GridPrimitive {
     pub divisions: u32,
     pub fade_distance: f32,
-    pub base_color: [f32; 4],
+    pub color: AnimatedColor,
     pub opacity: AnimatedValue,
+    pub style: GridStyle,
+    pub plane: GridPlane,
+    pub jitter: f32,
+    pub seed: u64,
 }
 
 impl GridPrimitive {
     pub fn from_element(element: &GridElement) -> Self {
-        let base_color = parse_hex_color(&element.color).unwrap_or([0.0, 1.0, 0.25, 1.0]);
-
         Self {
             divisions: element.divisions,
             fade_distance: element.fade_distance,
-            base_color,
+            color: element.color.clone(),
             opacity: element.opacity.clone(),
+            style: element.style,
+            plane: element.plane,
+            jitter: element.jitter,
+            seed: element.seed,
         }
     }
-}
 
-impl Primitive for GridPrimitive {
-    fn vertices(&self, ctx: &ExpressionContext) -> Vec<LineVertex> {
-        let mut vertices = Vec::new();
+    /// Map in-plane coordinates to world space; `a`/`b` are X/Z on the default floor.
+    /// Jitter is keyed by the coordinates, so lines meeting at a point stay joined.
+    fn point(&self, a: f32, b: f32) -> [f32; 3] {
+        let (a, b) = if self.jitter > 0.0 {
+            let [da, db] = jitter_offset(self.seed, a, b);
+            (a + da * self.jitter, b + db * self.jitter)
+        } else {
+            (a, b)
+        };
 
-        // Evaluate opacity at render time and clamp to valid range
-        let base_opacity = self.opacity.evaluate(ctx).clamp(0.0, 1.0);
+        match self.plane {
+            GridPlane::Xz => [a, 0.0, b],
+            GridPlane::Xy => [a, b, 0.0],
+            GridPlane::Yz => [0.0, a, b],
+        }
+    }
 
-        let half_size = self.fade_distance / 2.0;
+    fn rect_vertices(&self, base: [f32; 4], half_size: f32) -> Vec<LineVertex> {
+        let mut vertices = Vec::new();
         let step = half_size * 2.0 / self.divisions as f32;
 
-        // Generate grid lines along X axis
+        // Generate grid lines along the first in-plane axis
         for i in 0..=self.divisions {
             let z = -half_size + i as f32 * step;
-            let fade_factor = 1.0 - (z.abs() / half_size).powf(2.0);
-            let color = [
-                self.base_color[0],
-                self.base_color[1],
-                self.base_color[2],
-                base_opacity * fade_factor.max(0.0),
-            ];
-
-            vertices.push(LineVertex::new([-half_size, 0.0, z], color));
-            vertices.push(LineVertex::new([half_size, 0.0, z], color));
+            let color = faded(base, z, half_size);
+
+            vertices.push(LineVertex::new(self.point(-half_size, z), color));
+            vertices.push(LineVertex::new(self.point(half_size, z), color));
         }
 
-        // Generate grid lines along Z axis
+        // Generate grid lines along the second in-plane axis
         for i in 0..=self.divisions {
             let x = -half_size + i as f32 * step;
-            let fade_factor = 1.0 - (x.abs() / half_size).powf(2.0);
-            let color = [
-                self.base_color[0],
-                self.base_color[1],
-                self.base_color[2],
-                base_opacity * fade_factor.max(0.0),
-            ];
-
-            vertices.push(LineVertex::new([x, 0.0, -half_size], color));
-            vertices.push(LineVertex::new([x, 0.0, half_size], color));
+            let color = faded(base, x, half_size);
+
+            vertices.push(LineVertex::new(self.point(x, -half_size), color));
+            vertices.push(LineVertex::new(self.point(x, half_size), color));
         }
 
         vertices
     }
+
+    fn polar_vertices(&self, base: [f32; 4], half_size: f32) -> Vec<LineVertex> {
+        let mut vertices = Vec::new();
+        let step = half_size / self.divisions as f32;
+        let point =
+            |radius: f32, angle: f32| self.point(radius * angle.cos(), radius * angle.sin());
+
+        // Concentric rings, each faded as a whole by its radius
+        for i in 1..=self.divisions {
+            let radius = i as f32 * step;
+            let color = faded(base, radius, half_size);
+
+            for s in 0..RING_SEGMENTS {
+                let a0 = s as f32 / RING_SEGMENTS as f32 * TAU;
+                let a1 = (s + 1) as f32 / RING_SEGMENTS as f32 * TAU;
+                vertices.push(LineVertex::new(point(radius, a0), color));
+                vertices.push(LineVertex::new(point(radius, a1), color));
+            }
+        }
+
+        // Spokes are split at every ring so their fade follows the same curve
+        for s in 0..POLAR_SPOKES {
+            let angle = s as f32 / POLAR_SPOKES as f32 * TAU;
+            for i in 0..self.divisions {
+                let r0 = i as f32 * step;
+                let r1 = (i + 1) as f32 * step;
+                vertices.push(LineVertex::new(point(r0, angle), faded(base, r0, half_size)));
+                vertices.push(LineVertex::new(point(r1, angle), faded(base, r1, half_size)));
+            }
+        }
+
+        vertices
+    }
+}
+
+pub(super) fn estimate_vertices(element: &GridElement) -> u64 {
+    let divisions = element.divisions as u64;
+    match element.style {
+        GridStyle::Rect => 2 * (divisions + 1) * 2,
+        GridStyle::Polar => divisions * (RING_SEGMENTS + POLAR_SPOKES) as u64 * 2,
+    }
+}
+
+/// Deterministic offset in [-1, 1) on each in-plane axis for the point at `a`, `b`
+fn jitter_offset(seed: u64, a: f32, b: f32) -> [f32; 2] {
+    // Round to a thousandth so the same point reached by different float math matches
+    let key = |v: f32| (v * 1000.0).round() as i64 as u64;
+    let mut state = seed
+        ^ key(a).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ key(b).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+    [next_signed_unit(&mut state), next_signed_unit(&mut state)]
+}
+
+/// Advance a SplitMix64 stream and map its high bits to [-1, 1)
+fn next_signed_unit(state: &mut u64) -> f32 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+}
+
+/// Scale the base alpha by the grid fade, 1.0 at the center and 0.0 at `half_size`
+fn faded(base: [f32; 4], distance: f32, half_size: f32) -> [f32; 4] {
+    let fade_factor = 1.0 - (distance.abs() / half_size).powf(2.0);
+    [base[0], base[1], base[2], base[3] * fade_factor.max(0.0)]
+}
+
+impl Primitive for GridPrimitive {
+    fn vertices(&self, ctx: &ExpressionContext) -> Vec<LineVertex> {
+        // Evaluate opacity at render time and clamp to valid range
+        let base_color = self.color.evaluate(ctx).unwrap_or([0.0, 1.0, 0.25, 1.0]);
+        let base_opacity = self.opacity.evaluate(ctx).clamp(0.0, 1.0) * base_color[3];
+        let base = [base_color[0], base_color[1], base_color[2], base_opacity];
+
+        let half_size = self.fade_distance / 2.0;
+        match self.style {
+            GridStyle::Rect => self.rect_vertices(base, half_size),
+            GridStyle::Polar => self.polar_vertices(base, half_size),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polar_rings_and_spokes() {
+        let grid = GridPrimitive::from_element(&GridElement {
+            divisions: 4,
+            style: GridStyle::Polar,
+            ..Default::default()
+        });
+        let vertices = grid.vertices(&ExpressionContext::new(0, 1));
+
+        let expected = (4 * RING_SEGMENTS + POLAR_SPOKES * 4) * 2;
+        assert_eq!(vertices.len(), expected as usize);
+
+        // The outermost ring sits on the fade distance and is fully transparent
+        let half_size = grid.fade_distance / 2.0;
+        let outer = &vertices[(3 * RING_SEGMENTS * 2) as usize];
+        let radius = (outer.position[0].powi(2) + outer.position[2].powi(2)).sqrt();
+        assert!((radius - half_size).abs() < 1e-3);
+        assert!(outer.color[3].abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_estimate_matches_vertex_count() {
+        for style in [GridStyle::Rect, GridStyle::Polar] {
+            let element = GridElement {
+                divisions: 7,
+                style,
+                ..Default::default()
+            };
+            let grid = GridPrimitive::from_element(&element);
+            let vertices = grid.vertices(&ExpressionContext::new(0, 1));
+            assert_eq!(estimate_vertices(&element), vertices.len() as u64);
+        }
+    }
+
+    #[test]
+    fn test_plane_keeps_constant_axis() {
+        for (plane, axis) in [(GridPlane::Xz, 1), (GridPlane::Xy, 2), (GridPlane::Yz, 0)] {
+            let grid = GridPrimitive::from_element(&GridElement {
+                plane,
+                ..Default::default()
+            });
+            let vertices = grid.vertices(&ExpressionContext::new(0, 1));
+            assert!(vertices.iter().all(|v| v.position[axis] == 0.0));
+        }
+    }
+
+    #[test]
+    fn test_jitter_is_bounded_and_seeded() {
+        let grid = |jitter, seed| {
+            GridPrimitive::from_element(&GridElement {
+                divisions: 4,
+                jitter,
+                seed,
+                ..Default::default()
+            })
+            .vertices(&ExpressionContext::new(0, 1))
+        };
+        let exact = grid(0.0, 1);
+        let a = grid(0.1, 1);
+        let b = grid(0.1, 2);
+
+        assert_eq!(exact.len(), a.len());
+        for (e, j) in exact.iter().zip(&a) {
+            assert!((0..3).all(|i| (e.position[i] - j.position[i]).abs() <= 0.1));
+            assert_eq!(j.position[1], 0.0);
+        }
+        assert_ne!(a[0].position, exact[0].position);
+        assert_eq!(a[0].position, grid(0.1, 1)[0].position);
+        assert_ne!(a[0].position, b[0].position);
+    }
+
+    #[test]
+    fn test_jitter_keeps_polar_rings_closed() {
+        let grid = GridPrimitive::from_element(&GridElement {
+            divisions: 2,
+            style: GridStyle::Polar,
+            jitter: 0.2,
+            ..Default::default()
+        });
+        let vertices = grid.vertices(&ExpressionContext::new(0, 1));
+
+        // Each ring segment ends where the next one starts
+        for s in 0..(RING_SEGMENTS - 1) as usize {
+            assert_eq!(vertices[s * 2 + 1].position, vertices[s * 2 + 2].position);
+        }
+    }
 }
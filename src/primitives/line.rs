@@ -1,26 +1,229 @@
 use super::{LineVertex, Primitive};
-use crate::scene::{parse_hex_color, AnimatedValue, ExpressionContext, LineElement};
+use crate::scene::{
+    parse_color, AnimatedColor, AnimatedValue, ExpressionContext, LineDash, LineElement,
+};
+
+/// Number of concentric halo rings drawn around a glowing line.
+const GLOW_RINGS: usize = 4;
+/// Halo radius in line widths at `glow` 1.0.
+const GLOW_SPREAD: f32 = 2.0;
+
+/// A straight piece of the path, with its color at each end
+type Segment = (LineVertex, LineVertex);
 
 pub struct LinePrimitive {
     points: Vec<[f32; 3]>,
+    /// Per-point colors parallel to `points`, when the element sets `colors`
+    colors: Option<Vec<[f32; 4]>>,
     closed: bool,
-    base_color: [f32; 4],
+    color: AnimatedColor,
     opacity: AnimatedValue,
+    thickness: f32,
+    glow: f32,
+    dash: Option<LineDash>,
+    draw: AnimatedValue,
 }
 
 impl LinePrimitive {
     pub fn from_element(element: &LineElement) -> Self {
-        let base_color = parse_hex_color(&element.color).unwrap_or([0.0, 1.0, 0.25, 1.0]);
+        let colors = element.colors.as_ref().map(|colors| {
+            colors
+                .iter()
+                .map(|c| parse_color(c).unwrap_or([0.0, 1.0, 0.25, 1.0]))
+                .collect::<Vec<_>>()
+        });
+
+        let (points, colors) = if element.smooth_segments > 0 {
+            let subdivisions = element.smooth_segments;
+            (
+                catmull_rom(&element.points, subdivisions, element.closed),
+                colors.map(|c| blend_colors(&c, subdivisions, element.closed)),
+            )
+        } else {
+            (element.points.clone(), colors)
+        };
 
         Self {
-            points: element.points.clone(),
+            points,
+            colors,
             closed: element.closed,
-            base_color,
+            color: element.color.clone(),
             opacity: element.opacity.clone(),
+            thickness: element.thickness,
+            glow: element.glow,
+            dash: element.dash,
+            draw: element.draw.clone(),
+        }
+    }
+
+    fn segments(&self, colors: &[[f32; 4]]) -> Vec<Segment> {
+        let vertices: Vec<_> = self
+            .points
+            .iter()
+            .zip(colors)
+            .map(|(&position, &color)| LineVertex::new(position, color))
+            .collect();
+        let mut segments: Vec<_> = vertices.windows(2).map(|w| (w[0], w[1])).collect();
+
+        // A closed 2-point line retraces its only segment back to the start
+        if self.closed
+            && vertices.len() >= 2
+            && let Some(&last) = vertices.last()
+        {
+            segments.push((last, vertices[0]));
         }
+
+        segments
     }
 }
 
+/// Sample a uniform Catmull-Rom spline through `points`, `subdivisions` samples per span.
+/// Open splines repeat their end points for the outer tangents; closed ones wrap around
+/// and stop short of the first point, which the closing segment reconnects.
+fn catmull_rom(points: &[[f32; 3]], subdivisions: u32, closed: bool) -> Vec<[f32; 3]> {
+    let n = points.len();
+    if n < 3 {
+        return points.to_vec();
+    }
+
+    let at = |i: isize| {
+        if closed {
+            points[i.rem_euclid(n as isize) as usize]
+        } else {
+            points[i.clamp(0, n as isize - 1) as usize]
+        }
+    };
+    let spans = if closed { n } else { n - 1 };
+
+    let mut smoothed = Vec::with_capacity(spans * subdivisions as usize + 1);
+    for span in 0..spans as isize {
+        let (p0, p1, p2, p3) = (at(span - 1), at(span), at(span + 1), at(span + 2));
+        for step in 0..subdivisions {
+            let t = step as f32 / subdivisions as f32;
+            let (t2, t3) = (t * t, t * t * t);
+            smoothed.push(std::array::from_fn(|axis| {
+                0.5 * (2.0 * p1[axis]
+                    + (p2[axis] - p0[axis]) * t
+                    + (2.0 * p0[axis] - 5.0 * p1[axis] + 4.0 * p2[axis] - p3[axis]) * t2
+                    + (3.0 * p1[axis] - p0[axis] - 3.0 * p2[axis] + p3[axis]) * t3)
+            }));
+        }
+    }
+    if !closed {
+        smoothed.push(points[n - 1]);
+    }
+
+    smoothed
+}
+
+/// Per-point colors matching the samples of `catmull_rom`, blended linearly within each
+/// span so they never overshoot the way the spline's positions may.
+fn blend_colors(colors: &[[f32; 4]], subdivisions: u32, closed: bool) -> Vec<[f32; 4]> {
+    let n = colors.len();
+    if n < 3 {
+        return colors.to_vec();
+    }
+
+    let spans = if closed { n } else { n - 1 };
+    let mut blended = Vec::with_capacity(spans * subdivisions as usize + 1);
+    for span in 0..spans {
+        let (from, to) = (colors[span], colors[(span + 1) % n]);
+        for step in 0..subdivisions {
+            blended.push(lerp4(from, to, step as f32 / subdivisions as f32));
+        }
+    }
+    if !closed {
+        blended.push(colors[n - 1]);
+    }
+
+    blended
+}
+
+fn segment_length(&(start, end): &Segment) -> f32 {
+    length(sub(end.position, start.position))
+}
+
+fn path_length(segments: &[Segment]) -> f32 {
+    segments.iter().map(segment_length).sum()
+}
+
+/// Point and color a fraction `f` of the way from `a` to `b`
+fn lerp_vertex(a: LineVertex, b: LineVertex, f: f32) -> LineVertex {
+    LineVertex::new(
+        add(a.position, scale(sub(b.position, a.position), f)),
+        lerp4(a.color, b.color, f),
+    )
+}
+
+fn lerp4(a: [f32; 4], b: [f32; 4], f: f32) -> [f32; 4] {
+    std::array::from_fn(|i| a[i] + (b[i] - a[i]) * f)
+}
+
+/// Keep the first `visible` units of arc length, cutting the last segment mid-way.
+fn clip_segments(segments: &[Segment], visible: f32) -> Vec<Segment> {
+    let mut clipped = Vec::new();
+    let mut remaining = visible;
+    for segment @ &(start, end) in segments {
+        let len = segment_length(segment);
+        if remaining >= len {
+            clipped.push((start, end));
+            remaining -= len;
+        } else {
+            if remaining > 0.0 {
+                clipped.push((start, lerp_vertex(start, end, remaining / len)));
+            }
+            break;
+        }
+    }
+    clipped
+}
+
+/// Split a path into the "on" intervals of a dash pattern, measured by arc length along
+/// the whole path so dashes continue around corners. The pattern is stretched so a full
+/// path of length `total` starts and ends on a full dash, or repeats seamlessly if closed;
+/// `segments` may be a revealed prefix of that path.
+fn dash_segments(
+    segments: &[Segment],
+    dash: LineDash,
+    total: f32,
+    closed: bool,
+) -> Vec<Segment> {
+    let lengths: Vec<f32> = segments.iter().map(segment_length).collect();
+    let period = dash.on + dash.off;
+    if total <= 0.0 || dash.off <= 0.0 {
+        return segments.to_vec();
+    }
+
+    let stretch = if closed {
+        total / ((total / period).round().max(1.0) * period)
+    } else {
+        let dashes = ((total - dash.on) / period).round().max(0.0);
+        total / (dashes * period + dash.on)
+    };
+    let on = dash.on * stretch;
+    let period = period * stretch;
+
+    let mut dashed = Vec::new();
+    let mut offset = 0.0;
+    for (&(start, end), &len) in segments.iter().zip(&lengths) {
+        if len > 0.0 {
+            let at = |s: f32| lerp_vertex(start, end, (s - offset) / len);
+            let mut k = (offset / period).floor();
+            while k * period < offset + len {
+                let from = (k * period).max(offset);
+                let to = (k * period + on).min(offset + len);
+                if to > from {
+                    dashed.push((at(from), at(to)));
+                }
+                k += 1.0;
+            }
+        }
+        offset += len;
+    }
+
+    dashed
+}
+
 impl Primitive for LinePrimitive {
     fn vertices(&self, ctx: &ExpressionContext) -> Vec<LineVertex> {
         let mut vertices = Vec::new();
@@ -31,26 +234,320 @@ impl Primitive for LinePrimitive {
 
         // Evaluate opacity at render time and clamp to valid range
         let opacity = self.opacity.evaluate(ctx).clamp(0.0, 1.0);
-        let color = [
-            self.base_color[0],
-            self.base_color[1],
-            self.base_color[2],
-            opacity,
-        ];
+        let base_color = self.color.evaluate(ctx).unwrap_or([0.0, 1.0, 0.25, 1.0]);
+        let colors: Vec<[f32; 4]> = match &self.colors {
+            Some(colors) => colors
+                .iter()
+                .map(|c| [c[0], c[1], c[2], c[3] * opacity])
+                .collect(),
+            None => {
+                let color = [base_color[0], base_color[1], base_color[2], base_color[3] * opacity];
+                vec![color; self.points.len()]
+            }
+        };
 
-        for i in 0..self.points.len() - 1 {
-            vertices.push(LineVertex::new(self.points[i], color));
-            vertices.push(LineVertex::new(self.points[i + 1], color));
+        let path = self.segments(&colors);
+        let total = path_length(&path);
+        let draw = self.draw.evaluate(ctx).clamp(0.0, 1.0);
+        let mut segments = if draw < 1.0 {
+            clip_segments(&path, total * draw)
+        } else {
+            path
+        };
+        if let Some(dash) = self.dash {
+            segments = dash_segments(&segments, dash, total, self.closed);
         }
 
-        if self.closed && self.points.len() > 2 {
-            // Safe: points.len() > 2 guarantees last() returns Some
-            if let Some(&last) = self.points.last() {
-                vertices.push(LineVertex::new(last, color));
-                vertices.push(LineVertex::new(self.points[0], color));
+        // Halo first so the crisp core line blends on top of it
+        if self.glow > 0.0 {
+            // Radius in pixels, scaled by depth the way particles are sized so the halo
+            // keeps its screen width at any distance
+            let radius = self.thickness * self.glow * GLOW_SPREAD * ctx.pixel_size;
+            let world_radius = |p: [f32; 3]| {
+                let depth = (0..3)
+                    .map(|i| (p[i] - ctx.camera_position[i]) * ctx.camera_forward[i])
+                    .sum::<f32>();
+                radius * depth.max(0.0)
+            };
+
+            for ring in 1..=GLOW_RINGS {
+                let falloff = 1.0 - ring as f32 / (GLOW_RINGS + 1) as f32;
+                let ring_color =
+                    |c: [f32; 4]| [c[0], c[1], c[2], c[3] * self.glow * 0.35 * falloff];
+                let step = ring as f32 / GLOW_RINGS as f32;
+
+                for &(start, end) in &segments {
+                    let (a, b) = perpendiculars(sub(end.position, start.position));
+                    let start_offset = world_radius(start.position) * step;
+                    let end_offset = world_radius(end.position) * step;
+                    for dir in [a, b, scale(a, -1.0), scale(b, -1.0)] {
+                        vertices.push(LineVertex::new(
+                            add(start.position, scale(dir, start_offset)),
+                            ring_color(start.color),
+                        ));
+                        vertices.push(LineVertex::new(
+                            add(end.position, scale(dir, end_offset)),
+                            ring_color(end.color),
+                        ));
+                    }
+                }
             }
         }
 
+        for (start, end) in segments {
+            vertices.push(start);
+            vertices.push(end);
+        }
+
         vertices
     }
 }
+
+pub(super) fn estimate_vertices(element: &LineElement) -> u64 {
+    let points = &element.points;
+    if points.len() < 2 {
+        return 0;
+    }
+
+    let spans = points.len() as u64 - 1 + element.closed as u64;
+    let mut segments = spans.saturating_mul(element.smooth_segments.max(1) as u64);
+
+    // Dashing adds a piece per pattern period, plus one where a dash straddles a corner.
+    // Control points stand in for the smoothed path, which only bends between them.
+    if let Some(dash) = element.dash {
+        let mut total: f32 = points.windows(2).map(|w| length(sub(w[1], w[0]))).sum();
+        if element.closed {
+            total += length(sub(points[0], points[points.len() - 1]));
+        }
+        // The float cast saturates, so a tiny period must not overflow the sum either
+        segments = segments.saturating_add((total / (dash.on + dash.off)).ceil() as u64);
+    }
+
+    // The glow halo redraws every segment four ways per ring
+    let copies = if element.glow > 0.0 {
+        1 + 4 * GLOW_RINGS as u64
+    } else {
+        1
+    };
+    segments.saturating_mul(copies).saturating_mul(2)
+}
+
+// Two unit vectors perpendicular to `d` and to each other
+fn perpendiculars(d: [f32; 3]) -> ([f32; 3], [f32; 3]) {
+    let d = normalize(d);
+    // Avoid a degenerate cross product for near-vertical segments
+    let reference = if d[1].abs() > 0.9 {
+        [1.0, 0.0, 0.0]
+    } else {
+        [0.0, 1.0, 0.0]
+    };
+    let a = normalize(cross(d, reference));
+    (a, cross(d, a))
+}
+
+fn add(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(v: [f32; 3], s: f32) -> [f32; 3] {
+    [v[0] * s, v[1] * s, v[2] * s]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn length(v: [f32; 3]) -> f32 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let len = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    if len > 0.0 {
+        [v[0] / len, v[1] / len, v[2] / len]
+    } else {
+        [0.0, 0.0, 0.0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::BlendMode;
+
+    fn make_line(glow: f32, closed: bool) -> LineElement {
+        LineElement {
+            points: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]],
+            closed,
+            thickness: 2.0,
+            glow,
+            color: "#00ff41".into(),
+            opacity: AnimatedValue::Static(1.0),
+            enabled: true,
+            blend: BlendMode::Alpha,
+            dash: None,
+            draw: AnimatedValue::Static(1.0),
+            smooth_segments: 0,
+            colors: None,
+        }
+    }
+
+    fn segment(start: [f32; 3], end: [f32; 3]) -> Segment {
+        let white = [1.0; 4];
+        (LineVertex::new(start, white), LineVertex::new(end, white))
+    }
+
+    #[test]
+    fn test_no_glow_matches_core_line() {
+        let ctx = ExpressionContext::new(0, 30);
+        let vertices = LinePrimitive::from_element(&make_line(0.0, false)).vertices(&ctx);
+        assert_eq!(vertices.len(), 4);
+
+        let closed = LinePrimitive::from_element(&make_line(0.0, true)).vertices(&ctx);
+        assert_eq!(closed.len(), 6);
+    }
+
+    #[test]
+    fn test_closed_two_point_line_returns_to_start() {
+        let mut line = make_line(0.0, true);
+        line.points.truncate(2);
+        let vertices = LinePrimitive::from_element(&line).vertices(&ExpressionContext::new(0, 30));
+
+        assert_eq!(vertices.len(), 4);
+        assert_eq!(vertices[2].position, [1.0, 0.0, 0.0]);
+        assert_eq!(vertices[3].position, [0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_glow_adds_fainter_halo() {
+        let ctx = ExpressionContext::new(0, 30);
+        let vertices = LinePrimitive::from_element(&make_line(1.0, false)).vertices(&ctx);
+
+        // 2 segments x 4 rings x 4 offsets, plus the core line
+        assert_eq!(vertices.len(), 2 * GLOW_RINGS * 4 * 2 + 4);
+
+        let (halo, core) = vertices.split_at(vertices.len() - 4);
+        assert!(halo.iter().all(|v| v.color[3] < 1.0));
+        assert!(core.iter().all(|v| v.color[3] == 1.0));
+    }
+
+    #[test]
+    fn test_glow_radius_is_constant_in_pixels() {
+        let mut line = make_line(1.0, false);
+        line.points = vec![[0.0, 0.0, 5.0], [0.0, 0.0, -10.0]];
+        let ctx = ExpressionContext {
+            camera_position: [0.0, 0.0, 10.0],
+            camera_forward: [0.0, 0.0, -1.0],
+            pixel_size: 0.01,
+            ..ExpressionContext::new(0, 1)
+        };
+        let vertices = LinePrimitive::from_element(&line).vertices(&ctx);
+
+        // Outermost ring of a 2 pixel line at glow 1.0 sits GLOW_SPREAD line widths
+        // out, whether the endpoint is at depth 5 or 20
+        let outer = &vertices[(GLOW_RINGS - 1) * 8..GLOW_RINGS * 8];
+        let spread = |v: &LineVertex, z: f32| {
+            assert_eq!(v.position[2], z);
+            (v.position[0].powi(2) + v.position[1].powi(2)).sqrt()
+        };
+        let pixels = 2.0 * GLOW_SPREAD * 0.01;
+        for pair in outer.chunks(2) {
+            assert!((spread(&pair[0], 5.0) - pixels * 5.0).abs() < 1e-5);
+            assert!((spread(&pair[1], -10.0) - pixels * 20.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_dash_covers_on_intervals() {
+        // 10 units of path: four 1-unit dashes with 2-unit gaps fit exactly
+        let segments = [segment([0.0, 0.0, 0.0], [10.0, 0.0, 0.0])];
+        let dash = LineDash { on: 1.0, off: 2.0 };
+        let dashed = dash_segments(&segments, dash, 10.0, false);
+
+        assert_eq!(dashed.len(), 4);
+        assert_eq!(dashed[0].0.position, [0.0, 0.0, 0.0]);
+        assert!((dashed[3].1.position[0] - 10.0).abs() < 1e-4);
+        for (start, end) in dashed {
+            assert!((end.position[0] - start.position[0] - 1.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_dash_continues_around_corners() {
+        // The first dash bends around the corner at 1.0 as two sub-segments
+        let segments = [
+            segment([0.0, 0.0, 0.0], [1.0, 0.0, 0.0]),
+            segment([1.0, 0.0, 0.0], [1.0, 4.0, 0.0]),
+        ];
+        let dashed = dash_segments(&segments, LineDash { on: 1.5, off: 2.0 }, 5.0, false);
+
+        assert_eq!(dashed.len(), 3);
+        assert_eq!(dashed[0].1.position, [1.0, 0.0, 0.0]);
+        assert_eq!(dashed[1].0.position, [1.0, 0.0, 0.0]);
+        assert!((dashed[1].1.position[1] - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_colors_blend_along_path() {
+        let mut line = make_line(0.0, false);
+        line.colors = Some(vec!["#ff0000".into(), "#0000ff".into(), "#0000ff".into()]);
+        line.draw = AnimatedValue::Static(0.25);
+        let vertices = LinePrimitive::from_element(&line).vertices(&ExpressionContext::new(0, 30));
+
+        // Half way along the first segment, red has blended half way to blue
+        assert_eq!(vertices.len(), 2);
+        assert_eq!(vertices[0].color, [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(vertices[1].color, [0.5, 0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn test_draw_clips_at_fractional_length() {
+        // Path is 2 units long; 0.75 reveals the first segment and half the second
+        let mut line = make_line(0.0, false);
+        line.draw = AnimatedValue::Static(0.75);
+        let vertices = LinePrimitive::from_element(&line).vertices(&ExpressionContext::new(0, 30));
+
+        assert_eq!(vertices.len(), 4);
+        assert_eq!(vertices[3].position, [1.0, 0.5, 0.0]);
+
+        line.draw = AnimatedValue::Static(0.0);
+        let hidden = LinePrimitive::from_element(&line).vertices(&ExpressionContext::new(0, 30));
+        assert!(hidden.is_empty());
+    }
+
+    #[test]
+    fn test_catmull_rom_passes_through_points() {
+        let points = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]];
+
+        let open = catmull_rom(&points, 4, false);
+        assert_eq!(open.len(), 2 * 4 + 1);
+        assert_eq!(open[0], points[0]);
+        assert_eq!(open[4], points[1]);
+        assert_eq!(open[8], points[2]);
+
+        // Closed splines add the wrap span and leave the final join to `segments()`
+        let closed = catmull_rom(&points, 4, true);
+        assert_eq!(closed.len(), 3 * 4);
+        assert_eq!(closed[8], points[2]);
+        assert_ne!(closed[11], points[2]);
+    }
+
+    #[test]
+    fn test_perpendiculars_are_orthogonal() {
+        for d in [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.3, -0.2, 0.9]] {
+            let (a, b) = perpendiculars(d);
+            let dot = |x: [f32; 3], y: [f32; 3]| x[0] * y[0] + x[1] * y[1] + x[2] * y[2];
+            assert!(dot(a, d).abs() < 1e-5);
+            assert!(dot(b, d).abs() < 1e-5);
+            assert!(dot(a, b).abs() < 1e-5);
+        }
+    }
+}
@@ -3,32 +3,55 @@ mod geometry;
 mod glyph;
 mod grid;
 mod line;
+mod obj;
 mod particles;
 mod wireframe;
 
 pub use axes::AxesPrimitive;
-pub use geometry::generate_geometry;
+pub use geometry::{edge_count, generate_geometry, GeometryData};
 pub use glyph::GlyphPrimitive;
 pub use grid::GridPrimitive;
 pub use line::LinePrimitive;
+pub use obj::{load_obj, ObjError};
 pub use particles::ParticlesPrimitive;
 pub use wireframe::WireframePrimitive;
 
-use crate::scene::ExpressionContext;
+use crate::scene::{Element, ExpressionContext};
 
 pub trait Primitive {
     fn vertices(&self, ctx: &ExpressionContext) -> Vec<LineVertex>;
 }
 
+/// Upper bound on the vertices an element emits in any one frame, worked out from its
+/// parameters without generating them, so oversized scenes fail validation instead of
+/// exhausting memory.
+pub fn estimate_vertices(element: &Element) -> u64 {
+    match element {
+        Element::Grid(e) => grid::estimate_vertices(e),
+        Element::Wireframe(e) => wireframe::estimate_vertices(e),
+        Element::Glyph(e) => glyph::estimate_vertices(e),
+        Element::Line(e) => line::estimate_vertices(e),
+        Element::Particles(e) => particles::estimate_vertices(e),
+        Element::Axes(_) => axes::VERTEX_COUNT,
+    }
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct LineVertex {
     pub position: [f32; 3],
     pub color: [f32; 4],
+    /// Clip-space depth pulled toward the camera when depth testing, set per element
+    /// by the renderer
+    pub depth_offset: f32,
 }
 
 impl LineVertex {
     pub fn new(position: [f32; 3], color: [f32; 4]) -> Self {
-        Self { position, color }
+        Self {
+            position,
+            color,
+            depth_offset: 0.0,
+        }
     }
 }
@@ -0,0 +1,158 @@
+use super::geometry::GeometryData;
+use std::collections::HashSet;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ObjError {
+    #[error("failed to read: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("line {line}: {message}")]
+    Parse { line: usize, message: String },
+
+    #[error("no faces found")]
+    Empty,
+}
+
+/// Load an OBJ file's edges, normalized to fit a unit bounding box at the origin.
+pub fn load_obj(path: &Path) -> Result<GeometryData, ObjError> {
+    parse_obj(&std::fs::read_to_string(path)?)
+}
+
+/// Parse OBJ `v` and `f` statements into a de-duplicated edge list. Texture
+/// and normal indices are ignored; everything else is skipped.
+pub fn parse_obj(source: &str) -> Result<GeometryData, ObjError> {
+    let mut vertices = Vec::new();
+    let mut edges = Vec::new();
+    let mut faces = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (i, line) in source.lines().enumerate() {
+        let line_no = i + 1;
+        let parse_error = |message: String| ObjError::Parse {
+            line: line_no,
+            message,
+        };
+
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                let coords = tokens
+                    .take(3)
+                    .map(|t| t.parse::<f32>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| parse_error(e.to_string()))?;
+                if coords.len() != 3 {
+                    return Err(parse_error("vertex needs x, y and z".to_string()));
+                }
+                vertices.push([coords[0], coords[1], coords[2]]);
+            }
+            Some("f") => {
+                let indices = tokens
+                    .map(|t| resolve_index(t, vertices.len()))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(parse_error)?;
+                if indices.len() < 3 {
+                    return Err(parse_error("face needs at least 3 vertices".to_string()));
+                }
+
+                // Faces sharing an edge list it in opposite winding; store it once
+                for (k, &a) in indices.iter().enumerate() {
+                    let b = indices[(k + 1) % indices.len()];
+                    if a != b && seen.insert((a.min(b), a.max(b))) {
+                        edges.push((a, b));
+                    }
+                }
+                faces.push(indices);
+            }
+            _ => {}
+        }
+    }
+
+    if edges.is_empty() {
+        return Err(ObjError::Empty);
+    }
+
+    normalize(&mut vertices);
+    Ok(GeometryData {
+        vertices,
+        edges,
+        faces,
+    })
+}
+
+// OBJ indices are 1-based, or negative relative to the vertices read so far
+fn resolve_index(token: &str, vertex_count: usize) -> Result<usize, String> {
+    let raw = token.split('/').next().unwrap_or_default();
+    let index: i64 = raw
+        .parse()
+        .map_err(|_| format!("invalid face index '{}'", token))?;
+
+    let resolved = if index < 0 {
+        vertex_count as i64 + index
+    } else {
+        index - 1
+    };
+
+    if resolved < 0 || resolved >= vertex_count as i64 {
+        return Err(format!("face index {} out of range", index));
+    }
+    Ok(resolved as usize)
+}
+
+// Center on the origin and scale the largest extent to 1.0
+fn normalize(vertices: &mut [[f32; 3]]) {
+    let mut min = [f32::MAX; 3];
+    let mut max = [f32::MIN; 3];
+    for v in vertices.iter() {
+        for axis in 0..3 {
+            min[axis] = min[axis].min(v[axis]);
+            max[axis] = max[axis].max(v[axis]);
+        }
+    }
+
+    let extent = (0..3).map(|axis| max[axis] - min[axis]).fold(0.0, f32::max);
+    let scale = if extent > 0.0 { 1.0 / extent } else { 1.0 };
+    let center = [
+        (min[0] + max[0]) / 2.0,
+        (min[1] + max[1]) / 2.0,
+        (min[2] + max[2]) / 2.0,
+    ];
+
+    for v in vertices.iter_mut() {
+        for axis in 0..3 {
+            v[axis] = (v[axis] - center[axis]) * scale;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_edges_are_deduplicated() {
+        // A quad and a triangle sharing the edge 2-3
+        let source = "v 0 0 0\nv 2 0 0\nv 2 2 0\nv 0 2 0\nv 1 3 0\nf 1 2 3 4\nf 4 3 5\n";
+        let geometry = parse_obj(source).expect("obj should parse");
+        assert_eq!(geometry.vertices.len(), 5);
+        assert_eq!(geometry.edges.len(), 6);
+    }
+
+    #[test]
+    fn test_normalized_to_unit_box() {
+        let source = "v 10 10 10\nv 14 10 10\nv 10 12 10\nf 1/1/1 2/2/2 -1//3\n";
+        let geometry = parse_obj(source).expect("obj should parse");
+        assert_eq!(geometry.vertices[0], [-0.5, -0.25, 0.0]);
+        assert_eq!(geometry.vertices[1], [0.5, -0.25, 0.0]);
+        assert_eq!(geometry.vertices[2], [-0.5, 0.25, 0.0]);
+    }
+
+    #[test]
+    fn test_out_of_range_index() {
+        let result = parse_obj("v 0 0 0\nv 1 0 0\nf 1 2 3\n");
+        assert!(matches!(result, Err(ObjError::Parse { line: 3, .. })));
+        assert!(matches!(parse_obj("v 0 0 0\n"), Err(ObjError::Empty)));
+    }
+}
@@ -1,99 +1,300 @@
 use super::{LineVertex, Primitive};
-use crate::scene::{parse_hex_color, AnimatedValue, ExpressionContext, ParticlesElement};
+use crate::scene::{
+    AnimatedColor, AnimatedValue, ExpressionContext, ParticleDistribution, ParticleShape,
+    ParticlesElement,
+};
+use std::f32::consts::{PI, TAU};
 
 pub struct ParticlesPrimitive {
     positions: Vec<[f32; 3]>,
-    base_color: [f32; 4],
+    color: AnimatedColor,
     opacity: AnimatedValue,
     size: f32,
     depth_fade: bool,
-    bounds: [f32; 3],
+    /// Largest |z| a particle can have, where depth fade is strongest
+    max_z: f32,
+    segments: Vec<[[f32; 2]; 2]>,
 }
 
 impl ParticlesPrimitive {
     pub fn from_element(element: &ParticlesElement) -> Self {
-        let base_color = parse_hex_color(&element.color).unwrap_or([0.0, 1.0, 0.25, 1.0]);
-
-        // Generate particle positions using a simple PRNG
-        let mut positions = Vec::with_capacity(element.count as usize);
-        let mut seed = if element.seed == 0 {
+        let seed = if element.seed == 0 {
             12345u64
         } else {
             element.seed
         };
 
-        for _ in 0..element.count {
-            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
-            let x = ((seed >> 16) as f32 / 65535.0 - 0.5) * element.bounds[0];
-
-            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
-            let y = ((seed >> 16) as f32 / 65535.0 - 0.5) * element.bounds[1];
+        // Every particle draws from its own stream keyed by its index, so changing
+        // `count` adds or removes particles at the end without moving the rest
+        let radius = element.bounds[0];
+        let positions = (0..element.count)
+            .map(|index| {
+                let mut state = particle_seed(seed, index);
+                match element.distribution {
+                    ParticleDistribution::Box => {
+                        let x = next_signed_unit(&mut state) * 0.5;
+                        let y = next_signed_unit(&mut state) * 0.5;
+                        let z = next_signed_unit(&mut state) * 0.5;
+                        [
+                            x * element.bounds[0],
+                            y * element.bounds[1],
+                            z * element.bounds[2],
+                        ]
+                    }
+                    // Rejection sampling from the enclosing cube keeps the fill uniform
+                    ParticleDistribution::Sphere => loop {
+                        let x = next_signed_unit(&mut state);
+                        let y = next_signed_unit(&mut state);
+                        let z = next_signed_unit(&mut state);
+                        if x * x + y * y + z * z <= 1.0 {
+                            break [x * radius, y * radius, z * radius];
+                        }
+                    },
+                    ParticleDistribution::Disk => loop {
+                        let x = next_signed_unit(&mut state);
+                        let z = next_signed_unit(&mut state);
+                        if x * x + z * z <= 1.0 {
+                            break [x * radius, 0.0, z * radius];
+                        }
+                    },
+                }
+            })
+            .collect();
 
-            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
-            let z = ((seed >> 16) as f32 / 65535.0 - 0.5) * element.bounds[2];
-
-            positions.push([x, y, z]);
-        }
+        let max_z = match element.distribution {
+            ParticleDistribution::Box => element.bounds[2] / 2.0,
+            ParticleDistribution::Sphere | ParticleDistribution::Disk => radius,
+        };
 
         Self {
             positions,
-            base_color,
+            color: element.color.clone(),
             opacity: element.opacity.clone(),
             size: element.size,
             depth_fade: element.depth_fade,
-            bounds: element.bounds,
+            max_z,
+            segments: shape_segments(element.shape),
         }
     }
 }
 
+pub(super) fn estimate_vertices(element: &ParticlesElement) -> u64 {
+    (element.count as u64)
+        .saturating_mul(shape_segments(element.shape).len() as u64)
+        .saturating_mul(2)
+}
+
+/// Starting state for one particle's stream: a SplitMix64 hash of the element seed
+/// and the particle index
+fn particle_seed(seed: u64, index: u32) -> u64 {
+    let mut z = seed ^ (index as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Advance the particle LCG and return its new state
+fn next_seed(seed: &mut u64) -> u64 {
+    *seed = seed
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    *seed
+}
+
+/// Uniform value in [-1, 1) from the high bits of the next LCG state
+fn next_signed_unit(seed: &mut u64) -> f32 {
+    (next_seed(seed) >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+}
+
+/// Line segments of a particle shape in the XY plane, as unit offsets from its center
+fn shape_segments(shape: ParticleShape) -> Vec<[[f32; 2]; 2]> {
+    // Endpoints on a circle, used for both the octagon and the spokes of the star
+    let polar = |angle: f32, radius: f32| [radius * angle.cos(), radius * angle.sin()];
+
+    match shape {
+        ParticleShape::Cross => vec![[[-1.0, 0.0], [1.0, 0.0]], [[0.0, -1.0], [0.0, 1.0]]],
+        ParticleShape::Dot => (0..8)
+            .map(|i| {
+                let a0 = i as f32 / 8.0 * TAU;
+                let a1 = (i + 1) as f32 / 8.0 * TAU;
+                [polar(a0, 0.5), polar(a1, 0.5)]
+            })
+            .collect(),
+        ParticleShape::Square => vec![
+            [[-1.0, -1.0], [1.0, -1.0]],
+            [[1.0, -1.0], [1.0, 1.0]],
+            [[1.0, 1.0], [-1.0, 1.0]],
+            [[-1.0, 1.0], [-1.0, -1.0]],
+        ],
+        ParticleShape::Star => (0..3)
+            .map(|i| {
+                let angle = PI / 2.0 + i as f32 * PI / 3.0;
+                [polar(angle, 1.0), polar(angle + PI, 1.0)]
+            })
+            .collect(),
+    }
+}
+
 impl Primitive for ParticlesPrimitive {
     fn vertices(&self, ctx: &ExpressionContext) -> Vec<LineVertex> {
         let mut vertices = Vec::new();
 
         // Evaluate opacity at render time and clamp to valid range
-        let base_opacity = self.opacity.evaluate(ctx).clamp(0.0, 1.0);
+        let base_color = self.color.evaluate(ctx).unwrap_or([0.0, 1.0, 0.25, 1.0]);
+        let base_opacity = self.opacity.evaluate(ctx).clamp(0.0, 1.0) * base_color[3];
 
-        // Draw particles as small crosses
-        let half_size = self.size * 0.02; // Scale down for world space
+        let [right, up] = [ctx.camera_right, ctx.camera_up];
 
         for pos in &self.positions {
+            // Grow with depth so every particle covers `size` pixels on screen; those
+            // behind the camera are never visible
+            let offset = [0, 1, 2].map(|i| pos[i] - ctx.camera_position[i]);
+            let depth = (0..3).map(|i| offset[i] * ctx.camera_forward[i]).sum::<f32>();
+            if depth <= 0.0 {
+                continue;
+            }
+            let half_size = self.size * 0.5 * ctx.pixel_size * depth;
+
             let mut opacity = base_opacity;
 
             // Apply depth fade based on Z position
             if self.depth_fade {
-                let max_z = self.bounds[2] / 2.0;
-                let fade = 1.0 - (pos[2].abs() / max_z).min(1.0) * 0.7;
+                let fade = 1.0 - (pos[2].abs() / self.max_z).min(1.0) * 0.7;
                 opacity *= fade;
             }
 
-            let color = [
-                self.base_color[0],
-                self.base_color[1],
-                self.base_color[2],
-                opacity,
-            ];
-
-            // Horizontal line
-            vertices.push(LineVertex::new(
-                [pos[0] - half_size, pos[1], pos[2]],
-                color,
-            ));
-            vertices.push(LineVertex::new(
-                [pos[0] + half_size, pos[1], pos[2]],
-                color,
-            ));
-
-            // Vertical line
-            vertices.push(LineVertex::new(
-                [pos[0], pos[1] - half_size, pos[2]],
-                color,
-            ));
-            vertices.push(LineVertex::new(
-                [pos[0], pos[1] + half_size, pos[2]],
-                color,
-            ));
+            let color = [base_color[0], base_color[1], base_color[2], opacity];
+
+            // Draw each particle as its shape's line segments in the screen plane
+            for segment in &self.segments {
+                for [dx, dy] in segment {
+                    let point = [0, 1, 2]
+                        .map(|i| pos[i] + (right[i] * dx + up[i] * dy) * half_size);
+                    vertices.push(LineVertex::new(point, color));
+                }
+            }
         }
 
         vertices
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::BlendMode;
+
+    fn make_particles(distribution: ParticleDistribution, seed: u64) -> ParticlesElement {
+        ParticlesElement {
+            count: 200,
+            bounds: [4.0, 4.0, 4.0],
+            size: 2.0,
+            depth_fade: true,
+            color: "#00ff41".into(),
+            opacity: AnimatedValue::Static(1.0),
+            seed,
+            enabled: true,
+            blend: BlendMode::Alpha,
+            shape: ParticleShape::Cross,
+            distribution,
+        }
+    }
+
+    #[test]
+    fn test_sphere_and_disk_stay_within_radius() {
+        let element = make_particles(ParticleDistribution::Sphere, 7);
+        let sphere = ParticlesPrimitive::from_element(&element);
+        assert_eq!(sphere.positions.len(), 200);
+        for [x, y, z] in &sphere.positions {
+            assert!((x * x + y * y + z * z).sqrt() <= 4.0 + 1e-4);
+        }
+
+        let element = make_particles(ParticleDistribution::Disk, 7);
+        let disk = ParticlesPrimitive::from_element(&element);
+        for [x, y, z] in &disk.positions {
+            assert_eq!(*y, 0.0);
+            assert!((x * x + z * z).sqrt() <= 4.0 + 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_distribution_is_deterministic_per_seed() {
+        let a = ParticlesPrimitive::from_element(&make_particles(ParticleDistribution::Sphere, 3));
+        let b = ParticlesPrimitive::from_element(&make_particles(ParticleDistribution::Sphere, 3));
+        let c = ParticlesPrimitive::from_element(&make_particles(ParticleDistribution::Sphere, 4));
+        assert_eq!(a.positions, b.positions);
+        assert_ne!(a.positions, c.positions);
+    }
+
+    #[test]
+    fn test_box_stays_within_bounds() {
+        let element = make_particles(ParticleDistribution::Box, 9);
+        let particles = ParticlesPrimitive::from_element(&element);
+        for position in &particles.positions {
+            assert!(position.iter().all(|c| c.abs() <= 2.0));
+        }
+    }
+
+    #[test]
+    fn test_increasing_count_keeps_existing_particles() {
+        for distribution in [
+            ParticleDistribution::Box,
+            ParticleDistribution::Sphere,
+            ParticleDistribution::Disk,
+        ] {
+            let fewer = ParticlesPrimitive::from_element(&ParticlesElement {
+                count: 100,
+                ..make_particles(distribution, 5)
+            });
+            let more = ParticlesPrimitive::from_element(&ParticlesElement {
+                count: 120,
+                ..make_particles(distribution, 5)
+            });
+            assert_eq!(fewer.positions[..], more.positions[..100]);
+        }
+    }
+
+    #[test]
+    fn test_size_is_constant_in_pixels() {
+        let mut particles =
+            ParticlesPrimitive::from_element(&make_particles(ParticleDistribution::Box, 1));
+        particles.positions = vec![[0.0, 0.0, 5.0], [0.0, 0.0, -10.0], [0.0, 0.0, 20.0]];
+        particles.depth_fade = false;
+        let ctx = ExpressionContext {
+            camera_position: [0.0, 0.0, 10.0],
+            camera_forward: [0.0, 0.0, -1.0],
+            pixel_size: 0.01,
+            ..ExpressionContext::new(0, 1)
+        };
+        let vertices = particles.vertices(&ctx);
+
+        // Two cross strokes per particle; the one behind the camera is dropped
+        assert_eq!(vertices.len(), 2 * 4);
+        let width = |first: usize| vertices[first + 1].position[0] - vertices[first].position[0];
+        // size 2 pixels at depths 5 and 20 covers the same 2 pixels of screen
+        assert!((width(0) - 2.0 * 0.01 * 5.0).abs() < 1e-6);
+        assert!((width(4) - 2.0 * 0.01 * 20.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_shape_segment_counts() {
+        assert_eq!(shape_segments(ParticleShape::Cross).len(), 2);
+        assert_eq!(shape_segments(ParticleShape::Dot).len(), 8);
+        assert_eq!(shape_segments(ParticleShape::Square).len(), 4);
+        assert_eq!(shape_segments(ParticleShape::Star).len(), 3);
+    }
+
+    #[test]
+    fn test_shapes_stay_within_unit_extent() {
+        for shape in [
+            ParticleShape::Cross,
+            ParticleShape::Dot,
+            ParticleShape::Square,
+            ParticleShape::Star,
+        ] {
+            for [x, y] in shape_segments(shape).into_iter().flatten() {
+                assert!(x.abs() <= 1.0 + 1e-6 && y.abs() <= 1.0 + 1e-6);
+            }
+        }
+    }
+}
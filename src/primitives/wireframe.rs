@@ -1,73 +1,191 @@
-use super::{generate_geometry, LineVertex, Primitive};
-use crate::scene::{parse_hex_color, ExpressionContext, WireframeElement};
+use super::geometry::{centroid, dot, face_normal};
+use super::{edge_count, generate_geometry, GeometryData, LineVertex, Primitive};
+use crate::scene::{
+    parse_color, AnimatedRotation, ExpressionContext, Rotation, RotationOrder, WireframeElement,
+};
+use std::collections::HashMap;
 
 pub struct WireframePrimitive {
     element: WireframeElement,
-    base_color: [f32; 4],
+    edge_colors: HashMap<usize, [f32; 4]>,
 }
 
 impl WireframePrimitive {
     pub fn from_element(element: &WireframeElement) -> Self {
-        let base_color = parse_hex_color(&element.color).unwrap_or([0.0, 1.0, 0.25, 1.0]);
+        // Later entries for the same edge win; unparseable colors were caught by validation
+        let edge_colors = element
+            .edge_colors
+            .iter()
+            .filter_map(|accent| Some((accent.edge, parse_color(&accent.color)?)))
+            .collect();
 
         Self {
             element: element.clone(),
-            base_color,
+            edge_colors,
         }
     }
 
     fn apply_transform(&self, point: [f32; 3], ctx: &ExpressionContext) -> [f32; 3] {
         let scale = self.element.scale.evaluate(ctx);
+        let order = self.element.rotation_order;
 
         // Apply scale
         let mut p = [point[0] * scale[0], point[1] * scale[1], point[2] * scale[2]];
 
-        // Evaluate rotation
-        let rx = self.element.rotation.x.evaluate(ctx).to_radians();
-        let ry = self.element.rotation.y.evaluate(ctx).to_radians();
-        let rz = self.element.rotation.z.evaluate(ctx).to_radians();
-
-        // Apply rotation (Y * X * Z order)
-        p = rotate_y(p, ry);
-        p = rotate_x(p, rx);
-        p = rotate_z(p, rz);
+        // Apply rotation
+        match &self.element.rotation {
+            Rotation::Euler(rotation) => p = rotate_euler(p, rotation, order, 1.0, ctx),
+            Rotation::AxisAngle { axis, angle } => {
+                p = rotate_axis(p, *axis, angle.evaluate(ctx).to_radians());
+            }
+        }
 
-        // Apply translation
-        p[0] += self.element.position[0];
-        p[1] += self.element.position[1];
-        p[2] += self.element.position[2];
+        // Apply translation, stepping each repeated copy further along
+        let mut position = self.element.position.evaluate(ctx);
+        if let Some(repeat) = &self.element.repeat {
+            let steps = ctx.instance as f32;
+            p = rotate_euler(p, &repeat.rotation_step, order, steps, ctx);
+            for (axis, offset) in repeat.offset.iter().enumerate() {
+                position[axis] += offset * steps;
+            }
+        }
+        p[0] += position[0];
+        p[1] += position[1];
+        p[2] += position[2];
 
         p
     }
-}
-
-impl Primitive for WireframePrimitive {
-    fn vertices(&self, ctx: &ExpressionContext) -> Vec<LineVertex> {
-        let geometry = generate_geometry(&self.element.geometry);
 
+    /// Append one copy's edges, transformed for `ctx.instance`.
+    fn push_instance(
+        &self,
+        geometry: &GeometryData,
+        ctx: &ExpressionContext,
+        vertices: &mut Vec<LineVertex>,
+    ) {
         // Evaluate opacity at render time and clamp to valid range
         let opacity = self.element.opacity.evaluate(ctx).clamp(0.0, 1.0);
-        let color = [
-            self.base_color[0],
-            self.base_color[1],
-            self.base_color[2],
-            opacity,
-        ];
+        let base_color = self.element.color.evaluate(ctx).unwrap_or([0.0, 1.0, 0.25, 1.0]);
+        let color = [base_color[0], base_color[1], base_color[2], base_color[3] * opacity];
 
-        let mut vertices = Vec::new();
+        let world: Vec<[f32; 3]> = geometry
+            .vertices
+            .iter()
+            .map(|&v| self.apply_transform(v, ctx))
+            .collect();
+        let hidden = if self.element.hidden_line {
+            // A mirroring scale flips the winding, and with it every face normal
+            let scale = self.element.scale.evaluate(ctx);
+            let mirrored = scale[0] * scale[1] * scale[2] < 0.0;
+            hidden_edges(&world, &geometry.faces, ctx.camera_position, mirrored)
+        } else {
+            HashMap::new()
+        };
+        let hidden_opacity = self.element.hidden_line_opacity;
 
-        for (start_idx, end_idx) in geometry.edges {
-            let start = self.apply_transform(geometry.vertices[start_idx], ctx);
-            let end = self.apply_transform(geometry.vertices[end_idx], ctx);
+        for (edge, &(start_idx, end_idx)) in geometry.edges.iter().enumerate() {
+            let mut color = match self.edge_colors.get(&edge) {
+                Some(accent) => [accent[0], accent[1], accent[2], accent[3] * opacity],
+                None => color,
+            };
 
-            vertices.push(LineVertex::new(start, color));
-            vertices.push(LineVertex::new(end, color));
+            let key = (start_idx.min(end_idx), start_idx.max(end_idx));
+            if hidden.get(&key).copied().unwrap_or(false) {
+                if hidden_opacity <= 0.0 {
+                    continue;
+                }
+                color[3] *= hidden_opacity;
+            }
+
+            vertices.push(LineVertex::new(world[start_idx], color));
+            vertices.push(LineVertex::new(world[end_idx], color));
+        }
+    }
+}
+
+impl Primitive for WireframePrimitive {
+    fn vertices(&self, ctx: &ExpressionContext) -> Vec<LineVertex> {
+        let geometry = generate_geometry(&self.element.geometry, &self.element.params);
+        let count = self.element.repeat.as_ref().map_or(1, |repeat| repeat.count);
+
+        let mut vertices = Vec::new();
+        for instance in 0..count {
+            let ctx = ExpressionContext {
+                instance,
+                instance_count: count,
+                ..*ctx
+            };
+            self.push_instance(&geometry, &ctx, &mut vertices);
         }
 
         vertices
     }
 }
 
+pub(super) fn estimate_vertices(element: &WireframeElement) -> u64 {
+    let copies = element.repeat.as_ref().map_or(1, |repeat| repeat.count as u64);
+    edge_count(&element.geometry, &element.params)
+        .saturating_mul(2)
+        .saturating_mul(copies)
+}
+
+/// Rotates a point about one axis by an angle in radians.
+type AxisRotation = fn([f32; 3], f32) -> [f32; 3];
+
+/// Apply Euler angles in degrees, each multiplied by `steps`, in the given axis order.
+fn rotate_euler(
+    p: [f32; 3],
+    rotation: &AnimatedRotation,
+    order: RotationOrder,
+    steps: f32,
+    ctx: &ExpressionContext,
+) -> [f32; 3] {
+    let rx = (rotation.x.evaluate(ctx) * steps).to_radians();
+    let ry = (rotation.y.evaluate(ctx) * steps).to_radians();
+    let rz = (rotation.z.evaluate(ctx) * steps).to_radians();
+
+    // First axis first
+    let sequence: [(AxisRotation, f32); 3] = match order {
+        RotationOrder::Xyz => [(rotate_x, rx), (rotate_y, ry), (rotate_z, rz)],
+        RotationOrder::Xzy => [(rotate_x, rx), (rotate_z, rz), (rotate_y, ry)],
+        RotationOrder::Yxz => [(rotate_y, ry), (rotate_x, rx), (rotate_z, rz)],
+        RotationOrder::Yzx => [(rotate_y, ry), (rotate_z, rz), (rotate_x, rx)],
+        RotationOrder::Zxy => [(rotate_z, rz), (rotate_x, rx), (rotate_y, ry)],
+        RotationOrder::Zyx => [(rotate_z, rz), (rotate_y, ry), (rotate_x, rx)],
+    };
+    sequence
+        .into_iter()
+        .fold(p, |p, (rotate, angle)| rotate(p, angle))
+}
+
+/// Map each edge that borders a face to whether it is hidden, which is when every face
+/// it borders points away from the camera. Edges on no face are absent and stay visible.
+fn hidden_edges(
+    world: &[[f32; 3]],
+    faces: &[Vec<usize>],
+    camera_position: [f32; 3],
+    mirrored: bool,
+) -> HashMap<(usize, usize), bool> {
+    let mut hidden = HashMap::new();
+    for face in faces {
+        let center = centroid(world, face);
+        let to_camera = [
+            camera_position[0] - center[0],
+            camera_position[1] - center[1],
+            camera_position[2] - center[2],
+        ];
+        let facing = dot(face_normal(world, face), to_camera);
+        let back = if mirrored { facing >= 0.0 } else { facing <= 0.0 };
+
+        for (k, &a) in face.iter().enumerate() {
+            let b = face[(k + 1) % face.len()];
+            let entry = hidden.entry((a.min(b), a.max(b))).or_insert(true);
+            *entry &= back;
+        }
+    }
+    hidden
+}
+
 fn rotate_x(p: [f32; 3], angle: f32) -> [f32; 3] {
     let cos_a = angle.cos();
     let sin_a = angle.sin();
@@ -85,3 +203,192 @@ fn rotate_z(p: [f32; 3], angle: f32) -> [f32; 3] {
     let sin_a = angle.sin();
     [p[0] * cos_a - p[1] * sin_a, p[0] * sin_a + p[1] * cos_a, p[2]]
 }
+
+/// Rotate counterclockwise about `axis` (looking down it toward the origin) with the
+/// Rodrigues rotation matrix. A zero axis leaves the point alone.
+fn rotate_axis(p: [f32; 3], axis: [f32; 3], angle: f32) -> [f32; 3] {
+    let length = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+    if length == 0.0 {
+        return p;
+    }
+    let [x, y, z] = [axis[0] / length, axis[1] / length, axis[2] / length];
+    let (sin_a, cos_a) = angle.sin_cos();
+    let t = 1.0 - cos_a;
+
+    let matrix = [
+        [t * x * x + cos_a, t * x * y - sin_a * z, t * x * z + sin_a * y],
+        [t * x * y + sin_a * z, t * y * y + cos_a, t * y * z - sin_a * x],
+        [t * x * z - sin_a * y, t * y * z + sin_a * x, t * z * z + cos_a],
+    ];
+    matrix.map(|row| row[0] * p[0] + row[1] * p[1] + row[2] * p[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::{
+        AnimatedPoint, AnimatedValue, EdgeColor, GeometryType, Scale, WireframeRepeat,
+    };
+
+    fn visible_alphas(element: &WireframeElement, camera_position: [f32; 3]) -> Vec<f32> {
+        let ctx = ExpressionContext {
+            camera_position,
+            ..ExpressionContext::new(0, 1)
+        };
+        let vertices = WireframePrimitive::from_element(element).vertices(&ctx);
+        vertices.iter().step_by(2).map(|v| v.color[3]).collect()
+    }
+
+    #[test]
+    fn test_hidden_line_off_draws_every_edge() {
+        let alphas = visible_alphas(&WireframeElement::default(), [0.0, 0.0, 5.0]);
+        assert_eq!(alphas.len(), 12);
+        assert!(alphas.iter().all(|&a| a == 1.0));
+    }
+
+    #[test]
+    fn test_hidden_line_dims_back_edges() {
+        let element = WireframeElement {
+            hidden_line: true,
+            hidden_line_opacity: 0.25,
+            ..WireframeElement::default()
+        };
+
+        // Looking straight at the +Z face, only its four edges are in front
+        let alphas = visible_alphas(&element, [0.0, 0.0, 5.0]);
+        assert_eq!(alphas.iter().filter(|&&a| a == 1.0).count(), 4);
+        assert_eq!(alphas.iter().filter(|&&a| a == 0.25).count(), 8);
+
+        // From a corner three faces show, sharing nine edges
+        let alphas = visible_alphas(&element, [5.0, 5.0, 5.0]);
+        assert_eq!(alphas.iter().filter(|&&a| a == 1.0).count(), 9);
+    }
+
+    #[test]
+    fn test_hidden_line_zero_opacity_removes_edges() {
+        let element = WireframeElement {
+            hidden_line: true,
+            hidden_line_opacity: 0.0,
+            ..WireframeElement::default()
+        };
+        assert_eq!(visible_alphas(&element, [0.0, 0.0, 5.0]).len(), 4);
+    }
+
+    #[test]
+    fn test_edge_colors_override_listed_edges() {
+        let element = WireframeElement {
+            opacity: AnimatedValue::Static(0.5),
+            edge_colors: vec![EdgeColor {
+                edge: 3,
+                color: "#ff0000".to_string(),
+            }],
+            ..WireframeElement::default()
+        };
+        let ctx = ExpressionContext::new(0, 1);
+        let vertices = WireframePrimitive::from_element(&element).vertices(&ctx);
+
+        assert_eq!(vertices[6].color, [1.0, 0.0, 0.0, 0.5]);
+        assert_eq!(vertices[7].color, [1.0, 0.0, 0.0, 0.5]);
+        assert_eq!(vertices[0].color, [0.0, 1.0, 65.0 / 255.0, 0.5]);
+    }
+
+    #[test]
+    fn test_rotation_order() {
+        let rotated = |rotation_order| {
+            let element = WireframeElement {
+                rotation: Rotation::Euler(AnimatedRotation {
+                    x: AnimatedValue::Static(90.0),
+                    y: AnimatedValue::Static(90.0),
+                    z: AnimatedValue::Static(0.0),
+                }),
+                rotation_order,
+                ..WireframeElement::default()
+            };
+            let primitive = WireframePrimitive::from_element(&element);
+            primitive.apply_transform([1.0, 0.0, 0.0], &ExpressionContext::new(0, 1))
+        };
+        let close = |a: [f32; 3], b: [f32; 3]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-5);
+
+        // Y first swings +X to -Z, which X then lifts to +Y
+        assert!(close(rotated(RotationOrder::Yxz), [0.0, 1.0, 0.0]));
+        // X first leaves +X alone, then Y swings it to -Z
+        assert!(close(rotated(RotationOrder::Xyz), [0.0, 0.0, -1.0]));
+    }
+
+    #[test]
+    fn test_axis_angle_matches_euler_about_principal_axes() {
+        for angle in [30.0, 90.0, 200.0] {
+            let point = [0.3, -0.7, 1.1];
+            let expected = rotate_y(point, f32::to_radians(angle));
+            let actual = rotate_axis(point, [0.0, 2.0, 0.0], f32::to_radians(angle));
+            assert!(expected.iter().zip(actual).all(|(a, b)| (a - b).abs() < 1e-5));
+        }
+
+        // A third of a turn about the diagonal cycles the axes
+        let cycled = rotate_axis([1.0, 0.0, 0.0], [1.0, 1.0, 1.0], f32::to_radians(120.0));
+        assert!(cycled.iter().zip([0.0, 1.0, 0.0]).all(|(a, b)| (a - b).abs() < 1e-5));
+    }
+
+    #[test]
+    fn test_repeat_offsets_each_copy() {
+        let element = WireframeElement {
+            position: AnimatedPoint::Static([0.0, 1.0, 0.0]),
+            repeat: Some(WireframeRepeat {
+                count: 3,
+                offset: [2.0, 0.0, 0.0],
+                rotation_step: AnimatedRotation::default(),
+            }),
+            ..WireframeElement::default()
+        };
+        let ctx = ExpressionContext::new(0, 1);
+        let vertices = WireframePrimitive::from_element(&element).vertices(&ctx);
+        assert_eq!(vertices.len(), 3 * 24);
+
+        // Each copy's first edge is the cube's first, shifted by another offset
+        for copy in 0..3 {
+            let start = vertices[copy * 24].position;
+            assert_eq!(start, [-0.5 + 2.0 * copy as f32, 0.5, -0.5]);
+        }
+    }
+
+    #[test]
+    fn test_repeat_rotation_step_and_instance() {
+        let element = WireframeElement {
+            geometry: GeometryType::Custom {
+                vertices: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]],
+                edges: vec![[0, 1]],
+            },
+            scale: Scale::UniformExpression("instance + 1".into()),
+            repeat: Some(WireframeRepeat {
+                count: 2,
+                offset: [0.0; 3],
+                rotation_step: AnimatedRotation {
+                    z: AnimatedValue::Static(90.0),
+                    ..AnimatedRotation::default()
+                },
+            }),
+            ..WireframeElement::default()
+        };
+        let ctx = ExpressionContext::new(0, 1);
+        let vertices = WireframePrimitive::from_element(&element).vertices(&ctx);
+
+        assert_eq!(vertices[1].position, [1.0, 0.0, 0.0]);
+        // The second copy is twice as long and turned a quarter about Z
+        let end = vertices[3].position;
+        assert!(end[0].abs() < 1e-5 && (end[1] - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_hidden_line_ignores_faceless_geometry() {
+        let element = WireframeElement {
+            geometry: GeometryType::Custom {
+                vertices: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]],
+                edges: vec![[0, 1]],
+            },
+            hidden_line: true,
+            hidden_line_opacity: 0.0,
+            ..WireframeElement::default()
+        };
+        assert_eq!(visible_alphas(&element, [0.0, 0.0, 5.0]), vec![1.0]);
+    }
+}
@@ -0,0 +1,139 @@
+use super::RenderError;
+
+/// A GPU adapter as reported to users, so they can tell which device renders.
+#[derive(Debug, Clone)]
+pub struct AdapterSummary {
+    pub name: String,
+    pub backend: String,
+    pub device_type: String,
+    pub driver: String,
+}
+
+impl From<wgpu::AdapterInfo> for AdapterSummary {
+    fn from(info: wgpu::AdapterInfo) -> Self {
+        Self {
+            name: info.name,
+            backend: info.backend.to_str().to_string(),
+            device_type: format!("{:?}", info.device_type),
+            driver: format!("{} {}", info.driver, info.driver_info).trim().to_string(),
+        }
+    }
+}
+
+/// Graphics API to restrict adapter selection to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GpuBackend {
+    Vulkan,
+    Metal,
+    Dx12,
+    Gl,
+}
+
+impl GpuBackend {
+    fn backends(self) -> wgpu::Backends {
+        match self {
+            GpuBackend::Vulkan => wgpu::Backends::VULKAN,
+            GpuBackend::Metal => wgpu::Backends::METAL,
+            GpuBackend::Dx12 => wgpu::Backends::DX12,
+            GpuBackend::Gl => wgpu::Backends::GL,
+        }
+    }
+}
+
+/// Whether to prefer an integrated (low) or discrete (high) GPU when both exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum PowerPreference {
+    Low,
+    #[default]
+    High,
+}
+
+/// How a render picks its GPU adapter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AdapterOptions {
+    /// Only consider this API; `None` honors `WGPU_BACKEND`, then tries every API
+    pub backend: Option<GpuBackend>,
+    pub power: PowerPreference,
+}
+
+impl AdapterOptions {
+    pub(super) fn backends(&self) -> wgpu::Backends {
+        match self.backend {
+            Some(backend) => backend.backends(),
+            None => wgpu::Backends::from_env().unwrap_or(wgpu::Backends::all()),
+        }
+    }
+
+    /// The selection in words, for errors when no adapter matches it.
+    fn describe(&self) -> String {
+        let backend = match (self.backend, std::env::var("WGPU_BACKEND")) {
+            (Some(backend), _) => format!("{:?}", backend).to_lowercase(),
+            (None, Ok(env)) => format!("WGPU_BACKEND={}", env),
+            (None, Err(_)) => "any".to_string(),
+        };
+        let power = match self.power {
+            PowerPreference::Low => "low",
+            PowerPreference::High => "high",
+        };
+        format!("backend {}, power {}", backend, power)
+    }
+}
+
+pub(super) fn create_instance(backends: wgpu::Backends) -> wgpu::Instance {
+    wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends,
+        ..Default::default()
+    })
+}
+
+/// The adapter a `Renderer` renders with.
+pub(super) fn request_adapter(
+    instance: &wgpu::Instance,
+    options: &AdapterOptions,
+) -> Result<wgpu::Adapter, RenderError> {
+    let power_preference = match options.power {
+        PowerPreference::Low => wgpu::PowerPreference::LowPower,
+        PowerPreference::High => wgpu::PowerPreference::HighPerformance,
+    };
+    pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference,
+        compatible_surface: None,
+        force_fallback_adapter: false,
+    }))
+    .ok_or_else(|| {
+        RenderError::GpuInitFailed(format!(
+            "No suitable GPU adapter found ({})",
+            options.describe()
+        ))
+    })
+}
+
+/// Describe the adapter a render with these options would use, without creating a device.
+pub fn selected_adapter(options: &AdapterOptions) -> Result<AdapterSummary, RenderError> {
+    let instance = create_instance(options.backends());
+    request_adapter(&instance, options).map(|adapter| adapter.get_info().into())
+}
+
+/// Every adapter wgpu can see on any backend, chosen or not.
+pub fn available_adapters() -> Vec<AdapterSummary> {
+    create_instance(wgpu::Backends::all())
+        .enumerate_adapters(wgpu::Backends::all())
+        .into_iter()
+        .map(|adapter| adapter.get_info().into())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_explicit_selection() {
+        let options = AdapterOptions {
+            backend: Some(GpuBackend::Dx12),
+            power: PowerPreference::Low,
+        };
+        assert_eq!(options.describe(), "backend dx12, power low");
+        assert_eq!(options.backends(), wgpu::Backends::DX12);
+    }
+}
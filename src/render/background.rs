@@ -0,0 +1,234 @@
+use super::RenderError;
+use crate::scene::{parse_color, Background};
+
+const DEFAULT_COLOR: [f32; 4] = [0.04, 0.04, 0.04, 1.0];
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct BackgroundUniforms {
+    from_color: [f32; 4],
+    to_color: [f32; 4],
+    direction: [f32; 2],
+    resolution: [f32; 2],
+    gradient_length: f32,
+    use_image: f32,
+    _padding: [f32; 2],
+}
+
+/// Full-screen draw that paints a gradient or image before the lines.
+/// Plain color backgrounds don't need one; the render pass clear covers them.
+pub struct BackgroundPass {
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+}
+
+impl BackgroundPass {
+    /// Returns `Ok(None)` for plain color backgrounds.
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        background: &Background,
+        resolution: [f32; 2],
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+        depth_format: Option<wgpu::TextureFormat>,
+    ) -> Result<Option<Self>, RenderError> {
+        let mut uniforms = BackgroundUniforms {
+            from_color: DEFAULT_COLOR,
+            to_color: DEFAULT_COLOR,
+            direction: [0.0, 1.0],
+            resolution,
+            gradient_length: resolution[1],
+            use_image: 0.0,
+            _padding: [0.0; 2],
+        };
+
+        // Gradients still bind a texture, so they get a 1x1 placeholder
+        let placeholder;
+        let pixels = match background {
+            Background::Color(_) => return Ok(None),
+            Background::Gradient { gradient } => {
+                let angle = gradient.angle.to_radians();
+                let (sin, cos) = angle.sin_cos();
+                uniforms.from_color = parse_color(&gradient.from).unwrap_or(DEFAULT_COLOR);
+                uniforms.to_color = parse_color(&gradient.to).unwrap_or(DEFAULT_COLOR);
+                // CSS angles: 0 points up, 90 right; uv y grows downward
+                uniforms.direction = [sin, -cos];
+                // Long enough that the gradient spans corner to corner, as in CSS
+                uniforms.gradient_length =
+                    ((resolution[0] * sin).abs() + (resolution[1] * cos).abs()).max(1.0);
+                placeholder = image::RgbaImage::new(1, 1);
+                &placeholder
+            }
+            Background::Image {
+                image: path,
+                pixels,
+            } => {
+                uniforms.use_image = 1.0;
+                pixels.as_deref().ok_or_else(|| {
+                    RenderError::BackgroundImage(format!("{}: not loaded", path))
+                })?
+            }
+        };
+
+        let size = wgpu::Extent3d {
+            width: pixels.width(),
+            height: pixels.height(),
+            depth_or_array_layers: 1,
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("background texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            pixels,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * pixels.width()),
+                rows_per_image: Some(pixels.height()),
+            },
+            size,
+        );
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("background sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("background uniform buffer"),
+            size: std::mem::size_of::<BackgroundUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        queue.write_buffer(&uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("background bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("background bind group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("background shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/background.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("background pipeline layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        // Must match the main pass attachments: same format, samples, and depth buffer
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("background pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: depth_format.map(|format| wgpu::DepthStencilState {
+                format,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Always,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Some(Self {
+            pipeline,
+            bind_group,
+        }))
+    }
+
+    /// Record the background draw; call before any lines in the same pass.
+    pub fn draw(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
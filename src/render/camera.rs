@@ -1,8 +1,13 @@
-use crate::scene::Camera as SceneCamera;
+use crate::scene::{Camera as SceneCamera, ExpressionContext, Turntable, TurntableAxis};
+#[cfg(test)]
+use crate::scene::{AnimatedAxes, AnimatedPoint, AnimatedValue};
+use std::f32::consts::TAU;
 
+#[derive(Debug, Clone, Copy)]
 pub struct Camera {
     pub position: [f32; 3],
     pub target: [f32; 3],
+    pub up: [f32; 3],
     pub fov: f32,
     pub aspect: f32,
     pub near: f32,
@@ -10,30 +15,83 @@ pub struct Camera {
 }
 
 impl Camera {
-    pub fn from_scene(camera: &SceneCamera, width: u32, height: u32) -> Self {
-        Self {
-            position: camera.position,
-            target: camera.target,
+    /// Camera for a given frame, evaluating animated position/target and
+    /// orbiting the target when a turntable is set.
+    pub fn from_scene(
+        camera: &SceneCamera,
+        width: u32,
+        height: u32,
+        ctx: &ExpressionContext,
+    ) -> Self {
+        let base = Self {
+            position: camera.position.evaluate(ctx),
+            target: camera.target.evaluate(ctx),
+            up: [0.0, 1.0, 0.0],
             fov: camera.fov,
             aspect: width as f32 / height as f32,
             near: 0.1,
             far: 1000.0,
+        };
+
+        match camera.turntable {
+            Some(turntable) => base.orbit(turntable, ctx),
+            None => base,
         }
     }
 
+    fn orbit(&self, turntable: Turntable, ctx: &ExpressionContext) -> Self {
+        // frame / total_frames (not t) stops one step short of a full turn so the GIF loops cleanly
+        let progress = if ctx.total_frames > 0 {
+            ctx.frame as f32 / ctx.total_frames as f32
+        } else {
+            0.0
+        };
+        let angle = TAU * turntable.revolutions * progress;
+        let axis = axis_vector(turntable.axis);
+
+        // Rotating `up` too keeps look_at well-defined when orbiting over the poles
+        let offset = rotate_about_axis(subtract(self.position, self.target), axis, angle);
+        Self {
+            position: add(self.target, offset),
+            up: rotate_about_axis(self.up, axis, angle),
+            ..*self
+        }
+    }
+
+    /// Screen right and up directions in world space.
+    pub fn basis(&self) -> ([f32; 3], [f32; 3]) {
+        let f = self.forward();
+        let s = normalize(cross(f, self.up));
+        (s, cross(s, f))
+    }
+
+    /// Unit view direction in world space.
+    pub fn forward(&self) -> [f32; 3] {
+        normalize(subtract(self.target, self.position))
+    }
+
+    /// World-space height covered by one pixel at unit distance in front of the
+    /// camera, for an image `height` pixels tall; scale by depth for farther points.
+    pub fn pixel_size(&self, height: u32) -> f32 {
+        2.0 * (self.fov.to_radians() * 0.5).tan() / height as f32
+    }
+
     pub fn view_matrix(&self) -> [[f32; 4]; 4] {
-        look_at(self.position, self.target, [0.0, 1.0, 0.0])
+        look_at(self.position, self.target, self.up)
     }
 
     pub fn projection_matrix(&self) -> [[f32; 4]; 4] {
         perspective(self.fov.to_radians(), self.aspect, self.near, self.far)
     }
 
+    /// Row-major world-to-clip matrix, for projecting points on the CPU.
+    pub fn clip_matrix(&self) -> [[f32; 4]; 4] {
+        multiply_matrices(self.projection_matrix(), self.view_matrix())
+    }
+
     pub fn view_projection_matrix(&self) -> [[f32; 4]; 4] {
-        let view = self.view_matrix();
-        let proj = self.projection_matrix();
-        // Multiply and transpose for WGSL column-major layout
-        transpose(multiply_matrices(proj, view))
+        // Transpose for WGSL column-major layout
+        transpose(self.clip_matrix())
     }
 }
 
@@ -85,6 +143,30 @@ fn transpose(m: [[f32; 4]; 4]) -> [[f32; 4]; 4] {
     ]
 }
 
+fn axis_vector(axis: TurntableAxis) -> [f32; 3] {
+    match axis {
+        TurntableAxis::X => [1.0, 0.0, 0.0],
+        TurntableAxis::Y => [0.0, 1.0, 0.0],
+        TurntableAxis::Z => [0.0, 0.0, 1.0],
+    }
+}
+
+// Rodrigues' rotation formula; `axis` must be unit length
+fn rotate_about_axis(v: [f32; 3], axis: [f32; 3], angle: f32) -> [f32; 3] {
+    let (sin_a, cos_a) = angle.sin_cos();
+    let k_cross_v = cross(axis, v);
+    let k_dot_v = dot(axis, v) * (1.0 - cos_a);
+    [
+        v[0] * cos_a + k_cross_v[0] * sin_a + axis[0] * k_dot_v,
+        v[1] * cos_a + k_cross_v[1] * sin_a + axis[1] * k_dot_v,
+        v[2] * cos_a + k_cross_v[2] * sin_a + axis[2] * k_dot_v,
+    ]
+}
+
+fn add(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
 fn subtract(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
     [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
 }
@@ -109,3 +191,87 @@ fn normalize(v: [f32; 3]) -> [f32; 3] {
         [0.0, 0.0, 0.0]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: [f32; 3], b: [f32; 3]) {
+        for i in 0..3 {
+            assert!((a[i] - b[i]).abs() < 1e-4, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_turntable_half_revolution() {
+        let scene_camera = SceneCamera {
+            turntable: Some(Turntable::default()),
+            ..SceneCamera::default()
+        };
+        let at = |frame| {
+            Camera::from_scene(&scene_camera, 800, 600, &ExpressionContext::new(frame, 30))
+        };
+
+        assert_close(at(0).position, [5.0, 5.0, 5.0]);
+        assert_close(at(15).position, [-5.0, 5.0, -5.0]);
+    }
+
+    #[test]
+    fn test_no_turntable_is_static() {
+        let scene_camera = SceneCamera::default();
+        let first = Camera::from_scene(&scene_camera, 800, 600, &ExpressionContext::new(0, 30));
+        let moved = Camera::from_scene(&scene_camera, 800, 600, &ExpressionContext::new(15, 30));
+        assert_close(moved.position, first.position);
+        assert_close(moved.up, [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_basis_matches_front_view() {
+        let scene_camera = SceneCamera {
+            position: AnimatedPoint::Static([0.0, 0.0, 5.0]),
+            target: AnimatedPoint::Static([0.0, 0.0, 0.0]),
+            ..SceneCamera::default()
+        };
+        let camera = Camera::from_scene(&scene_camera, 800, 600, &ExpressionContext::new(0, 30));
+        let (right, up) = camera.basis();
+        assert_close(right, [1.0, 0.0, 0.0]);
+        assert_close(up, [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_pixel_size_spans_view_height() {
+        let camera = Camera::from_scene(
+            &SceneCamera {
+                fov: 90.0,
+                ..SceneCamera::default()
+            },
+            800,
+            600,
+            &ExpressionContext::new(0, 30),
+        );
+
+        // A 90 degree view is two units tall at unit distance
+        assert!((camera.pixel_size(600) * 600.0 - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_animated_position_and_target() {
+        let scene_camera = SceneCamera {
+            position: AnimatedPoint::PerAxis(Box::new(AnimatedAxes {
+                x: AnimatedValue::Expression("sin(t * TAU) * 10".into()),
+                y: AnimatedValue::Static(5.0),
+                z: AnimatedValue::Expression("cos(t * TAU) * 10".into()),
+            })),
+            target: AnimatedPoint::PerAxis(Box::new(AnimatedAxes {
+                y: AnimatedValue::Expression("t * 4".into()),
+                ..AnimatedAxes::default()
+            })),
+            ..SceneCamera::default()
+        };
+
+        // t = 0.25 at frame 1 of 5 (t = frame / (total_frames - 1))
+        let camera = Camera::from_scene(&scene_camera, 800, 600, &ExpressionContext::new(1, 5));
+        assert_close(camera.position, [10.0, 5.0, 0.0]);
+        assert_close(camera.target, [0.0, 1.0, 0.0]);
+    }
+}
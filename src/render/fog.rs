@@ -0,0 +1,111 @@
+use crate::primitives::LineVertex;
+use crate::scene::{parse_color, ExpressionContext, Fog};
+
+/// Scene fog with its color parsed, applied to finished vertices so every primitive
+/// fades the same way on either backend.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct FogPass {
+    color: [f32; 3],
+    near: f32,
+    far: f32,
+}
+
+impl FogPass {
+    pub(super) fn new(fog: &Fog) -> Self {
+        let [r, g, b, _] = parse_color(&fog.color).unwrap_or([0.04, 0.04, 0.04, 1.0]);
+        Self {
+            color: [r, g, b],
+            near: fog.near,
+            far: fog.far,
+        }
+    }
+
+    /// Blend each vertex's color toward the fog by its depth along the view direction.
+    /// Alpha is kept, so fogged lines still composite over the background. Vertices from
+    /// `additive_start` on are added to the frame rather than covering it, so they fade
+    /// toward black instead, which adds nothing.
+    pub(super) fn apply(
+        &self,
+        vertices: Vec<LineVertex>,
+        additive_start: usize,
+        ctx: &ExpressionContext,
+    ) -> Vec<LineVertex> {
+        vertices
+            .into_iter()
+            .enumerate()
+            .map(|(i, vertex)| {
+                let amount = self.amount(depth(vertex.position, ctx));
+                let mix = |c: f32, fog: f32| c + (fog - c) * amount;
+                let [r, g, b, a] = vertex.color;
+                let [fr, fg, fb] = if i < additive_start {
+                    self.color
+                } else {
+                    [0.0; 3]
+                };
+                LineVertex {
+                    color: [mix(r, fr), mix(g, fg), mix(b, fb), a],
+                    ..vertex
+                }
+            })
+            .collect()
+    }
+
+    /// 0 at or before `near`, 1 at or past `far`.
+    fn amount(&self, depth: f32) -> f32 {
+        ((depth - self.near) / (self.far - self.near)).clamp(0.0, 1.0)
+    }
+}
+
+fn depth(position: [f32; 3], ctx: &ExpressionContext) -> f32 {
+    (0..3)
+        .map(|i| (position[i] - ctx.camera_position[i]) * ctx.camera_forward[i])
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fog() -> FogPass {
+        FogPass::new(&Fog {
+            color: "#000000".to_string(),
+            near: 5.0,
+            far: 15.0,
+        })
+    }
+
+    #[test]
+    fn test_fog_blends_by_view_depth() {
+        // Camera at the origin looking down -z
+        let ctx = ExpressionContext {
+            camera_forward: [0.0, 0.0, -1.0],
+            ..ExpressionContext::new(0, 1)
+        };
+        let white = [1.0, 1.0, 1.0, 0.8];
+        let vertices = [-2.0, -10.0, -20.0, 3.0]
+            .map(|z| LineVertex::new([1.0, 1.0, z], white))
+            .to_vec();
+
+        let fogged = fog().apply(vertices, 4, &ctx);
+        let reds: Vec<f32> = fogged.iter().map(|v| v.color[0]).collect();
+        assert_eq!(reds, vec![1.0, 0.5, 0.0, 1.0]);
+        assert!(fogged.iter().all(|v| v.color[3] == 0.8));
+    }
+
+    #[test]
+    fn test_fog_fades_additive_vertices_to_black() {
+        let ctx = ExpressionContext {
+            camera_forward: [0.0, 0.0, -1.0],
+            ..ExpressionContext::new(0, 1)
+        };
+        let mut fog = fog();
+        fog.color = [1.0, 1.0, 1.0];
+        let red = [1.0, 0.0, 0.0, 1.0];
+        let vertices = [-10.0, -10.0].map(|z| LineVertex::new([0.0, 0.0, z], red)).to_vec();
+
+        // Halfway into the fog: the alpha vertex mixes toward white, the additive one dims
+        let fogged = fog.apply(vertices, 1, &ctx);
+        assert_eq!(fogged[0].color, [1.0, 0.5, 0.5, 1.0]);
+        assert_eq!(fogged[1].color, [0.5, 0.0, 0.0, 1.0]);
+    }
+}
@@ -1,5 +1,13 @@
+mod adapter;
+mod background;
 mod camera;
+mod fog;
 mod pipeline;
 mod post;
+mod software;
 
-pub use pipeline::{Renderer, RenderError};
+pub use adapter::{
+    available_adapters, selected_adapter, AdapterOptions, AdapterSummary, GpuBackend,
+    PowerPreference,
+};
+pub use pipeline::{RenderBackend, RenderError, RenderProgress, Renderer};
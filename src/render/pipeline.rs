@@ -1,11 +1,20 @@
+use super::adapter::{create_instance, request_adapter, AdapterOptions};
+use super::background::BackgroundPass;
 use super::camera::Camera;
+use super::fog::FogPass;
 use super::post::PostProcessor;
+use super::software::SoftwareRenderer;
 use crate::primitives::{
     AxesPrimitive, GlyphPrimitive, GridPrimitive, LinePrimitive, LineVertex, ParticlesPrimitive,
     Primitive, WireframePrimitive,
 };
-use crate::scene::{parse_hex_color, Element, ExpressionContext, Scene};
+use crate::scene::{
+    BlendMode, Camera as SceneCamera, Element, ExpressionContext, Playback, Scene, Viewport,
+};
+use rayon::prelude::*;
+use std::cell::RefCell;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -22,8 +31,46 @@ pub enum RenderError {
 
     #[error("Frame capture failed: {0}")]
     CaptureFailed(String),
+
+    #[error("Render exceeded time budget of {}s", .0.as_secs_f32())]
+    Timeout(Duration),
+
+    #[error("Frame range {start}..{end} is outside the scene's {total} frames")]
+    FrameRange { start: u32, end: u32, total: u32 },
+
+    #[error("Failed to load background image: {0}")]
+    BackgroundImage(String),
 }
 
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// Source-over; alpha accumulates as coverage so transparent canvases stay premultiplied.
+const ALPHA_BLEND: wgpu::BlendState = wgpu::BlendState {
+    color: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::SrcAlpha,
+        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+        operation: wgpu::BlendOperation::Add,
+    },
+    alpha: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+        operation: wgpu::BlendOperation::Add,
+    },
+};
+
+/// Adds light without darkening what is beneath; alpha blends as in `ALPHA_BLEND`.
+const ADDITIVE_BLEND: wgpu::BlendState = wgpu::BlendState {
+    color: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::SrcAlpha,
+        dst_factor: wgpu::BlendFactor::One,
+        operation: wgpu::BlendOperation::Add,
+    },
+    alpha: ALPHA_BLEND.alpha,
+};
+
+/// Frames whose vertices `render_range` builds in parallel before drawing them.
+const PRECOMPUTE_CHUNK: u32 = 32;
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
 struct Uniforms {
@@ -32,38 +79,263 @@ struct Uniforms {
     _padding: [f32; 2],
 }
 
+/// Where a [`Renderer`] rasterizes its lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum RenderBackend {
+    /// The GPU when an adapter is available, otherwise the CPU
+    #[default]
+    Auto,
+    /// wgpu only; fails when no adapter is available
+    Gpu,
+    /// Software rasterizer; no post effects, depth test, MSAA, or image backgrounds
+    Cpu,
+}
+
 pub struct Renderer {
+    target: Target,
+    width: u32,
+    height: u32,
+    camera: SceneCamera,
+    elements: Vec<Element>,
+    fog: Option<FogPass>,
+    sort_transparency: bool,
+    depth_bias: f32,
+    total_frames: u32,
+    playback: Playback,
+    verbose: bool,
+    timeout: Option<Duration>,
+}
+
+enum Target {
+    Gpu(Box<GpuTarget>),
+    Cpu(SoftwareRenderer),
+}
+
+/// Device, pipeline, and render targets for drawing frames with wgpu.
+struct GpuTarget {
     device: Arc<wgpu::Device>,
     queue: Arc<wgpu::Queue>,
     pipeline: wgpu::RenderPipeline,
+    additive_pipeline: wgpu::RenderPipeline,
     uniform_buffer: wgpu::Buffer,
     uniform_bind_group: wgpu::BindGroup,
     #[allow(dead_code)]
     texture: wgpu::Texture,
     texture_view: wgpu::TextureView,
+    /// Multisampled color target, resolved into `texture` when MSAA is on
+    msaa_view: Option<wgpu::TextureView>,
+    depth_view: Option<wgpu::TextureView>,
+    /// Reused across frames; only reallocated when a frame needs more room
+    vertex_buffer: RefCell<Option<wgpu::Buffer>>,
     output_buffer: wgpu::Buffer,
     width: u32,
     height: u32,
+    /// Region of the rendered canvas copied into output images
+    viewport: Viewport,
     background_color: [f32; 4],
-    camera: Camera,
-    elements: Vec<Element>,
-    total_frames: u32,
+    background: Option<BackgroundPass>,
+    transparent: bool,
     post_processor: PostProcessor,
 }
 
+/// One frame's line list, with alpha-blended elements first and additive ones after,
+/// so each blend mode is a single draw.
+pub(super) struct FrameVertices {
+    pub(super) vertices: Vec<LineVertex>,
+    /// Index of the first vertex drawn with additive blending
+    pub(super) additive_start: usize,
+}
+
+/// Progress reported before each frame renders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderProgress {
+    /// Absolute index of the frame about to render
+    pub frame: u32,
+    /// Total frames in the scene timeline
+    pub total: u32,
+}
+
 impl Renderer {
+    /// Render on the GPU, falling back to the CPU when no adapter can be used.
     pub fn new(scene: &Scene) -> Result<Self, RenderError> {
-        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::all(),
-            ..Default::default()
-        });
+        Self::with_backend(scene, RenderBackend::Auto, &AdapterOptions::default())
+    }
+
+    /// Render on an explicit backend, picking the GPU adapter by `adapter`. `Auto` only
+    /// falls back to the CPU when the GPU fails to initialize; other errors, like a
+    /// missing background image, still fail.
+    pub fn with_backend(
+        scene: &Scene,
+        backend: RenderBackend,
+        adapter: &AdapterOptions,
+    ) -> Result<Self, RenderError> {
+        let target = match backend {
+            RenderBackend::Gpu => Target::Gpu(Box::new(GpuTarget::new(scene, adapter)?)),
+            RenderBackend::Cpu => Target::Cpu(SoftwareRenderer::new(scene)),
+            RenderBackend::Auto => match GpuTarget::new(scene, adapter) {
+                Ok(gpu) => Target::Gpu(Box::new(gpu)),
+                Err(RenderError::GpuInitFailed(_)) => Target::Cpu(SoftwareRenderer::new(scene)),
+                Err(e) => return Err(e),
+            },
+        };
+
+        Ok(Self {
+            target,
+            width: scene.canvas.width,
+            height: scene.canvas.height,
+            camera: scene.camera.clone(),
+            elements: scene.elements.clone(),
+            fog: scene.fog.as_ref().map(FogPass::new),
+            sort_transparency: scene.sort_transparency,
+            depth_bias: scene.depth_bias,
+            total_frames: scene.total_frames(),
+            playback: scene.playback,
+            verbose: false,
+            timeout: None,
+        })
+    }
 
-        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::HighPerformance,
-            compatible_surface: None,
-            force_fallback_adapter: false,
-        }))
-        .ok_or_else(|| RenderError::GpuInitFailed("No suitable GPU adapter found".to_string()))?;
+    /// The backend frames are drawn with; never `Auto` once constructed.
+    pub fn backend(&self) -> RenderBackend {
+        match self.target {
+            Target::Gpu(_) => RenderBackend::Gpu,
+            Target::Cpu(_) => RenderBackend::Cpu,
+        }
+    }
+
+    /// Print per-frame diagnostics (e.g. frames with no vertices) to stderr.
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+    }
+
+    /// Abort multi-frame renders with `RenderError::Timeout` once this budget is spent.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Render the whole timeline in playback order; ping-pong scenes get their
+    /// interior frames appended in reverse.
+    pub fn render_all<F: FnMut(RenderProgress)>(
+        &self,
+        on_progress: F,
+    ) -> Result<Vec<image::RgbaImage>, RenderError> {
+        let mut frames = self.render_range(0, self.total_frames, on_progress)?;
+        if self.playback == Playback::Pingpong {
+            ping_pong(&mut frames);
+        }
+        Ok(frames)
+    }
+
+    /// Render frames `start..end`, calling `on_progress` before each one.
+    /// Expressions still see the scene's full `total_frames`, so `t` matches a
+    /// full render.
+    pub fn render_range<F: FnMut(RenderProgress)>(
+        &self,
+        start: u32,
+        end: u32,
+        mut on_progress: F,
+    ) -> Result<Vec<image::RgbaImage>, RenderError> {
+        if start >= end || end > self.total_frames {
+            return Err(RenderError::FrameRange {
+                start,
+                end,
+                total: self.total_frames,
+            });
+        }
+
+        let mut frames = Vec::with_capacity((end - start) as usize);
+        let started = Instant::now();
+
+        // Vertex generation is pure CPU work, so build a chunk of frames' geometry in
+        // parallel before drawing it; the GPU submit and readback stay sequential
+        // because they share one output buffer. Chunking bounds the vertices held at
+        // once and lets the timeout stop a long render between chunks.
+        let total_frames = self.total_frames;
+        let (camera, width, height) = (&self.camera, self.width, self.height);
+        let (elements, fog) = (&self.elements, self.fog.as_ref());
+        let (sort, depth_bias) = (self.sort_transparency, self.depth_bias);
+        for chunk_start in (start..end).step_by(PRECOMPUTE_CHUNK as usize) {
+            let chunk = chunk_start..end.min(chunk_start.saturating_add(PRECOMPUTE_CHUNK));
+            let frame_vertices: Vec<FrameVertices> = chunk
+                .clone()
+                .into_par_iter()
+                .map(|frame| {
+                    let ctx = ExpressionContext::new(frame, total_frames);
+                    let ctx = with_camera(camera, width, height, ctx);
+                    build_vertices(elements, fog, sort, depth_bias, &ctx)
+                })
+                .collect();
+
+            for (frame, vertices) in chunk.zip(frame_vertices) {
+                if let Some(limit) = self.timeout
+                    && started.elapsed() > limit
+                {
+                    return Err(RenderError::Timeout(limit));
+                }
+
+                on_progress(RenderProgress {
+                    frame,
+                    total: self.total_frames,
+                });
+
+                let ctx = ExpressionContext::new(frame, self.total_frames);
+                let image = self.draw_frame(&ctx, &vertices)?;
+                frames.push(image);
+            }
+        }
+
+        Ok(frames)
+    }
+
+    /// Render the frame nearest `t`, a 0-1 fraction of the scene timeline.
+    pub fn render_frame_at(&self, t: f32) -> Result<image::RgbaImage, RenderError> {
+        let frame = nearest_frame(t, self.total_frames);
+        self.render_frame(&ExpressionContext::new(frame, self.total_frames))
+    }
+
+    /// Render a single frame at an explicit expression context.
+    pub fn render_frame(&self, ctx: &ExpressionContext) -> Result<image::RgbaImage, RenderError> {
+        let ctx = with_camera(&self.camera, self.width, self.height, *ctx);
+        let frame = self.build_vertices(&ctx);
+        self.draw_frame(&ctx, &frame)
+    }
+
+    fn build_vertices(&self, ctx: &ExpressionContext) -> FrameVertices {
+        build_vertices(
+            &self.elements,
+            self.fog.as_ref(),
+            self.sort_transparency,
+            self.depth_bias,
+            ctx,
+        )
+    }
+
+    /// Draw precomputed vertices for one frame on whichever backend this renderer uses.
+    fn draw_frame(
+        &self,
+        ctx: &ExpressionContext,
+        frame: &FrameVertices,
+    ) -> Result<image::RgbaImage, RenderError> {
+        // An empty frame is valid but usually means the scene is accidentally invisible
+        if self.verbose && frame.vertices.is_empty() {
+            eprintln!(
+                "warning: frame {} produced no vertices; nothing will be drawn",
+                ctx.frame
+            );
+        }
+
+        let camera = Camera::from_scene(&self.camera, self.width, self.height, ctx);
+        match &self.target {
+            Target::Gpu(gpu) => gpu.draw(&camera, ctx, frame),
+            Target::Cpu(cpu) => Ok(cpu.draw(&camera, frame)),
+        }
+    }
+}
+
+impl GpuTarget {
+    fn new(scene: &Scene, options: &AdapterOptions) -> Result<Self, RenderError> {
+        let instance = create_instance(options.backends());
+        let adapter = request_adapter(&instance, options)?;
 
         let (device, queue) = pollster::block_on(adapter.request_device(
             &wgpu::DeviceDescriptor {
@@ -82,6 +354,13 @@ impl Renderer {
         let width = scene.canvas.width;
         let height = scene.canvas.height;
 
+        // HDR scenes render into a float target; the post pass tonemaps back to 8-bit
+        let format = if scene.canvas.hdr {
+            wgpu::TextureFormat::Rgba16Float
+        } else {
+            wgpu::TextureFormat::Rgba8Unorm
+        };
+
         // Create texture for rendering
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("render texture"),
@@ -93,7 +372,7 @@ impl Renderer {
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8Unorm,
+            format,
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT
                 | wgpu::TextureUsages::COPY_SRC
                 | wgpu::TextureUsages::TEXTURE_BINDING,
@@ -101,11 +380,62 @@ impl Renderer {
         });
         let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
 
+        // Lines draw into a multisampled target that resolves into the texture above,
+        // so post-processing still reads a single-sample image
+        let sample_count = scene.msaa.max(1);
+        let msaa_view = (sample_count > 1).then(|| {
+            device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some("msaa texture"),
+                    size: wgpu::Extent3d {
+                        width,
+                        height,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[],
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default())
+        });
+
+        // Depth buffer is only allocated when the scene opts into occlusion
+        let depth_view = scene.depth_test.then(|| {
+            device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some("depth texture"),
+                    size: wgpu::Extent3d {
+                        width,
+                        height,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: DEPTH_FORMAT,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[],
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default())
+        });
+
+        // Only the viewport is read back; the rest of the canvas still renders so
+        // the camera framing matches a full-size render
+        let viewport = scene.canvas.viewport.unwrap_or(Viewport {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        });
+
         // Create output buffer for reading pixels
-        let bytes_per_row = (width * 4 + 255) & !255; // Align to 256 bytes
+        let bytes_per_row = (viewport.width * 4 + 255) & !255; // Align to 256 bytes
         let output_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("output buffer"),
-            size: (bytes_per_row * height) as u64,
+            size: (bytes_per_row * viewport.height) as u64,
             usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
             mapped_at_creation: false,
         });
@@ -155,143 +485,106 @@ impl Renderer {
             push_constant_ranges: &[],
         });
 
-        // Create render pipeline
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("line render pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                buffers: &[wgpu::VertexBufferLayout {
-                    array_stride: std::mem::size_of::<LineVertex>() as u64,
-                    step_mode: wgpu::VertexStepMode::Vertex,
-                    attributes: &[
-                        wgpu::VertexAttribute {
-                            format: wgpu::VertexFormat::Float32x3,
-                            offset: 0,
-                            shader_location: 0,
-                        },
-                        wgpu::VertexAttribute {
-                            format: wgpu::VertexFormat::Float32x4,
-                            offset: 12,
-                            shader_location: 1,
-                        },
-                    ],
-                }],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Rgba8Unorm,
-                    blend: Some(wgpu::BlendState {
-                        color: wgpu::BlendComponent {
-                            src_factor: wgpu::BlendFactor::SrcAlpha,
-                            dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                            operation: wgpu::BlendOperation::Add,
-                        },
-                        alpha: wgpu::BlendComponent {
-                            src_factor: wgpu::BlendFactor::One,
-                            dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                            operation: wgpu::BlendOperation::Add,
-                        },
-                    }),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::LineList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                unclipped_depth: false,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        });
+        // Create render pipelines; additive elements draw with their own blend state
+        let pipeline = line_pipeline(
+            &device,
+            "line render pipeline",
+            &pipeline_layout,
+            &shader,
+            format,
+            sample_count,
+            scene.depth_test,
+            ALPHA_BLEND,
+        );
+        let additive_pipeline = line_pipeline(
+            &device,
+            "additive line render pipeline",
+            &pipeline_layout,
+            &shader,
+            format,
+            sample_count,
+            scene.depth_test,
+            ADDITIVE_BLEND,
+        );
 
-        let camera = Camera::from_scene(&scene.camera, width, height);
-        let background_color =
-            parse_hex_color(&scene.canvas.background).unwrap_or([0.04, 0.04, 0.04, 1.0]);
+        let background_color = clear_color(scene);
 
-        let post_processor =
-            PostProcessor::new(Arc::clone(&device), Arc::clone(&queue), width, height, &scene.post);
+        // Gradient and image backgrounds are painted by an extra full-screen draw
+        let background = if scene.canvas.transparent {
+            None
+        } else {
+            BackgroundPass::new(
+                &device,
+                &queue,
+                &scene.canvas.background,
+                [width as f32, height as f32],
+                format,
+                sample_count,
+                scene.depth_test.then_some(DEPTH_FORMAT),
+            )?
+        };
+
+        let post_processor = PostProcessor::new(
+            Arc::clone(&device),
+            Arc::clone(&queue),
+            width,
+            height,
+            &scene.post,
+            scene.canvas.hdr,
+            scene.canvas.transparent,
+        );
 
         Ok(Self {
             device,
             queue,
             pipeline,
+            additive_pipeline,
             uniform_buffer,
             uniform_bind_group,
             texture,
             texture_view,
+            msaa_view,
+            depth_view,
+            vertex_buffer: RefCell::new(None),
             output_buffer,
             width,
             height,
+            viewport,
             background_color,
-            camera,
-            elements: scene.elements.clone(),
-            total_frames: scene.total_frames(),
+            background,
+            transparent: scene.canvas.transparent,
             post_processor,
         })
     }
 
-    pub fn render_all(&self, json_output: bool) -> Result<Vec<image::RgbaImage>, RenderError> {
-        let mut frames = Vec::with_capacity(self.total_frames as usize);
-
-        for frame in 0..self.total_frames {
-            let ctx = ExpressionContext::new(frame, self.total_frames);
-
-            if json_output {
-                println!(
-                    "{}",
-                    serde_json::json!({
-                        "status": "rendering",
-                        "frame": frame + 1,
-                        "total": self.total_frames
-                    })
-                );
+    /// Submit precomputed vertices for one frame, post-process, and read the image back.
+    fn draw(
+        &self,
+        camera: &Camera,
+        ctx: &ExpressionContext,
+        frame: &FrameVertices,
+    ) -> Result<image::RgbaImage, RenderError> {
+        // Upload vertices, growing the shared buffer only when this frame doesn't fit
+        let vertex_bytes: &[u8] = bytemuck::cast_slice(&frame.vertices);
+        let vertex_len = vertex_bytes.len() as u64;
+        let mut vertex_buffer = self.vertex_buffer.borrow_mut();
+        if vertex_len > 0 {
+            if vertex_buffer.as_ref().is_none_or(|buffer| buffer.size() < vertex_len) {
+                *vertex_buffer = Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("vertex buffer"),
+                    size: vertex_len.next_power_of_two(),
+                    usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                }));
+            }
+            if let Some(buffer) = vertex_buffer.as_ref() {
+                self.queue.write_buffer(buffer, 0, vertex_bytes);
             }
-
-            let image = self.render_frame(&ctx)?;
-            frames.push(image);
-        }
-
-        Ok(frames)
-    }
-
-    fn render_frame(&self, ctx: &ExpressionContext) -> Result<image::RgbaImage, RenderError> {
-        // Collect vertices from all elements
-        let mut all_vertices: Vec<LineVertex> = Vec::new();
-
-        for element in &self.elements {
-            let vertices = match element {
-                Element::Grid(g) => GridPrimitive::from_element(g).vertices(ctx),
-                Element::Wireframe(w) => WireframePrimitive::from_element(w).vertices(ctx),
-                Element::Glyph(g) => GlyphPrimitive::from_element(g).vertices(ctx),
-                Element::Line(l) => LinePrimitive::from_element(l).vertices(ctx),
-                Element::Particles(p) => ParticlesPrimitive::from_element(p).vertices(ctx),
-                Element::Axes(a) => AxesPrimitive::from_element(a).vertices(ctx),
-            };
-            all_vertices.extend(vertices);
         }
 
-        // Create vertex buffer
-        let vertex_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("vertex buffer"),
-            contents: bytemuck::cast_slice(&all_vertices),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-
         // Update uniforms
         let uniforms = Uniforms {
-            view_proj: self.camera.view_projection_matrix(),
+            view_proj: camera.view_projection_matrix(),
             resolution: [self.width as f32, self.height as f32],
             _padding: [0.0, 0.0],
         };
@@ -310,8 +603,8 @@ impl Renderer {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("main render pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.texture_view,
-                    resolve_target: None,
+                    view: self.msaa_view.as_ref().unwrap_or(&self.texture_view),
+                    resolve_target: self.msaa_view.as_ref().map(|_| &self.texture_view),
                     ops: wgpu::Operations {
                         load: wgpu::LoadOp::Clear(wgpu::Color {
                             r: self.background_color[0] as f64,
@@ -322,15 +615,39 @@ impl Renderer {
                         store: wgpu::StoreOp::Store,
                     },
                 })],
-                depth_stencil_attachment: None,
+                depth_stencil_attachment: self.depth_view.as_ref().map(|view| {
+                    wgpu::RenderPassDepthStencilAttachment {
+                        view,
+                        depth_ops: Some(wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(1.0),
+                            store: wgpu::StoreOp::Discard,
+                        }),
+                        stencil_ops: None,
+                    }
+                }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
             });
 
-            render_pass.set_pipeline(&self.pipeline);
+            if let Some(background) = &self.background {
+                background.draw(&mut render_pass);
+            }
+
             render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
-            render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
-            render_pass.draw(0..all_vertices.len() as u32, 0..1);
+            if let Some(buffer) = vertex_buffer.as_ref().filter(|_| vertex_len > 0) {
+                // Draw only this frame's vertices, not the whole buffer capacity
+                render_pass.set_vertex_buffer(0, buffer.slice(..vertex_len));
+                let additive_start = frame.additive_start as u32;
+                let end = frame.vertices.len() as u32;
+                if additive_start > 0 {
+                    render_pass.set_pipeline(&self.pipeline);
+                    render_pass.draw(0..additive_start, 0..1);
+                }
+                if end > additive_start {
+                    render_pass.set_pipeline(&self.additive_pipeline);
+                    render_pass.draw(additive_start..end, 0..1);
+                }
+            }
         }
 
         self.queue.submit(Some(encoder.finish()));
@@ -338,8 +655,14 @@ impl Renderer {
         // Apply post-processing
         let final_texture = self.post_processor.process(&self.texture_view, &self.texture, ctx);
 
-        // Copy texture to buffer
-        let bytes_per_row = (self.width * 4 + 255) & !255;
+        // Copy the viewport to the buffer
+        let Viewport {
+            x,
+            y,
+            width,
+            height,
+        } = self.viewport;
+        let bytes_per_row = (width * 4 + 255) & !255;
         let mut encoder = self
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
@@ -350,7 +673,7 @@ impl Renderer {
             wgpu::TexelCopyTextureInfo {
                 texture: final_texture,
                 mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
+                origin: wgpu::Origin3d { x, y, z: 0 },
                 aspect: wgpu::TextureAspect::All,
             },
             wgpu::TexelCopyBufferInfo {
@@ -358,12 +681,12 @@ impl Renderer {
                 layout: wgpu::TexelCopyBufferLayout {
                     offset: 0,
                     bytes_per_row: Some(bytes_per_row),
-                    rows_per_image: Some(self.height),
+                    rows_per_image: Some(height),
                 },
             },
             wgpu::Extent3d {
-                width: self.width,
-                height: self.height,
+                width,
+                height,
                 depth_or_array_layers: 1,
             },
         );
@@ -385,43 +708,319 @@ impl Renderer {
         let data = buffer_slice.get_mapped_range();
 
         // Convert to image, handling row padding
-        let mut pixels = Vec::with_capacity((self.width * self.height * 4) as usize);
-        for y in 0..self.height {
-            let start = (y * bytes_per_row) as usize;
-            let end = start + (self.width * 4) as usize;
+        let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+        for row in 0..height {
+            let start = (row * bytes_per_row) as usize;
+            let end = start + (width * 4) as usize;
             pixels.extend_from_slice(&data[start..end]);
         }
 
         drop(data);
         self.output_buffer.unmap();
 
-        image::RgbaImage::from_raw(self.width, self.height, pixels)
+        if self.transparent {
+            unpremultiply(&mut pixels);
+        }
+
+        image::RgbaImage::from_raw(width, height, pixels)
             .ok_or_else(|| RenderError::CaptureFailed("Failed to create image".to_string()))
     }
 }
 
-// Helper trait for buffer initialization
-trait DeviceExt {
-    fn create_buffer_init(&self, desc: &wgpu::util::BufferInitDescriptor) -> wgpu::Buffer;
+/// Color the canvas is cleared to before any lines are drawn.
+pub(super) fn clear_color(scene: &Scene) -> [f32; 4] {
+    // Transparent canvases clear to zero alpha so only drawn lines are opaque
+    if scene.canvas.transparent {
+        [0.0; 4]
+    } else {
+        scene.canvas.background.base_color().unwrap_or([0.04, 0.04, 0.04, 1.0])
+    }
+}
+
+/// Blending over a zero-alpha clear leaves premultiplied color; PNG wants straight alpha.
+pub(super) fn unpremultiply(pixels: &mut [u8]) {
+    for pixel in pixels.chunks_exact_mut(4) {
+        let alpha = pixel[3] as u32;
+        if alpha > 0 && alpha < 255 {
+            for channel in &mut pixel[..3] {
+                *channel = (*channel as u32 * 255 / alpha).min(255) as u8;
+            }
+        }
+    }
+}
+
+/// Line-list pipeline drawing with `blend`; the alpha and additive pipelines differ
+/// only in their blend state.
+#[allow(clippy::too_many_arguments)]
+fn line_pipeline(
+    device: &wgpu::Device,
+    label: &str,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    depth_test: bool,
+    blend: wgpu::BlendState,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_main"),
+            buffers: &[wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<LineVertex>() as u64,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &[
+                    wgpu::VertexAttribute {
+                        format: wgpu::VertexFormat::Float32x3,
+                        offset: 0,
+                        shader_location: 0,
+                    },
+                    wgpu::VertexAttribute {
+                        format: wgpu::VertexFormat::Float32x4,
+                        offset: 12,
+                        shader_location: 1,
+                    },
+                    wgpu::VertexAttribute {
+                        format: wgpu::VertexFormat::Float32,
+                        offset: 28,
+                        shader_location: 2,
+                    },
+                ],
+            }],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::LineList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            conservative: false,
+        },
+        // Line topologies reject pipeline depth bias, so the bias is applied in the shader
+        depth_stencil: depth_test.then(|| wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::LessEqual,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
+            ..Default::default()
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+/// World-space point an element is depth-sorted by: its position, the middle of a line's
+/// points, or the origin for grids and particle fields, which are centered there.
+fn anchor(element: &Element, ctx: &ExpressionContext) -> [f32; 3] {
+    match element {
+        Element::Wireframe(w) => w.position.evaluate(ctx),
+        Element::Glyph(g) => g.position.evaluate(ctx),
+        Element::Axes(a) => a.position.evaluate(ctx),
+        Element::Line(l) if !l.points.is_empty() => {
+            let sum = l
+                .points
+                .iter()
+                .fold([0.0; 3], |acc, p| [acc[0] + p[0], acc[1] + p[1], acc[2] + p[2]]);
+            sum.map(|c| c / l.points.len() as f32)
+        }
+        Element::Line(_) | Element::Grid(_) | Element::Particles(_) => [0.0; 3],
+    }
+}
+
+/// Distance in front of the camera along its view direction.
+fn view_depth(point: [f32; 3], ctx: &ExpressionContext) -> f32 {
+    (0..3)
+        .map(|i| (point[i] - ctx.camera_position[i]) * ctx.camera_forward[i])
+        .sum()
+}
+
+/// Append the frames between the last and first in reverse, so looping playback runs
+/// forward then backward without showing either end frame twice in a row.
+fn ping_pong<T: Clone>(frames: &mut Vec<T>) {
+    let interior = frames.len().saturating_sub(1);
+    let reversed: Vec<T> = frames[1.min(interior)..interior].iter().rev().cloned().collect();
+    frames.extend(reversed);
+}
+
+/// Index of the frame whose `t` is closest to the given timeline fraction.
+fn nearest_frame(t: f32, total_frames: u32) -> u32 {
+    let last = total_frames.saturating_sub(1);
+    (t.clamp(0.0, 1.0) * last as f32).round() as u32
+}
+
+/// Fill in the frame's camera basis, position, and pixel scale so billboarded primitives
+/// can face the viewer, wireframes can find their hidden edges, and particles and line
+/// glow can be sized in pixels.
+fn with_camera(
+    camera: &SceneCamera,
+    width: u32,
+    height: u32,
+    ctx: ExpressionContext,
+) -> ExpressionContext {
+    let camera = Camera::from_scene(camera, width, height, &ctx);
+    let (camera_right, camera_up) = camera.basis();
+    ExpressionContext {
+        camera_right,
+        camera_up,
+        camera_position: camera.position,
+        camera_forward: camera.forward(),
+        pixel_size: camera.pixel_size(height),
+        ..ctx
+    }
 }
 
-impl DeviceExt for wgpu::Device {
-    fn create_buffer_init(&self, desc: &wgpu::util::BufferInitDescriptor) -> wgpu::Buffer {
-        let unpadded_size = desc.contents.len() as u64;
-        let padding = (4 - (unpadded_size % 4)) % 4;
-        let padded_size = unpadded_size + padding;
-
-        let buffer = self.create_buffer(&wgpu::BufferDescriptor {
-            label: desc.label,
-            size: padded_size,
-            usage: desc.usage | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: true,
+/// Collect vertices from all enabled elements for one frame, farthest element first when
+/// `sort` is set, and fogged when the scene has fog. Each element's vertices are pulled
+/// toward the camera by its scene index times `depth_bias`.
+fn build_vertices(
+    elements: &[Element],
+    fog: Option<&FogPass>,
+    sort: bool,
+    depth_bias: f32,
+    ctx: &ExpressionContext,
+) -> FrameVertices {
+    let mut alpha: Vec<LineVertex> = Vec::new();
+    let mut additive: Vec<LineVertex> = Vec::new();
+    let element_count = elements.len() as u32;
+
+    // Index counts disabled elements too, so toggling one doesn't shift the others
+    let mut enabled: Vec<(ExpressionContext, &Element)> = elements
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.is_enabled())
+        .map(|(index, element)| {
+            let ctx = ExpressionContext {
+                index: index as u32,
+                element_count,
+                ..*ctx
+            };
+            (ctx, element)
+        })
+        .collect();
+
+    // Stable, so elements at equal depth keep their scene order
+    if sort {
+        enabled.sort_by(|(a_ctx, a), (b_ctx, b)| {
+            view_depth(anchor(b, b_ctx), ctx).total_cmp(&view_depth(anchor(a, a_ctx), ctx))
         });
+    }
 
-        buffer.slice(..).get_mapped_range_mut()[..desc.contents.len()]
-            .copy_from_slice(desc.contents);
-        buffer.unmap();
+    for (ctx, element) in &enabled {
+        let vertices = match element {
+            Element::Grid(g) => GridPrimitive::from_element(g).vertices(ctx),
+            Element::Wireframe(w) => WireframePrimitive::from_element(w).vertices(ctx),
+            Element::Glyph(g) => GlyphPrimitive::from_element(g).vertices(ctx),
+            Element::Line(l) => LinePrimitive::from_element(l).vertices(ctx),
+            Element::Particles(p) => ParticlesPrimitive::from_element(p).vertices(ctx),
+            Element::Axes(a) => AxesPrimitive::from_element(a).vertices(ctx),
+        };
+        // Later elements sit slightly nearer, so where edges coincide they win as
+        // they would without depth testing
+        let depth_offset = ctx.index as f32 * depth_bias;
+        let vertices = vertices.into_iter().map(|vertex| LineVertex {
+            depth_offset,
+            ..vertex
+        });
+        match element.blend() {
+            BlendMode::Alpha => alpha.extend(vertices),
+            BlendMode::Additive => additive.extend(vertices),
+        }
+    }
+
+    let additive_start = alpha.len();
+    alpha.extend(additive);
+    FrameVertices {
+        vertices: match fog {
+            Some(fog) => fog.apply(alpha, additive_start, ctx),
+            None => alpha,
+        },
+        additive_start,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ping_pong() {
+        let mut frames = vec![0, 1, 2, 3];
+        ping_pong(&mut frames);
+        assert_eq!(frames, vec![0, 1, 2, 3, 2, 1]);
+
+        for short in [vec![], vec![0], vec![0, 1]] {
+            let mut frames = short.clone();
+            ping_pong(&mut frames);
+            assert_eq!(frames, short);
+        }
+    }
+
+    #[test]
+    fn test_nearest_frame() {
+        assert_eq!(nearest_frame(0.0, 60), 0);
+        assert_eq!(nearest_frame(0.5, 60), 30);
+        assert_eq!(nearest_frame(1.0, 60), 59);
+        assert_eq!(nearest_frame(0.5, 1), 0);
+        assert_eq!(nearest_frame(2.0, 10), 9);
+    }
+
+    #[test]
+    fn test_sort_transparency_draws_far_elements_first() {
+        let line = |z: f32| -> Element {
+            let json = format!(r#"{{ "type": "line", "points": [[0, 0, {z}], [1, 0, {z}]] }}"#);
+            serde_json::from_str(&json).unwrap()
+        };
+        // Default camera context: at the origin looking down -z
+        let elements = vec![line(-2.0), line(-20.0), line(-8.0)];
+        let ctx = ExpressionContext::new(0, 1);
+        let first_z = |sort| {
+            let frame = build_vertices(&elements, None, sort, 0.0, &ctx);
+            let per_line = frame.vertices.len() / 3;
+            frame
+                .vertices
+                .iter()
+                .step_by(per_line)
+                .map(|v| v.position[2].round())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(first_z(false), vec![-2.0, -20.0, -8.0]);
+        assert_eq!(first_z(true), vec![-20.0, -8.0, -2.0]);
+    }
+
+    #[test]
+    fn test_depth_bias_steps_by_scene_index() {
+        let line = |enabled: bool| -> Element {
+            let json = format!(
+                r#"{{ "type": "line", "points": [[0, 0, -1], [1, 0, -1]], "enabled": {enabled} }}"#
+            );
+            serde_json::from_str(&json).unwrap()
+        };
+        // The disabled element still holds index 1
+        let elements = vec![line(true), line(false), line(true)];
+        let ctx = ExpressionContext::new(0, 1);
+        let frame = build_vertices(&elements, None, false, 0.01, &ctx);
 
-        buffer
+        let (first, last) = frame.vertices.split_at(frame.vertices.len() / 2);
+        assert!(first.iter().all(|v| v.depth_offset == 0.0));
+        assert!(last.iter().all(|v| v.depth_offset == 0.02));
     }
 }
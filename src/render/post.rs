@@ -1,4 +1,5 @@
-use crate::scene::{ExpressionContext, PostProcessing};
+use crate::scene::{parse_color, ExpressionContext, PostProcessing};
+use std::cell::Cell;
 use std::sync::Arc;
 
 pub struct PostProcessor {
@@ -7,11 +8,19 @@ pub struct PostProcessor {
     width: u32,
     height: u32,
     output_texture: wgpu::Texture,
+    /// Previous frame's output, blended back in for phosphor persistence
+    history_texture: wgpu::Texture,
+    history_view: wgpu::TextureView,
+    /// Frame whose output is in `history_texture`, if any
+    history_frame: Cell<Option<u32>>,
     post_pipeline: Option<wgpu::RenderPipeline>,
     post_bind_group_layout: wgpu::BindGroupLayout,
     sampler: wgpu::Sampler,
     uniform_buffer: wgpu::Buffer,
     settings: PostProcessing,
+    tint: [f32; 4],
+    hdr: bool,
+    transparent: bool,
 }
 
 #[repr(C)]
@@ -26,6 +35,23 @@ struct PostUniforms {
     noise: f32,
     vignette: f32,
     crt_curvature: f32,
+    tonemap: f32,
+    transparent: f32,
+    noise_seed: f32,
+    persistence: f32,
+    bloom_threshold: f32,
+    pixelate: f32,
+    tint: [f32; 4],
+    gamma: f32,
+    saturation: f32,
+    scanline_scroll: f32,
+    vignette_roundness: f32,
+    vignette_smoothness: f32,
+    lens_distortion: f32,
+    dot_mask: f32,
+    dot_mask_scale: f32,
+    glitch: f32,
+    chromatic_aberration_angle: f32,
     _padding: [f32; 2],
 }
 
@@ -36,8 +62,10 @@ impl PostProcessor {
         width: u32,
         height: u32,
         settings: &PostProcessing,
+        hdr: bool,
+        transparent: bool,
     ) -> Self {
-        // Create output texture
+        // Output is always 8-bit so frames can be read back as RGBA8
         let output_texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("post output texture"),
             size: wgpu::Extent3d {
@@ -55,6 +83,22 @@ impl PostProcessor {
             view_formats: &[],
         });
 
+        let history_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("post history texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let history_view = history_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
         // Create sampler
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("post sampler"),
@@ -102,16 +146,37 @@ impl PostProcessor {
                         },
                         count: None,
                     },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
                 ],
             });
 
-        // Check if we need post-processing
-        let needs_post = settings.bloom > 0.0
+        let tint = parse_color(&settings.tint).unwrap_or([1.0; 4]);
+        let needs_grading =
+            tint[..3] != [1.0; 3] || settings.gamma != 1.0 || settings.saturation != 1.0;
+
+        // Check if we need post-processing (HDR always needs the tonemap pass)
+        let needs_post = hdr
+            || settings.bloom > 0.0
             || settings.scanlines.is_some()
             || settings.chromatic_aberration > 0.0
             || settings.noise > 0.0
             || settings.vignette > 0.0
-            || settings.crt_curvature > 0.0;
+            || settings.crt_curvature > 0.0
+            || settings.lens_distortion != 0.0
+            || settings.dot_mask > 0.0
+            || settings.glitch > 0.0
+            || settings.persistence > 0.0
+            || settings.pixelate > 1
+            || needs_grading;
 
         let post_pipeline = if needs_post {
             let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
@@ -163,11 +228,17 @@ impl PostProcessor {
             width,
             height,
             output_texture,
+            history_texture,
+            history_view,
+            history_frame: Cell::new(None),
             post_pipeline,
             post_bind_group_layout,
             sampler,
             uniform_buffer,
             settings: settings.clone(),
+            tint,
+            hdr,
+            transparent,
         }
     }
 
@@ -183,12 +254,24 @@ impl PostProcessor {
         };
 
         // Update uniforms
-        let (scanline_intensity, scanline_count) = self
+        let (scanline_intensity, scanline_count, scanline_scroll) = self
             .settings
             .scanlines
             .as_ref()
-            .map(|s| (s.intensity, s.count as f32))
-            .unwrap_or((0.0, 0.0));
+            .map(|s| (s.intensity, s.count as f32, s.scroll_speed))
+            .unwrap_or((0.0, 0.0, 0.0));
+
+        // Trails only continue from the directly preceding frame, so loops restart
+        // clean at frame 0 and partial renders never blend in stale or blank history
+        let has_history = ctx
+            .frame
+            .checked_sub(1)
+            .is_some_and(|previous| self.history_frame.get() == Some(previous));
+        let persistence = if has_history {
+            self.settings.persistence
+        } else {
+            0.0
+        };
 
         let uniforms = PostUniforms {
             resolution: [self.width as f32, self.height as f32],
@@ -200,7 +283,24 @@ impl PostProcessor {
             noise: self.settings.noise,
             vignette: self.settings.vignette,
             crt_curvature: self.settings.crt_curvature,
-            _padding: [0.0, 0.0],
+            tonemap: if self.hdr { 1.0 } else { 0.0 },
+            transparent: if self.transparent { 1.0 } else { 0.0 },
+            noise_seed: self.settings.noise_seed,
+            persistence,
+            bloom_threshold: self.settings.bloom_threshold,
+            pixelate: self.settings.pixelate as f32,
+            tint: self.tint,
+            gamma: self.settings.gamma,
+            saturation: self.settings.saturation,
+            scanline_scroll,
+            vignette_roundness: self.settings.vignette_roundness,
+            vignette_smoothness: self.settings.vignette_smoothness,
+            lens_distortion: self.settings.lens_distortion,
+            dot_mask: self.settings.dot_mask,
+            dot_mask_scale: self.settings.dot_mask_scale,
+            glitch: self.settings.glitch,
+            chromatic_aberration_angle: self.settings.chromatic_aberration_angle.to_radians(),
+            _padding: [0.0; 2],
         };
         self.queue
             .write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
@@ -222,6 +322,10 @@ impl PostProcessor {
                     binding: 2,
                     resource: self.uniform_buffer.as_entire_binding(),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(&self.history_view),
+                },
             ],
         });
 
@@ -256,6 +360,20 @@ impl PostProcessor {
             render_pass.draw(0..6, 0..1);
         }
 
+        // Keep this frame's output as the next frame's history
+        if self.settings.persistence > 0.0 {
+            encoder.copy_texture_to_texture(
+                self.output_texture.as_image_copy(),
+                self.history_texture.as_image_copy(),
+                wgpu::Extent3d {
+                    width: self.width,
+                    height: self.height,
+                    depth_or_array_layers: 1,
+                },
+            );
+            self.history_frame.set(Some(ctx.frame));
+        }
+
         self.queue.submit(Some(encoder.finish()));
 
         &self.output_texture
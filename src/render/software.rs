@@ -0,0 +1,391 @@
+use super::camera::Camera;
+use super::pipeline::{clear_color, unpremultiply, FrameVertices};
+use crate::primitives::LineVertex;
+use crate::scene::{BlendMode, Scene, Viewport};
+
+/// Draws line lists into an image on the CPU, for machines without a usable GPU adapter.
+///
+/// Lines are one pixel wide like the GPU pipeline's, anti-aliased with Wu's algorithm,
+/// and blend the same way, but post effects, depth testing, and MSAA are skipped, and
+/// gradient backgrounds clear to their `from` color.
+pub struct SoftwareRenderer {
+    width: u32,
+    height: u32,
+    /// Region of the rendered canvas copied into output images
+    viewport: Viewport,
+    background_color: [f32; 4],
+    transparent: bool,
+}
+
+impl SoftwareRenderer {
+    pub fn new(scene: &Scene) -> Self {
+        let (width, height) = (scene.canvas.width, scene.canvas.height);
+        Self {
+            width,
+            height,
+            viewport: scene.canvas.viewport.unwrap_or(Viewport {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            }),
+            background_color: clear_color(scene),
+            transparent: scene.canvas.transparent,
+        }
+    }
+
+    /// Rasterize a frame's line list (consecutive vertex pairs) as seen from `camera`.
+    pub(super) fn draw(&self, camera: &Camera, frame: &FrameVertices) -> image::RgbaImage {
+        let matrix = camera.clip_matrix();
+        let mut canvas = Canvas::new(self.width, self.height, self.background_color);
+
+        let (alpha, additive) = frame.vertices.split_at(frame.additive_start);
+        for (vertices, blend) in [(alpha, BlendMode::Alpha), (additive, BlendMode::Additive)] {
+            for pair in vertices.chunks_exact(2) {
+                let a = ClipVertex::project(&matrix, &pair[0]);
+                let b = ClipVertex::project(&matrix, &pair[1]);
+                if let Some((a, b)) = clip_segment(a, b) {
+                    canvas.line(self.to_screen(a), self.to_screen(b), blend);
+                }
+            }
+        }
+
+        canvas.into_image(self.viewport, self.transparent)
+    }
+
+    /// Perspective divide and map NDC to pixels, with y growing downward.
+    fn to_screen(&self, vertex: ClipVertex) -> ScreenPoint {
+        let [x, y, _, w] = vertex.position;
+        ScreenPoint {
+            x: (x / w + 1.0) * 0.5 * self.width as f32,
+            y: (1.0 - y / w) * 0.5 * self.height as f32,
+            color: vertex.color,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ClipVertex {
+    position: [f32; 4],
+    color: [f32; 4],
+}
+
+impl ClipVertex {
+    fn project(matrix: &[[f32; 4]; 4], vertex: &LineVertex) -> Self {
+        let [x, y, z] = vertex.position;
+        let position = matrix.map(|row| row[0] * x + row[1] * y + row[2] * z + row[3]);
+        Self {
+            position,
+            color: vertex.color,
+        }
+    }
+
+    fn lerp(self, other: Self, t: f32) -> Self {
+        Self {
+            position: lerp4(self.position, other.position, t),
+            color: lerp4(self.color, other.color, t),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ScreenPoint {
+    x: f32,
+    y: f32,
+    color: [f32; 4],
+}
+
+/// Clip a segment to the view volume in homogeneous space, as the GPU does before
+/// the perspective divide. Returns `None` when no part of it is visible.
+fn clip_segment(a: ClipVertex, b: ClipVertex) -> Option<(ClipVertex, ClipVertex)> {
+    // Signed distance to each clip plane, non-negative inside (wgpu depth is 0..w)
+    let planes = |[x, y, z, w]: [f32; 4]| [w + x, w - x, w + y, w - y, z, w - z];
+    let (da, db) = (planes(a.position), planes(b.position));
+
+    let (mut t0, mut t1) = (0.0f32, 1.0f32);
+    for (da, db) in da.into_iter().zip(db) {
+        if da < 0.0 && db < 0.0 {
+            return None;
+        }
+        if da < 0.0 {
+            t0 = t0.max(da / (da - db));
+        } else if db < 0.0 {
+            t1 = t1.min(da / (da - db));
+        }
+    }
+
+    (t0 <= t1).then(|| (a.lerp(b, t0), a.lerp(b, t1)))
+}
+
+/// Premultiplied float color buffer, blended like the GPU line pipelines.
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, clear: [f32; 4]) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![clear; (width * height) as usize],
+        }
+    }
+
+    /// Xiaolin Wu's anti-aliased line. Each step along the major axis splits coverage
+    /// between the two pixels straddling the ideal line, and each end pixel is weighted
+    /// by how much of it the segment spans. Colors interpolate from `a` to `b`, and
+    /// coverage scales their alpha.
+    fn line(&mut self, a: ScreenPoint, b: ScreenPoint, blend: BlendMode) {
+        // Shift so pixel centers sit on integer coordinates
+        let (mut x0, mut y0, mut x1, mut y1) = (a.x - 0.5, a.y - 0.5, b.x - 0.5, b.y - 0.5);
+        let (mut color0, mut color1) = (a.color, b.color);
+
+        // Walk the major axis left to right
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+        if steep {
+            (x0, y0, x1, y1) = (y0, x0, y1, x1);
+        }
+        if x0 > x1 {
+            (x0, y0, x1, y1) = (x1, y1, x0, y0);
+            (color0, color1) = (color1, color0);
+        }
+
+        let dx = x1 - x0;
+        let gradient = if dx == 0.0 { 1.0 } else { (y1 - y0) / dx };
+        let color_at = |x: f32| {
+            let t = if dx == 0.0 { 0.0 } else { ((x - x0) / dx).clamp(0.0, 1.0) };
+            lerp4(color0, color1, t)
+        };
+
+        // Ends: nearest pixel column, weighted by the part of it the segment covers
+        let mut end = |x: f32, y: f32, gap: f32| {
+            let column = (x + 0.5).floor();
+            let y = y + gradient * (column - x);
+            let (color, row) = (color_at(column), y.floor() as i32);
+            self.plot(steep, column as i32, row, color, (1.0 - fract(y)) * gap, blend);
+            self.plot(steep, column as i32, row + 1, color, fract(y) * gap, blend);
+            (column as i32, y)
+        };
+        let (first, start_y) = end(x0, y0, 1.0 - fract(x0 + 0.5));
+        let (last, _) = end(x1, y1, fract(x1 + 0.5));
+
+        let mut y = start_y + gradient;
+        for column in first + 1..last {
+            let color = color_at(column as f32);
+            let (row, coverage) = (y.floor() as i32, fract(y));
+            self.plot(steep, column, row, color, 1.0 - coverage, blend);
+            self.plot(steep, column, row + 1, color, coverage, blend);
+            y += gradient;
+        }
+    }
+
+    /// Blend `color` at `coverage` into a pixel, swapping axes back for steep lines.
+    fn plot(
+        &mut self,
+        steep: bool,
+        x: i32,
+        y: i32,
+        color: [f32; 4],
+        coverage: f32,
+        blend: BlendMode,
+    ) {
+        if coverage <= 0.0 {
+            return;
+        }
+        let (x, y) = if steep { (y, x) } else { (x, y) };
+        let [r, g, b, a] = color;
+        self.blend(x, y, [r, g, b, a * coverage], blend);
+    }
+
+    /// Blend as the line pipelines do: color by source alpha over (or, additive, plus)
+    /// the destination, and alpha source-over either way.
+    fn blend(&mut self, x: i32, y: i32, color: [f32; 4], blend: BlendMode) {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return;
+        }
+        // 8-bit targets clamp fragment output before blending
+        let [r, g, b, a] = color.map(|c| c.clamp(0.0, 1.0));
+        let pixel = &mut self.pixels[(y as u32 * self.width + x as u32) as usize];
+        let keep = match blend {
+            BlendMode::Alpha => 1.0 - a,
+            BlendMode::Additive => 1.0,
+        };
+        *pixel = [
+            r * a + pixel[0] * keep,
+            g * a + pixel[1] * keep,
+            b * a + pixel[2] * keep,
+            a + pixel[3] * (1.0 - a),
+        ];
+    }
+
+    fn into_image(self, viewport: Viewport, transparent: bool) -> image::RgbaImage {
+        let mut image = image::RgbaImage::from_fn(viewport.width, viewport.height, |x, y| {
+            let pixel = self.pixels[((viewport.y + y) * self.width + viewport.x + x) as usize];
+            image::Rgba(pixel.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8))
+        });
+
+        if transparent {
+            unpremultiply(&mut image);
+        }
+
+        image
+    }
+}
+
+/// Fractional part, in 0..1 for negative values too.
+fn fract(v: f32) -> f32 {
+    v - v.floor()
+}
+
+fn lerp4(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+        a[3] + (b[3] - a[3]) * t,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::{AnimatedPoint, Camera as SceneCamera, ExpressionContext};
+
+    const WHITE: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+    fn renderer(transparent: bool) -> SoftwareRenderer {
+        SoftwareRenderer {
+            width: 64,
+            height: 48,
+            viewport: Viewport {
+                x: 0,
+                y: 0,
+                width: 64,
+                height: 48,
+            },
+            background_color: if transparent { [0.0; 4] } else { [0.0, 0.0, 0.0, 1.0] },
+            transparent,
+        }
+    }
+
+    /// Looking down -z from z = 5, so the origin lands in the middle of the image.
+    fn front_camera() -> Camera {
+        let scene_camera = SceneCamera {
+            position: AnimatedPoint::Static([0.0, 0.0, 5.0]),
+            target: AnimatedPoint::Static([0.0, 0.0, 0.0]),
+            ..SceneCamera::default()
+        };
+        Camera::from_scene(&scene_camera, 64, 48, &ExpressionContext::new(0, 1))
+    }
+
+    fn line(from: [f32; 3], to: [f32; 3]) -> FrameVertices {
+        alpha_only(vec![LineVertex::new(from, WHITE), LineVertex::new(to, WHITE)])
+    }
+
+    fn alpha_only(vertices: Vec<LineVertex>) -> FrameVertices {
+        FrameVertices {
+            additive_start: vertices.len(),
+            vertices,
+        }
+    }
+
+    fn lit(image: &image::RgbaImage) -> usize {
+        image.pixels().filter(|p| p.0[..3] != [0, 0, 0]).count()
+    }
+
+    #[test]
+    fn test_horizontal_line_on_pixel_edge_splits_rows() {
+        // y = 0 lands exactly between rows 23 and 24, so each gets half coverage
+        let image = renderer(false).draw(&front_camera(), &line([-1.0, 0.0, 0.0], [1.0, 0.0, 0.0]));
+
+        let lit_rows: Vec<u32> = image
+            .enumerate_pixels()
+            .filter(|(_, _, p)| p.0[..3] != [0, 0, 0])
+            .map(|(_, y, _)| y)
+            .collect();
+        assert!(lit_rows.iter().all(|y| (23..=24).contains(y)));
+
+        let half = image.pixels().filter(|p| p.0 == [128, 128, 128, 255]).count();
+        assert!(half > 20);
+    }
+
+    #[test]
+    fn test_wu_diagonal_coverage() {
+        // From pixel center (0, 0) to (7, 3), so the ideal line passes y = 3x / 7
+        let mut canvas = Canvas::new(8, 8, [0.0; 4]);
+        let point = |x: f32, y: f32| ScreenPoint {
+            x: x + 0.5,
+            y: y + 0.5,
+            color: [1.0, 1.0, 1.0, 0.5],
+        };
+        canvas.line(point(0.0, 0.0), point(7.0, 3.0), BlendMode::Alpha);
+        let alpha = |x: u32, y: u32| canvas.pixels[(y * 8 + x) as usize][3];
+
+        // (column, upper row, its coverage, coverage of the row below) at vertex alpha 0.5
+        let expected = [
+            (1, 0, 0.285714, 0.214286),
+            (2, 0, 0.071429, 0.428571),
+            (3, 1, 0.357143, 0.142857),
+            (4, 1, 0.142857, 0.357143),
+            (5, 2, 0.428571, 0.071429),
+            (6, 2, 0.214286, 0.285714),
+        ];
+        for (x, row, upper, lower) in expected {
+            assert!((alpha(x, row) - upper).abs() < 1e-4, "column {}", x);
+            assert!((alpha(x, row + 1) - lower).abs() < 1e-4, "column {}", x);
+        }
+
+        // Endpoints at pixel centers cover half their pixel
+        assert!((alpha(0, 0) - 0.25).abs() < 1e-4);
+        assert!((alpha(7, 3) - 0.25).abs() < 1e-4);
+        assert_eq!(alpha(0, 1), 0.0);
+    }
+
+    #[test]
+    fn test_segments_behind_camera_are_clipped() {
+        let renderer = renderer(false);
+        let behind = renderer.draw(&front_camera(), &line([-1.0, 0.0, 6.0], [1.0, 0.0, 8.0]));
+        assert_eq!(lit(&behind), 0);
+
+        // Crossing the camera plane keeps only the part in front of it
+        let crossing = renderer.draw(&front_camera(), &line([0.0, -1.0, 0.0], [0.0, -1.0, 10.0]));
+        assert!(lit(&crossing) > 0);
+    }
+
+    #[test]
+    fn test_transparent_canvas_keeps_straight_alpha() {
+        let vertices = alpha_only(vec![
+            LineVertex::new([-1.0, 0.0, 0.0], [1.0, 0.0, 0.0, 0.5]),
+            LineVertex::new([1.0, 0.0, 0.0], [1.0, 0.0, 0.0, 0.5]),
+        ]);
+        let image = renderer(true).draw(&front_camera(), &vertices);
+
+        // Half alpha at half coverage; color is un-premultiplied back to full red
+        let drawn: Vec<_> = image.pixels().filter(|p| p.0[3] > 0).collect();
+        assert!(!drawn.is_empty());
+        assert!(drawn.iter().all(|p| p.0[..3] == [255, 0, 0]));
+        assert_eq!(drawn.iter().map(|p| p.0[3]).max(), Some(64));
+    }
+
+    #[test]
+    fn test_additive_lines_accumulate() {
+        let half_red = [0.5, 0.0, 0.0, 1.0];
+        let crossing = |additive_start| FrameVertices {
+            vertices: vec![
+                LineVertex::new([-1.0, 0.0, 0.0], half_red),
+                LineVertex::new([1.0, 0.0, 0.0], half_red),
+                LineVertex::new([-1.0, 0.0, 0.0], half_red),
+                LineVertex::new([1.0, 0.0, 0.0], half_red),
+            ],
+            additive_start,
+        };
+        let brightest = |image: image::RgbaImage| image.pixels().map(|p| p.0[0]).max();
+
+        // Both lines half-cover rows 23 and 24: alpha blending converges on the line
+        // color, while additive blending sums the two
+        assert_eq!(brightest(renderer(false).draw(&front_camera(), &crossing(4))), Some(96));
+        assert_eq!(brightest(renderer(false).draw(&front_camera(), &crossing(0))), Some(128));
+    }
+}
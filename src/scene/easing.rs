@@ -0,0 +1,255 @@
+use std::f64::consts::PI;
+
+/// Maps progress in 0-1 to eased progress.
+pub type Easing = fn(f64) -> f64;
+
+/// Easing functions callable from expressions, e.g. `ease_out_bounce(t)`.
+/// Each maps 0 to 0 and 1 to 1; inputs are clamped to that range first.
+pub const EASINGS: &[(&str, Easing)] = &[
+    // Short names kept for existing scenes; ease_in_out is smoothstep
+    ("ease_in", in_quad),
+    ("ease_out", out_quad),
+    ("ease_in_out", smoothstep),
+    ("ease_in_quad", in_quad),
+    ("ease_out_quad", out_quad),
+    ("ease_in_out_quad", in_out_quad),
+    ("ease_in_cubic", in_cubic),
+    ("ease_out_cubic", out_cubic),
+    ("ease_in_out_cubic", in_out_cubic),
+    ("ease_in_quart", in_quart),
+    ("ease_out_quart", out_quart),
+    ("ease_in_out_quart", in_out_quart),
+    ("ease_in_sine", in_sine),
+    ("ease_out_sine", out_sine),
+    ("ease_in_out_sine", in_out_sine),
+    ("ease_in_expo", in_expo),
+    ("ease_out_expo", out_expo),
+    ("ease_in_out_expo", in_out_expo),
+    ("ease_in_circ", in_circ),
+    ("ease_out_circ", out_circ),
+    ("ease_in_out_circ", in_out_circ),
+    ("ease_in_back", in_back),
+    ("ease_out_back", out_back),
+    ("ease_in_out_back", in_out_back),
+    ("ease_in_elastic", in_elastic),
+    ("ease_out_elastic", out_elastic),
+    ("ease_in_out_elastic", in_out_elastic),
+    ("ease_in_bounce", in_bounce),
+    ("ease_out_bounce", out_bounce),
+    ("ease_in_out_bounce", in_out_bounce),
+];
+
+// Overshoot constants for the back and elastic curves
+const BACK: f64 = 1.70158;
+const BACK_IN_OUT: f64 = BACK * 1.525;
+const ELASTIC: f64 = 2.0 * PI / 3.0;
+const ELASTIC_IN_OUT: f64 = 2.0 * PI / 4.5;
+
+fn smoothstep(x: f64) -> f64 {
+    x * x * (3.0 - 2.0 * x)
+}
+
+fn in_quad(x: f64) -> f64 {
+    x * x
+}
+
+fn out_quad(x: f64) -> f64 {
+    1.0 - (1.0 - x).powi(2)
+}
+
+fn in_out_quad(x: f64) -> f64 {
+    if x < 0.5 {
+        2.0 * x * x
+    } else {
+        1.0 - (-2.0 * x + 2.0).powi(2) / 2.0
+    }
+}
+
+fn in_cubic(x: f64) -> f64 {
+    x.powi(3)
+}
+
+fn out_cubic(x: f64) -> f64 {
+    1.0 - (1.0 - x).powi(3)
+}
+
+fn in_out_cubic(x: f64) -> f64 {
+    if x < 0.5 {
+        4.0 * x.powi(3)
+    } else {
+        1.0 - (-2.0 * x + 2.0).powi(3) / 2.0
+    }
+}
+
+fn in_quart(x: f64) -> f64 {
+    x.powi(4)
+}
+
+fn out_quart(x: f64) -> f64 {
+    1.0 - (1.0 - x).powi(4)
+}
+
+fn in_out_quart(x: f64) -> f64 {
+    if x < 0.5 {
+        8.0 * x.powi(4)
+    } else {
+        1.0 - (-2.0 * x + 2.0).powi(4) / 2.0
+    }
+}
+
+fn in_sine(x: f64) -> f64 {
+    1.0 - (x * PI / 2.0).cos()
+}
+
+fn out_sine(x: f64) -> f64 {
+    (x * PI / 2.0).sin()
+}
+
+fn in_out_sine(x: f64) -> f64 {
+    -((PI * x).cos() - 1.0) / 2.0
+}
+
+fn in_expo(x: f64) -> f64 {
+    if x == 0.0 {
+        0.0
+    } else {
+        2f64.powf(10.0 * x - 10.0)
+    }
+}
+
+fn out_expo(x: f64) -> f64 {
+    if x == 1.0 {
+        1.0
+    } else {
+        1.0 - 2f64.powf(-10.0 * x)
+    }
+}
+
+fn in_out_expo(x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        x
+    } else if x < 0.5 {
+        2f64.powf(20.0 * x - 10.0) / 2.0
+    } else {
+        (2.0 - 2f64.powf(-20.0 * x + 10.0)) / 2.0
+    }
+}
+
+fn in_circ(x: f64) -> f64 {
+    1.0 - (1.0 - x * x).sqrt()
+}
+
+fn out_circ(x: f64) -> f64 {
+    (1.0 - (x - 1.0).powi(2)).sqrt()
+}
+
+fn in_out_circ(x: f64) -> f64 {
+    if x < 0.5 {
+        (1.0 - (1.0 - (2.0 * x).powi(2)).sqrt()) / 2.0
+    } else {
+        ((1.0 - (-2.0 * x + 2.0).powi(2)).sqrt() + 1.0) / 2.0
+    }
+}
+
+fn in_back(x: f64) -> f64 {
+    (BACK + 1.0) * x.powi(3) - BACK * x * x
+}
+
+fn out_back(x: f64) -> f64 {
+    1.0 + (BACK + 1.0) * (x - 1.0).powi(3) + BACK * (x - 1.0).powi(2)
+}
+
+fn in_out_back(x: f64) -> f64 {
+    if x < 0.5 {
+        (2.0 * x).powi(2) * ((BACK_IN_OUT + 1.0) * 2.0 * x - BACK_IN_OUT) / 2.0
+    } else {
+        ((2.0 * x - 2.0).powi(2) * ((BACK_IN_OUT + 1.0) * (2.0 * x - 2.0) + BACK_IN_OUT) + 2.0)
+            / 2.0
+    }
+}
+
+fn in_elastic(x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        x
+    } else {
+        -(2f64.powf(10.0 * x - 10.0) * ((10.0 * x - 10.75) * ELASTIC).sin())
+    }
+}
+
+fn out_elastic(x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        x
+    } else {
+        2f64.powf(-10.0 * x) * ((10.0 * x - 0.75) * ELASTIC).sin() + 1.0
+    }
+}
+
+fn in_out_elastic(x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        x
+    } else if x < 0.5 {
+        -(2f64.powf(20.0 * x - 10.0) * ((20.0 * x - 11.125) * ELASTIC_IN_OUT).sin()) / 2.0
+    } else {
+        2f64.powf(-20.0 * x + 10.0) * ((20.0 * x - 11.125) * ELASTIC_IN_OUT).sin() / 2.0 + 1.0
+    }
+}
+
+fn in_bounce(x: f64) -> f64 {
+    1.0 - out_bounce(1.0 - x)
+}
+
+fn out_bounce(x: f64) -> f64 {
+    const N: f64 = 7.5625;
+    const D: f64 = 2.75;
+
+    if x < 1.0 / D {
+        N * x * x
+    } else if x < 2.0 / D {
+        let x = x - 1.5 / D;
+        N * x * x + 0.75
+    } else if x < 2.5 / D {
+        let x = x - 2.25 / D;
+        N * x * x + 0.9375
+    } else {
+        let x = x - 2.625 / D;
+        N * x * x + 0.984375
+    }
+}
+
+fn in_out_bounce(x: f64) -> f64 {
+    if x < 0.5 {
+        (1.0 - out_bounce(1.0 - 2.0 * x)) / 2.0
+    } else {
+        (1.0 + out_bounce(2.0 * x - 1.0)) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_easings_hit_endpoints() {
+        for (name, ease) in EASINGS {
+            assert!(ease(0.0).abs() < 1e-9, "{}(0) = {}", name, ease(0.0));
+            assert!((ease(1.0) - 1.0).abs() < 1e-9, "{}(1) = {}", name, ease(1.0));
+        }
+    }
+
+    #[test]
+    fn test_easing_names_unique() {
+        for (i, (name, _)) in EASINGS.iter().enumerate() {
+            assert!(EASINGS[i + 1..].iter().all(|(other, _)| other != name));
+        }
+    }
+
+    #[test]
+    fn test_easing_shapes() {
+        assert_eq!(in_cubic(0.5), 0.125);
+        assert_eq!(in_out_cubic(0.5), 0.5);
+        // Back overshoots past 1 before settling
+        assert!(out_back(0.8) > 1.0);
+        // Bounce touches the ground partway through
+        assert!((out_bounce(1.0 / 2.75) - 1.0).abs() < 1e-9);
+    }
+}
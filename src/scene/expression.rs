@@ -1,12 +1,29 @@
-use evalexpr::{context_map, eval_float_with_context, EvalexprError};
-use std::f32::consts::{PI, TAU};
+use evalexpr::{
+    build_operator_tree, context_map, ContextWithMutableFunctions, ContextWithMutableVariables,
+    EvalexprError, EvalexprResult, Function, HashMapContext, Node, Value,
+};
+use schemars::r#gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::f32::consts::{E, PI, TAU};
+use std::fmt;
 use thiserror::Error;
 
+use super::easing::EASINGS;
+
+/// The golden ratio, (1 + sqrt(5)) / 2
+const PHI: f64 = 1.618_033_988_749_895;
+
 #[derive(Debug, Error)]
 pub enum ExpressionError {
     #[error("Failed to create evaluation context")]
     ContextCreationFailed,
 
+    #[error("Expression parse failed: {0}")]
+    ParseFailed(String),
+
     #[error("Expression evaluation failed: {0}")]
     EvaluationFailed(#[from] EvalexprError),
 }
@@ -16,6 +33,24 @@ pub struct ExpressionContext {
     pub t: f32,
     pub frame: u32,
     pub total_frames: u32,
+    /// Position of the element being evaluated in `scene.elements`, for staggering
+    /// animations across elements, and the length of that list.
+    pub index: u32,
+    pub element_count: u32,
+    /// Which copy of a repeated wireframe is being drawn, and how many there are.
+    pub instance: u32,
+    pub instance_count: u32,
+    /// World-space screen right and up for this frame's camera, so billboards can face
+    /// the viewer. Defaults to the X/Y axes until the renderer fills them in.
+    pub camera_right: [f32; 3],
+    pub camera_up: [f32; 3],
+    /// World-space camera position, for culling faces that point away from the viewer.
+    pub camera_position: [f32; 3],
+    /// Unit view direction and the world-space height of one pixel at unit distance
+    /// along it, for sizing things in pixels. `pixel_size` is 0 until the renderer
+    /// fills it in.
+    pub camera_forward: [f32; 3],
+    pub pixel_size: f32,
 }
 
 impl ExpressionContext {
@@ -29,68 +64,283 @@ impl ExpressionContext {
             t,
             frame,
             total_frames,
+            index: 0,
+            element_count: 1,
+            instance: 0,
+            instance_count: 1,
+            camera_right: [1.0, 0.0, 0.0],
+            camera_up: [0.0, 1.0, 0.0],
+            camera_position: [0.0, 0.0, 0.0],
+            camera_forward: [0.0, 0.0, -1.0],
+            pixel_size: 0.0,
         }
     }
 }
 
+/// An expression parsed once when the scene is loaded, so rendering a frame only swaps
+/// the frame variables into the context instead of re-parsing the string.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct CompiledExpression {
+    source: String,
+    tree: Result<Node, String>,
+}
+
+impl CompiledExpression {
+    pub fn new(source: impl Into<String>) -> Self {
+        let source = source.into();
+        // Pre-process expression to handle custom functions
+        let tree = build_operator_tree(&preprocess_expression(&source)).map_err(|e| e.to_string());
+        Self { source, tree }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    pub fn evaluate(&self, ctx: &ExpressionContext) -> Result<f32, ExpressionError> {
+        let tree = self.tree.as_ref().map_err(|e| ExpressionError::ParseFailed(e.clone()))?;
+        with_context(ctx, |context| Ok(tree.eval_float_with_context(context)? as f32))
+    }
+}
+
+impl From<String> for CompiledExpression {
+    fn from(source: String) -> Self {
+        Self::new(source)
+    }
+}
+
+impl From<&str> for CompiledExpression {
+    fn from(source: &str) -> Self {
+        Self::new(source)
+    }
+}
+
+impl From<CompiledExpression> for String {
+    fn from(expr: CompiledExpression) -> Self {
+        expr.source
+    }
+}
+
+impl PartialEq<str> for CompiledExpression {
+    fn eq(&self, other: &str) -> bool {
+        self.source == other
+    }
+}
+
+impl PartialEq<&str> for CompiledExpression {
+    fn eq(&self, other: &&str) -> bool {
+        self.source == *other
+    }
+}
+
+/// Expressions are plain strings in scene files; the parsed tree never reaches JSON.
+impl JsonSchema for CompiledExpression {
+    fn schema_name() -> String {
+        "Expression".to_string()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        String::json_schema(generator)
+    }
+}
+
+impl fmt::Display for CompiledExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
 pub fn evaluate_expression(expr: &str, ctx: &ExpressionContext) -> Result<f32, ExpressionError> {
-    let context = context_map! {
-        "t" => ctx.t as f64,
-        "frame" => ctx.frame as i64,
-        "total_frames" => ctx.total_frames as i64,
+    CompiledExpression::new(expr).evaluate(ctx)
+}
+
+thread_local! {
+    // Constants and functions never change, so each thread builds them once and
+    // only the frame variables are overwritten before every evaluation
+    static CONTEXT: RefCell<Option<HashMapContext>> = const { RefCell::new(None) };
+}
+
+fn with_context<R>(
+    ctx: &ExpressionContext,
+    f: impl FnOnce(&HashMapContext) -> Result<R, ExpressionError>,
+) -> Result<R, ExpressionError> {
+    CONTEXT.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        let context = match &mut *slot {
+            Some(context) => context,
+            None => slot.insert(build_context()?),
+        };
+
+        let variables = [
+            ("t", Value::Float(ctx.t as f64)),
+            ("frame", Value::Int(ctx.frame as i64)),
+            ("total_frames", Value::Int(ctx.total_frames as i64)),
+            ("index", Value::Int(ctx.index as i64)),
+            ("element_count", Value::Int(ctx.element_count as i64)),
+            // Floats, so `instance * 15` evaluates to a float rather than an integer
+            ("instance", Value::Float(ctx.instance as f64)),
+            ("instance_count", Value::Float(ctx.instance_count as f64)),
+        ];
+        for (name, value) in variables {
+            context
+                .set_value(name.to_string(), value)
+                .map_err(|_| ExpressionError::ContextCreationFailed)?;
+        }
+
+        f(context)
+    })
+}
+
+fn build_context() -> Result<HashMapContext, ExpressionError> {
+    let mut context = context_map! {
         "PI" => PI as f64,
         "TAU" => TAU as f64,
+        "E" => E as f64,
+        "PHI" => PHI,
+        // Always return floats so results work with eval_float, even for integer arguments
+        "clamp" => Function::new(|argument| {
+            let [x, lo, hi] = float_args(argument)?;
+            Ok(Value::Float(x.max(lo).min(hi)))
+        }),
+        "lerp" => Function::new(|argument| {
+            let [a, b, t] = float_args(argument)?;
+            Ok(Value::Float(a + (b - a) * t))
+        }),
+        "min" => Function::new(|argument| {
+            let [a, b] = float_args(argument)?;
+            Ok(Value::Float(a.min(b)))
+        }),
+        "max" => Function::new(|argument| {
+            let [a, b] = float_args(argument)?;
+            Ok(Value::Float(a.max(b)))
+        }),
+        // Euclidean modulo, so mod(-1, 360) wraps to 359 like an angle should
+        "mod" => Function::new(|argument| {
+            let [x, n] = float_args(argument)?;
+            Ok(Value::Float(x.rem_euclid(n)))
+        }),
+        // Both branches are evaluated; the condition must be a boolean such as `t < 0.5`
+        "if" => Function::new(|argument| {
+            let tuple = argument.as_fixed_len_tuple(3)?;
+            let branch = if tuple[0].as_boolean()? { &tuple[1] } else { &tuple[2] };
+            Ok(Value::Float(branch.as_number()?))
+        }),
+        // Deterministic so the same frame always renders the same jitter
+        "random" => Function::new(|argument| Ok(Value::Float(hash_unit(argument.as_number()?)))),
+        "noise" => Function::new(|argument| Ok(Value::Float(value_noise(argument.as_number()?)))),
     }
     .map_err(|_| ExpressionError::ContextCreationFailed)?;
 
-    // Pre-process expression to handle custom functions
-    let processed = preprocess_expression(expr);
+    for &(name, ease) in EASINGS {
+        let function = Function::new(move |argument| {
+            Ok(Value::Float(ease(argument.as_number()?.clamp(0.0, 1.0))))
+        });
+        context
+            .set_function(name.to_string(), function)
+            .map_err(|_| ExpressionError::ContextCreationFailed)?;
+    }
 
-    let result = eval_float_with_context(&processed, &context)?;
-    Ok(result as f32)
+    Ok(context)
 }
 
-fn preprocess_expression(expr: &str) -> String {
-    let mut result = expr.to_string();
+/// Unpack a function's argument tuple into exactly `N` numbers.
+fn float_args<const N: usize>(argument: &Value) -> EvalexprResult<[f64; N]> {
+    let tuple = argument.as_fixed_len_tuple(N)?;
+    let mut args = [0.0; N];
+    for (arg, value) in args.iter_mut().zip(&tuple) {
+        *arg = value.as_number()?;
+    }
+    Ok(args)
+}
 
-    // Replace easing functions with their expanded forms
-    // ease_in(x) = x^2
-    // ease_out(x) = 1 - (1-x)^2
-    // ease_in_out(x) = 2*x^2 if x < 0.5, else 1 - (-2*x + 2)^2 / 2
+/// Hash a number to a stable value in [0, 1). `random(3)` and `random(3.0)` agree.
+fn hash_unit(n: f64) -> f64 {
+    // Normalize -0.0 so it hashes like 0.0
+    let n = if n == 0.0 { 0.0 } else { n };
 
-    // For simplicity, we'll handle these as approximations
-    // A more robust solution would use a proper expression transformer
+    // SplitMix64 finalizer
+    let mut z = n.to_bits().wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
 
-    // Replace ease_in_out(t) with a polynomial approximation
-    if result.contains("ease_in_out") {
-        // Approximate: 3*t^2 - 2*t^3 (smoothstep)
-        result = result.replace("ease_in_out(t)", "(3.0 * t * t - 2.0 * t * t * t)");
-    }
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
 
-    // Replace ease_in(t) with t^2
-    if result.contains("ease_in") {
-        result = result.replace("ease_in(t)", "(t * t)");
-    }
+/// 1D value noise in [0, 1): random values at integer points, smoothly interpolated.
+fn value_noise(x: f64) -> f64 {
+    let i = x.floor();
+    let f = x - i;
+    let s = f * f * (3.0 - 2.0 * f);
+    let a = hash_unit(i);
+    let b = hash_unit(i + 1.0);
+    a + (b - a) * s
+}
 
-    // Replace ease_out(t) with 1 - (1-t)^2
-    if result.contains("ease_out") {
-        result = result.replace("ease_out(t)", "(1.0 - (1.0 - t) * (1.0 - t))");
-    }
+/// Functions users may call without evalexpr's `math::` namespace.
+const MATH_FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "asin", "acos", "atan", "sinh", "cosh", "tanh", "sqrt", "abs", "floor",
+    "ceil", "round",
+];
+
+/// Add the `math::` prefix to bare math function calls so users can write `sin(x)`.
+/// Whole identifiers are matched, so `asin(` or a name like `sinc` is never rewritten
+/// mid-word, and string literals are copied through untouched.
+fn preprocess_expression(expr: &str) -> String {
+    let mut result = String::with_capacity(expr.len());
+    let mut chars = expr.char_indices().peekable();
+    let mut in_string = false;
+
+    while let Some((start, c)) = chars.next() {
+        if in_string {
+            result.push(c);
+            if c == '\\' {
+                if let Some((_, escaped)) = chars.next() {
+                    result.push(escaped);
+                }
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        if c == '"' {
+            in_string = true;
+            result.push(c);
+            continue;
+        }
 
-    // Add math:: prefix to trig functions for evalexpr compatibility
-    // This allows users to write sin(x) instead of math::sin(x)
-    for func in ["sin", "cos", "tan", "asin", "acos", "atan", "sinh", "cosh", "tanh", "sqrt", "abs", "floor", "ceil", "round"] {
-        let pattern = format!("{}(", func);
-        let replacement = format!("math::{}(", func);
-        // Only replace if not already prefixed with math::
-        if result.contains(&pattern) && !result.contains(&replacement) {
-            result = result.replace(&pattern, &replacement);
+        if !is_identifier_char(c) {
+            result.push(c);
+            continue;
         }
+
+        let mut end = start + c.len_utf8();
+        while let Some(&(i, next)) = chars.peek() {
+            if !is_identifier_char(next) {
+                break;
+            }
+            end = i + next.len_utf8();
+            chars.next();
+        }
+
+        let identifier = &expr[start..end];
+        let is_call = expr[end..].trim_start().starts_with('(');
+        // Already namespaced calls like `math::sin(` are left alone
+        if is_call && !result.ends_with("::") && MATH_FUNCTIONS.contains(&identifier) {
+            result.push_str("math::");
+        }
+        result.push_str(identifier);
     }
 
     result
 }
 
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,6 +359,37 @@ mod tests {
         let ctx = ExpressionContext::new(0, 30);
         let result = evaluate_expression("PI", &ctx).expect("PI should evaluate");
         assert!((result - PI).abs() < 0.001);
+
+        let e = evaluate_expression("E", &ctx).expect("E should evaluate");
+        assert!((e - E).abs() < 0.001);
+        let phi = evaluate_expression("PHI * PHI - PHI", &ctx).expect("PHI should evaluate");
+        assert!((phi - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_element_index_variables() {
+        let ctx = ExpressionContext {
+            index: 3,
+            element_count: 4,
+            ..ExpressionContext::new(0, 30)
+        };
+        let result = evaluate_expression("1 - index * 0.1", &ctx).unwrap();
+        assert!((result - 0.7).abs() < 1e-6);
+
+        let last = evaluate_expression("index / (element_count - 1.0)", &ctx).unwrap();
+        assert_eq!(last, 1.0);
+    }
+
+    #[test]
+    fn test_instance_variables_are_floats() {
+        let ctx = ExpressionContext {
+            instance: 2,
+            instance_count: 4,
+            ..ExpressionContext::new(0, 30)
+        };
+        assert_eq!(evaluate_expression("instance * 15", &ctx).unwrap(), 30.0);
+        assert_eq!(evaluate_expression("instance + 1", &ctx).unwrap(), 3.0);
+        assert_eq!(evaluate_expression("instance / instance_count", &ctx).unwrap(), 0.5);
     }
 
     #[test]
@@ -118,6 +399,131 @@ mod tests {
         assert!(result.abs() < 0.001);
     }
 
+    #[test]
+    fn test_math_helpers() {
+        let ctx = ExpressionContext::new(0, 30);
+        let eval = |expr| evaluate_expression(expr, &ctx).expect("helper should evaluate");
+        assert_eq!(eval("clamp(2, 0, 1)"), 1.0);
+        assert_eq!(eval("clamp(-0.5, 0, 1)"), 0.0);
+        assert_eq!(eval("lerp(0, 10, 0.5)"), 5.0);
+        assert_eq!(eval("min(3, 1.5)"), 1.5);
+        assert_eq!(eval("max(3, 1.5)"), 3.0);
+        assert_eq!(eval("mod(370, 360)"), 10.0);
+        assert_eq!(eval("mod(-90, 360)"), 270.0);
+        assert_eq!(eval("lerp(0, 1, clamp(t * 2, 0, 1))"), 0.0);
+    }
+
+    #[test]
+    fn test_if_selects_branch() {
+        let early = ExpressionContext::new(0, 30);
+        let late = ExpressionContext::new(29, 30);
+        assert_eq!(evaluate_expression("if(t < 0.5, 0, 1)", &early).unwrap(), 0.0);
+        assert_eq!(evaluate_expression("if(t < 0.5, 0, 1)", &late).unwrap(), 1.0);
+        assert_eq!(evaluate_expression("if(frame == 0 && t < 1, 2.5, 0)", &early).unwrap(), 2.5);
+
+        // Conditions must be booleans, and all three arguments are required
+        assert!(evaluate_expression("if(t, 0, 1)", &early).is_err());
+        assert!(evaluate_expression("if(t < 0.5, 0)", &early).is_err());
+    }
+
+    #[test]
+    fn test_math_helpers_wrong_arity() {
+        let ctx = ExpressionContext::new(0, 30);
+        assert!(evaluate_expression("clamp(2, 0)", &ctx).is_err());
+        assert!(evaluate_expression("lerp(1)", &ctx).is_err());
+    }
+
+    #[test]
+    fn test_random_is_deterministic() {
+        let ctx = ExpressionContext::new(7, 30);
+        let a = evaluate_expression("random(frame)", &ctx).unwrap();
+        let b = evaluate_expression("random(7.0)", &ctx).unwrap();
+        assert_eq!(a, b);
+        assert!((0.0..1.0).contains(&a));
+
+        let c = evaluate_expression("random(8)", &ctx).unwrap();
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_noise_is_smooth_and_bounded() {
+        for i in 0..1000 {
+            let x = i as f64 * 0.013 - 5.0;
+            let n = value_noise(x);
+            assert!((0.0..1.0).contains(&n), "noise({}) = {}", x, n);
+            assert!((value_noise(x + 0.001) - n).abs() < 0.01);
+        }
+        // Integer points land exactly on the lattice values
+        assert_eq!(value_noise(3.0), hash_unit(3.0));
+    }
+
+    #[test]
+    fn test_noise_in_expression() {
+        let ctx = ExpressionContext::new(12, 30);
+        let first = evaluate_expression("frame * 5 + noise(t * 10) * 20", &ctx).unwrap();
+        let second = evaluate_expression("frame * 5 + noise(t * 10) * 20", &ctx).unwrap();
+        assert_eq!(first, second);
+        assert!((60.0..80.0).contains(&first));
+    }
+
+    #[test]
+    fn test_easing_functions() {
+        let ctx = ExpressionContext::new(15, 31);
+        let eval = |expr| evaluate_expression(expr, &ctx).expect("easing should evaluate");
+        assert!((eval("ease_in(t)") - 0.25).abs() < 1e-6);
+        assert!((eval("ease_out(t)") - 0.75).abs() < 1e-6);
+        assert!((eval("ease_in_out(t)") - 0.5).abs() < 1e-6);
+        assert!((eval("ease_in_cubic(t)") - 0.125).abs() < 1e-6);
+        // Arbitrary sub-expressions, clamped to 0..1
+        assert!((eval("ease_out_bounce(t * 4)") - 1.0).abs() < 1e-6);
+        assert!((eval("ease_in_out_elastic(1 - t * 2) * 10")).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_unknown_easing_returns_error() {
+        let ctx = ExpressionContext::new(0, 30);
+        assert!(evaluate_expression("ease_out_wobble(t)", &ctx).is_err());
+    }
+
+    #[test]
+    fn test_preprocess_matches_whole_identifiers() {
+        assert_eq!(preprocess_expression("asin(0.5)"), "math::asin(0.5)");
+        assert_eq!(preprocess_expression("sinc + sin (t)"), "sinc + math::sin (t)");
+        assert_eq!(preprocess_expression("math::sin(t) + cos(t)"), "math::sin(t) + math::cos(t)");
+        assert_eq!(preprocess_expression("ease_in_out(t)"), "ease_in_out(t)");
+        assert_eq!(preprocess_expression(r#"str::len("sin(")"#), r#"str::len("sin(")"#);
+    }
+
+    #[test]
+    fn test_overlapping_function_names_evaluate() {
+        let ctx = ExpressionContext::new(15, 31);
+        let asin = evaluate_expression("asin(0.5)", &ctx).unwrap();
+        assert!((asin - 0.5f32.asin()).abs() < 1e-6);
+
+        let tanh = evaluate_expression("tanh(1) + atan(1)", &ctx).unwrap();
+        assert!((tanh - (1f32.tanh() + 1f32.atan())).abs() < 1e-6);
+
+        let eased = evaluate_expression("ease_in_out(t) + ease_in(t)", &ctx).unwrap();
+        assert!((eased - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_compiled_expression_reuses_tree() {
+        let expr = CompiledExpression::new("t * 360");
+        let first = expr.evaluate(&ExpressionContext::new(0, 2)).unwrap();
+        let last = expr.evaluate(&ExpressionContext::new(1, 2)).unwrap();
+        assert_eq!(first, 0.0);
+        assert_eq!(last, 360.0);
+        assert_eq!(expr.as_str(), "t * 360");
+    }
+
+    #[test]
+    fn test_compiled_expression_parse_error() {
+        let expr = CompiledExpression::new("(1 + 2");
+        let result = expr.evaluate(&ExpressionContext::new(0, 30));
+        assert!(matches!(result, Err(ExpressionError::ParseFailed(_))));
+    }
+
     #[test]
     fn test_invalid_expression_returns_error() {
         let ctx = ExpressionContext::new(0, 30);
@@ -0,0 +1,128 @@
+/// Blank out `//` and `/* */` comments, and commas directly before a closing `]` or `}`,
+/// so hand-written scenes parse as strict JSON. Removed bytes become spaces and newlines
+/// are kept, so parse errors still report the line and column of the original file.
+pub fn strip_jsonc(source: &str) -> String {
+    let bytes = source.as_bytes();
+    let mut out = String::with_capacity(source.len());
+    // Source before this index has been copied or blanked into `out`; the two stay the
+    // same length, so indices carry over
+    let mut copied = 0;
+    let mut in_string = false;
+    // Last comma outside a string, until something other than whitespace follows it
+    let mut pending_comma = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if in_string {
+            match byte {
+                b'\\' => i += 1,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+
+        match (byte, bytes.get(i + 1).copied()) {
+            (b'/', Some(b'/')) => {
+                let end = source[i..].find('\n').map_or(source.len(), |e| i + e);
+                out.push_str(&source[copied..i]);
+                out.extend(blank(&source[i..end]));
+                copied = end;
+                i = end;
+                continue;
+            }
+            (b'/', Some(b'*')) => {
+                // An unterminated comment runs to the end of the file
+                let end = source[i + 2..]
+                    .find("*/")
+                    .map_or(source.len(), |e| i + 2 + e + 2);
+                out.push_str(&source[copied..i]);
+                out.extend(blank(&source[i..end]));
+                copied = end;
+                i = end;
+                continue;
+            }
+            (b'"', _) => {
+                in_string = true;
+                pending_comma = None;
+            }
+            (b',', _) => pending_comma = Some(i),
+            (b']' | b'}', _) => {
+                if let Some(comma) = pending_comma.take() {
+                    out.push_str(&source[copied..i]);
+                    copied = i;
+                    out.replace_range(comma..comma + 1, " ");
+                }
+            }
+            (b, _) if b.is_ascii_whitespace() => {}
+            _ => pending_comma = None,
+        }
+        i += 1;
+    }
+
+    out.push_str(&source[copied..]);
+    out
+}
+
+/// One space per byte of `text`, keeping its newlines.
+fn blank(text: &str) -> impl Iterator<Item = char> + '_ {
+    text.bytes().map(|b| if b == b'\n' { '\n' } else { ' ' })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::Scene;
+
+    #[test]
+    fn test_strict_json_is_unchanged() {
+        let json = r#"{ "elements": [ { "type": "glyph", "text": "a // b /* c */, ]" } ] }"#;
+        assert_eq!(strip_jsonc(json), json);
+    }
+
+    #[test]
+    fn test_strips_comments_and_trailing_commas() {
+        let source = r#"{
+            // the camera stays put
+            "canvas": {},
+            "fps": 30, /* half a second */ "duration": 0.5,
+            "elements": [
+                { "type": "axes", },
+                { "type": "grid" /* floor */ },
+            ],
+        }"#;
+        let scene: Scene = serde_json::from_str(&strip_jsonc(source)).unwrap();
+        assert_eq!(scene.fps, 30);
+        assert_eq!(scene.elements.len(), 2);
+    }
+
+    #[test]
+    fn test_keeps_line_numbers() {
+        let source = "{\n  // note\n  \"fps\": \"thirty\",\n}";
+        let stripped = strip_jsonc(source);
+        assert_eq!(stripped.lines().count(), source.lines().count());
+
+        let err = serde_json::from_str::<Scene>(&stripped).unwrap_err();
+        assert_eq!(err.line(), 3);
+    }
+
+    #[test]
+    fn test_escaped_quotes_stay_in_string() {
+        let source = r#"{ "text": "say \"hi\" // not a comment", }"#;
+        assert_eq!(
+            strip_jsonc(source),
+            r#"{ "text": "say \"hi\" // not a comment"  }"#
+        );
+    }
+
+    #[test]
+    fn test_non_ascii_comments_keep_byte_columns() {
+        let source = "{ \"text\": \"café\", /* é */ }";
+        // The two-byte é in the comment becomes two spaces
+        let stripped = strip_jsonc(source);
+        assert_eq!(stripped.len(), source.len());
+        assert_eq!(stripped, format!("{{ \"text\": \"café\"{}}}", " ".repeat(11)));
+    }
+}
@@ -1,8 +1,14 @@
+mod colors;
+mod easing;
 mod expression;
+mod jsonc;
 mod schema;
 pub mod templates;
 mod validate;
 
-pub use expression::{evaluate_expression, ExpressionContext, ExpressionError};
+pub use expression::{
+    evaluate_expression, CompiledExpression, ExpressionContext, ExpressionError,
+};
+pub use jsonc::strip_jsonc;
 pub use schema::*;
 pub use validate::ValidationError;
@@ -1,8 +1,13 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::primitives::GeometryData;
 
 use super::validate::ValidationError;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Scene {
     pub canvas: Canvas,
     #[serde(default)]
@@ -13,15 +18,76 @@ pub struct Scene {
     pub fps: u32,
     #[serde(default = "default_loop")]
     pub r#loop: bool,
+    /// Extra plays after the first, overriding `loop`; 0 repeats forever.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loop_count: Option<u32>,
+    #[serde(default)]
+    pub playback: Playback,
     #[serde(default)]
     pub elements: Vec<Element>,
     #[serde(default)]
     pub post: PostProcessing,
+    /// Test and write a depth buffer so nearer geometry occludes farther geometry.
+    #[serde(default)]
+    pub depth_test: bool,
+    /// Clip-space depth step between elements when depth testing: each element is pulled
+    /// toward the camera by its index times this, so later elements win coincident edges.
+    #[serde(default)]
+    pub depth_bias: f32,
+    /// Samples per pixel for anti-aliased lines: 1 (off) or 4.
+    #[serde(default = "default_msaa")]
+    pub msaa: u32,
+    /// Most vertices all enabled elements may emit in one frame. Validation rejects
+    /// larger scenes rather than letting the renderer run out of memory.
+    #[serde(default = "default_max_vertices")]
+    pub max_vertices: u64,
+    /// Fade every element toward a fog color with distance from the camera.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fog: Option<Fog>,
+    /// Draw elements farthest-first by the view depth of their position, so blending
+    /// composites correctly in mostly transparent scenes without a depth buffer.
+    #[serde(default)]
+    pub sort_transparency: bool,
+}
+
+/// Atmospheric falloff for the whole scene: vertex colors are untouched up to `near`
+/// units in front of the camera and blend linearly to `color` by `far`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Fog {
+    #[serde(default = "default_fog_color")]
+    pub color: String,
+    #[serde(default)]
+    pub near: f32,
+    #[serde(default = "default_fog_far")]
+    pub far: f32,
+}
+
+fn default_fog_color() -> String {
+    "#0a0a0a".to_string()
+}
+fn default_fog_far() -> f32 {
+    20.0
+}
+
+/// Order frames play in. `pingpong` plays forward then backward without repeating the
+/// end frames, so output runs about twice as long as `duration`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Playback {
+    #[default]
+    Loop,
+    Pingpong,
 }
 
 fn default_duration() -> f32 {
     2.0
 }
+fn default_msaa() -> u32 {
+    1
+}
+fn default_max_vertices() -> u64 {
+    4_000_000
+}
 fn default_fps() -> u32 {
     30
 }
@@ -34,19 +100,77 @@ impl Scene {
         (self.duration * self.fps as f32).ceil() as u32
     }
 
+    /// Extra plays after the first: `Some(0)` repeats forever and `None` plays once.
+    pub fn repeats(&self) -> Option<u32> {
+        self.loop_count.or(self.r#loop.then_some(0))
+    }
+
+    /// Playback length actually produced once the frame count is rounded up.
+    pub fn effective_duration(&self) -> f32 {
+        self.total_frames() as f32 / self.fps as f32
+    }
+
     pub fn validate(&self) -> Result<(), ValidationError> {
         super::validate::validate_scene(self)
     }
+
+    /// Decode the background image and parse every OBJ model once, resolving relative
+    /// paths against `base_dir` (the directory holding the scene file), so rendering
+    /// never touches the filesystem.
+    pub fn load_assets(&mut self, base_dir: &Path) -> Result<(), ValidationError> {
+        if let Background::Image { image: path, pixels } = &mut self.canvas.background {
+            let decoded = image::open(base_dir.join(&*path)).map_err(|e| {
+                ValidationError::InvalidValue(format!("background image '{}': {}", path, e))
+            })?;
+            *pixels = Some(Arc::new(decoded.to_rgba8()));
+        }
+
+        for (i, element) in self.elements.iter_mut().enumerate() {
+            let Element::Wireframe(wf) = element else {
+                continue;
+            };
+            if let GeometryType::Obj { path, mesh } = &mut wf.geometry {
+                let geometry =
+                    crate::primitives::load_obj(&base_dir.join(&*path)).map_err(|e| {
+                        ValidationError::InvalidElement(format!(
+                            "Element {}: obj '{}': {}",
+                            i, path, e
+                        ))
+                    })?;
+                *mesh = Some(Arc::new(geometry));
+            }
+        }
+        Ok(())
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Canvas {
     #[serde(default = "default_width")]
     pub width: u32,
     #[serde(default = "default_height")]
     pub height: u32,
     #[serde(default = "default_background")]
-    pub background: String,
+    pub background: Background,
+    /// Render into a 16-bit float target and tonemap to 8-bit on output.
+    #[serde(default)]
+    pub hdr: bool,
+    /// Clear to transparent instead of the background so PNG frames keep alpha.
+    /// GIF output is still flattened onto the background color.
+    #[serde(default)]
+    pub transparent: bool,
+    /// Output only this pixel rectangle of the canvas, keeping the full canvas framing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub viewport: Option<Viewport>,
+}
+
+/// Pixel rectangle of the canvas, measured from the top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Viewport {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
 }
 
 fn default_width() -> u32 {
@@ -55,8 +179,56 @@ fn default_width() -> u32 {
 fn default_height() -> u32 {
     600
 }
-fn default_background() -> String {
-    "#0a0a0a".to_string()
+fn default_background() -> Background {
+    Background::Color("#0a0a0a".to_string())
+}
+
+/// Canvas background:
+/// - Color: `"#0a0a0a"`
+/// - Linear gradient: `{ "gradient": { "from": "#000", "to": "#003", "angle": 90 } }`
+/// - Image stretched to the canvas: `{ "image": "bg.png" }`, relative to the scene file
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Background {
+    Color(String),
+    Gradient { gradient: BackgroundGradient },
+    Image {
+        image: String,
+        /// Decoded image, filled in by [`Scene::load_assets`]
+        #[serde(skip)]
+        pixels: Option<Arc<image::RgbaImage>>,
+    },
+}
+
+impl Background {
+    /// Solid color used for the clear and for flattening transparent frames.
+    /// Gradients use their `from` color; images have none.
+    pub fn base_color(&self) -> Option<[f32; 4]> {
+        match self {
+            Background::Color(color) => parse_color(color),
+            Background::Gradient { gradient } => parse_color(&gradient.from),
+            Background::Image { .. } => None,
+        }
+    }
+}
+
+impl From<&str> for Background {
+    fn from(color: &str) -> Self {
+        Background::Color(color.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BackgroundGradient {
+    pub from: String,
+    pub to: String,
+    /// CSS-style direction in degrees: 0 runs bottom to top, 90 left to right.
+    #[serde(default = "default_gradient_angle")]
+    pub angle: f32,
+}
+
+fn default_gradient_angle() -> f32 {
+    180.0
 }
 
 impl Default for Canvas {
@@ -65,25 +237,31 @@ impl Default for Canvas {
             width: default_width(),
             height: default_height(),
             background: default_background(),
+            hdr: false,
+            transparent: false,
+            viewport: None,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Camera {
     #[serde(default = "default_camera_position")]
-    pub position: [f32; 3],
+    pub position: AnimatedPoint,
     #[serde(default = "default_camera_target")]
-    pub target: [f32; 3],
+    pub target: AnimatedPoint,
     #[serde(default = "default_fov")]
     pub fov: f32,
+    /// Orbit the camera around its target over the scene duration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turntable: Option<Turntable>,
 }
 
-fn default_camera_position() -> [f32; 3] {
-    [5.0, 5.0, 5.0]
+fn default_camera_position() -> AnimatedPoint {
+    AnimatedPoint::Static([5.0, 5.0, 5.0])
 }
-fn default_camera_target() -> [f32; 3] {
-    [0.0, 0.0, 0.0]
+fn default_camera_target() -> AnimatedPoint {
+    AnimatedPoint::Static([0.0, 0.0, 0.0])
 }
 fn default_fov() -> f32 {
     45.0
@@ -95,31 +273,139 @@ impl Default for Camera {
             position: default_camera_position(),
             target: default_camera_target(),
             fov: default_fov(),
+            turntable: None,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Automatic camera orbit around the target, completing `revolutions` turns
+/// over the animation so the last frame loops back into the first.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub struct Turntable {
+    #[serde(default)]
+    pub axis: TurntableAxis,
+    #[serde(default = "default_revolutions")]
+    pub revolutions: f32,
+}
+
+fn default_revolutions() -> f32 {
+    1.0
+}
+
+impl Default for Turntable {
+    fn default() -> Self {
+        Self {
+            axis: TurntableAxis::default(),
+            revolutions: default_revolutions(),
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default, clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TurntableAxis {
+    X,
+    #[default]
+    Y,
+    Z,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum Element {
     Grid(GridElement),
-    Wireframe(WireframeElement),
+    Wireframe(Box<WireframeElement>),
     Glyph(GlyphElement),
     Line(LineElement),
     Particles(ParticlesElement),
     Axes(AxesElement),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Element {
+    /// Whether the element should be rendered. Disabled elements stay in the
+    /// scene file but are skipped when generating vertices.
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            Element::Grid(e) => e.enabled,
+            Element::Wireframe(e) => e.enabled,
+            Element::Glyph(e) => e.enabled,
+            Element::Line(e) => e.enabled,
+            Element::Particles(e) => e.enabled,
+            Element::Axes(e) => e.enabled,
+        }
+    }
+
+    /// How the element's lines combine with what is already drawn.
+    pub fn blend(&self) -> BlendMode {
+        match self {
+            Element::Grid(e) => e.blend,
+            Element::Wireframe(e) => e.blend,
+            Element::Glyph(e) => e.blend,
+            Element::Line(e) => e.blend,
+            Element::Particles(e) => e.blend,
+            Element::Axes(e) => e.blend,
+        }
+    }
+}
+
+/// How an element's lines combine with the image beneath them. `additive` adds light
+/// instead of covering it, so overlapping glowing lines brighten; additive elements draw
+/// after all `alpha` ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BlendMode {
+    #[default]
+    Alpha,
+    Additive,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GridElement {
     #[serde(default = "default_grid_divisions")]
     pub divisions: u32,
     #[serde(default = "default_fade_distance")]
     pub fade_distance: f32,
     #[serde(default = "default_color")]
-    pub color: String,
+    pub color: AnimatedColor,
     #[serde(default = "default_opacity")]
     pub opacity: AnimatedValue,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub blend: BlendMode,
+    #[serde(default)]
+    pub style: GridStyle,
+    #[serde(default)]
+    pub plane: GridPlane,
+    /// Largest in-plane displacement of each line endpoint, in world units, for a
+    /// hand-drawn, imperfect-CRT look. 0 keeps the grid exact.
+    #[serde(default)]
+    pub jitter: f32,
+    /// Picks the jitter pattern; the same seed always wobbles the same way.
+    #[serde(default)]
+    pub seed: u64,
+}
+
+/// Layout of grid lines. Polar draws `divisions` concentric rings plus radial spokes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GridStyle {
+    #[default]
+    Rect,
+    Polar,
+}
+
+/// Plane the grid lies in, centered on the origin: `xz` is a floor, `xy` a back wall,
+/// `yz` a side wall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GridPlane {
+    #[default]
+    Xz,
+    Xy,
+    Yz,
 }
 
 fn default_grid_divisions() -> u32 {
@@ -128,13 +414,17 @@ fn default_grid_divisions() -> u32 {
 fn default_fade_distance() -> f32 {
     50.0
 }
-fn default_color() -> String {
-    "#00ff41".to_string()
+fn default_color() -> AnimatedColor {
+    AnimatedColor::Hex("#00ff41".to_string())
 }
 fn default_opacity() -> AnimatedValue {
     AnimatedValue::Static(0.5)
 }
 
+fn default_enabled() -> bool {
+    true
+}
+
 impl Default for GridElement {
     fn default() -> Self {
         Self {
@@ -142,26 +432,69 @@ impl Default for GridElement {
             fade_distance: default_fade_distance(),
             color: default_color(),
             opacity: AnimatedValue::Static(0.5),
+            enabled: true,
+            blend: BlendMode::Alpha,
+            style: GridStyle::default(),
+            plane: GridPlane::default(),
+            jitter: 0.0,
+            seed: 0,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WireframeElement {
     #[serde(default = "default_geometry")]
     pub geometry: GeometryType,
     #[serde(default)]
-    pub position: [f32; 3],
+    pub position: AnimatedPoint,
     #[serde(default)]
-    pub rotation: AnimatedRotation,
+    pub rotation: Rotation,
+    /// Only used by the Euler form of `rotation`.
+    #[serde(default)]
+    pub rotation_order: RotationOrder,
     #[serde(default = "default_scale")]
     pub scale: Scale,
     #[serde(default = "default_color")]
-    pub color: String,
+    pub color: AnimatedColor,
     #[serde(default = "default_thickness")]
     pub thickness: f32,
     #[serde(default = "default_full_opacity")]
     pub opacity: AnimatedValue,
+    /// Dim edges whose adjacent faces all point away from the camera. Shapes without
+    /// faces, such as custom edge lists, draw every edge as usual.
+    #[serde(default)]
+    pub hidden_line: bool,
+    /// Opacity multiplier for hidden edges; 0 removes them entirely.
+    #[serde(default = "default_hidden_line_opacity")]
+    pub hidden_line_opacity: f32,
+    /// Colors for individual edges by index into the geometry's edge list; the rest
+    /// keep `color`. Opacity still applies.
+    #[serde(default)]
+    pub edge_colors: Vec<EdgeColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat: Option<WireframeRepeat>,
+    #[serde(flatten)]
+    pub params: GeometryParams,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub blend: BlendMode,
+}
+
+/// Sequence in which a wireframe's `rotation` angles are applied, first axis first, so
+/// `xyz` matches Blender's XYZ Euler mode. Defaults to `yxz`: heading, then pitch, then
+/// roll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RotationOrder {
+    Xyz,
+    Xzy,
+    #[default]
+    Yxz,
+    Yzx,
+    Zxy,
+    Zyx,
 }
 
 fn default_geometry() -> GeometryType {
@@ -176,22 +509,118 @@ fn default_thickness() -> f32 {
 fn default_full_opacity() -> AnimatedValue {
     AnimatedValue::Static(1.0)
 }
+fn default_hidden_line_opacity() -> f32 {
+    0.15
+}
 
 impl Default for WireframeElement {
     fn default() -> Self {
         Self {
             geometry: default_geometry(),
-            position: [0.0, 0.0, 0.0],
-            rotation: AnimatedRotation::default(),
+            position: AnimatedPoint::default(),
+            rotation: Rotation::default(),
+            rotation_order: RotationOrder::default(),
             scale: default_scale(),
             color: default_color(),
             thickness: default_thickness(),
             opacity: AnimatedValue::Static(1.0),
+            hidden_line: false,
+            hidden_line_opacity: default_hidden_line_opacity(),
+            edge_colors: Vec::new(),
+            repeat: None,
+            params: GeometryParams::default(),
+            enabled: true,
+            blend: BlendMode::Alpha,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EdgeColor {
+    pub edge: usize,
+    pub color: String,
+}
+
+/// Draw `count` copies of a wireframe, each moved a further `offset` and turned a
+/// further `rotation_step` degrees about its own position. Expressions see the copy
+/// number as `instance`, from 0 to `instance_count - 1`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WireframeRepeat {
+    #[serde(default = "default_repeat_count")]
+    pub count: u32,
+    #[serde(default)]
+    pub offset: [f32; 3],
+    #[serde(default)]
+    pub rotation_step: AnimatedRotation,
+}
+
+fn default_repeat_count() -> u32 {
+    1
+}
+
+/// Subdivision and shape parameters for the generated geometries. These sit
+/// directly on the wireframe element in JSON; each only affects the shapes
+/// that use it.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GeometryParams {
+    /// Sphere longitude segments and cylinder circle segments.
+    #[serde(default = "default_segments")]
+    pub segments: u32,
+    /// Sphere latitude rings.
+    #[serde(default = "default_rings")]
+    pub rings: u32,
+    /// Torus segments around the tube cross-section.
+    #[serde(default = "default_tube_segments")]
+    pub tube_segments: u32,
+    /// Torus segments around the main ring.
+    #[serde(default = "default_radial_segments")]
+    pub radial_segments: u32,
+    #[serde(default = "default_major_radius")]
+    pub major_radius: f32,
+    #[serde(default = "default_minor_radius")]
+    pub minor_radius: f32,
+    /// Draw spokes from the center to the rim on cylinder end caps.
+    #[serde(default)]
+    pub caps: bool,
+}
+
+fn default_segments() -> u32 {
+    16
+}
+fn default_rings() -> u32 {
+    12
+}
+fn default_tube_segments() -> u32 {
+    24
+}
+fn default_radial_segments() -> u32 {
+    12
+}
+fn default_major_radius() -> f32 {
+    1.0
+}
+fn default_minor_radius() -> f32 {
+    0.3
+}
+
+impl Default for GeometryParams {
+    fn default() -> Self {
+        Self {
+            segments: default_segments(),
+            rings: default_rings(),
+            tube_segments: default_tube_segments(),
+            radial_segments: default_radial_segments(),
+            major_radius: default_major_radius(),
+            minor_radius: default_minor_radius(),
+            caps: false,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// Built-in shapes are plain strings (`"cube"`); file-backed and inline
+/// geometry are objects: `{ "obj": { "path": "model.obj" } }` or
+/// `{ "custom": { "vertices": [[0, 0, 0], [1, 0, 0]], "edges": [[0, 1]] } }`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum GeometryType {
     #[default]
@@ -200,10 +629,26 @@ pub enum GeometryType {
     Torus,
     Ico,
     Cylinder,
+    Tetrahedron,
+    Octahedron,
+    Dodecahedron,
+    /// Edges of an OBJ model, normalized to a unit bounding box. Relative paths are
+    /// resolved against the scene file's directory.
+    Obj {
+        path: String,
+        /// Parsed model, filled in by [`Scene::load_assets`]
+        #[serde(skip)]
+        mesh: Option<Arc<GeometryData>>,
+    },
+    /// Explicit vertices joined by index pairs, used as-is
+    Custom {
+        vertices: Vec<[f32; 3]>,
+        edges: Vec<[usize; 2]>,
+    },
 }
 
 /// Animated scale with per-axis expression support.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct AnimatedScale {
     #[serde(default = "default_scale_axis")]
     pub x: AnimatedValue,
@@ -224,12 +669,12 @@ fn default_scale_axis() -> AnimatedValue {
 /// - Non-uniform static: `[2.0, 1.0, 2.0]`
 /// - Uniform expression: `"t * 4 + 1"`
 /// - Per-axis animated: `{ "x": "1 + sin(t * PI)", "y": 1.0, "z": 1.0 }`
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum Scale {
     // Order matters for serde untagged: objects first, then strings, then arrays, then numbers
     PerAxis(AnimatedScale),
-    UniformExpression(String),
+    UniformExpression(super::CompiledExpression),
     NonUniform([f32; 3]),
     Uniform(f32),
 }
@@ -247,7 +692,7 @@ impl Scale {
             Scale::Uniform(s) => [*s, *s, *s],
             Scale::NonUniform(v) => *v,
             Scale::UniformExpression(expr) => {
-                let s = super::evaluate_expression(expr, ctx).unwrap_or(1.0);
+                let s = expr.evaluate(ctx).unwrap_or(1.0);
                 [s, s, s]
             }
             Scale::PerAxis(animated) => [
@@ -259,7 +704,108 @@ impl Scale {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// A point in space (camera and element positions), either fixed or animated per axis:
+/// - Static: `[0, 2, 10]`
+/// - Per-axis: `{ "x": "sin(t * TAU) * 10", "y": 5, "z": "cos(t * TAU) * 10" }`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum AnimatedPoint {
+    PerAxis(Box<AnimatedAxes>),
+    Static([f32; 3]),
+}
+
+impl Default for AnimatedPoint {
+    fn default() -> Self {
+        AnimatedPoint::Static([0.0, 0.0, 0.0])
+    }
+}
+
+impl AnimatedPoint {
+    /// Evaluate the point at the given frame context.
+    pub fn evaluate(&self, ctx: &super::ExpressionContext) -> [f32; 3] {
+        match self {
+            AnimatedPoint::Static(v) => *v,
+            AnimatedPoint::PerAxis(animated) => [
+                animated.x.evaluate(ctx),
+                animated.y.evaluate(ctx),
+                animated.z.evaluate(ctx),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+pub struct AnimatedAxes {
+    #[serde(default)]
+    pub x: AnimatedValue,
+    #[serde(default)]
+    pub y: AnimatedValue,
+    #[serde(default)]
+    pub z: AnimatedValue,
+}
+
+/// An element color, either a fixed hex string or animated per channel:
+/// - Static: `"#00ff41"`
+/// - Per-channel, 0.0 to 1.0: `{ "r": 0, "g": 1, "b": "0.5 + sin(t * TAU) * 0.5" }`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum AnimatedColor {
+    Hex(String),
+    Channels(Box<AnimatedRgb>),
+}
+
+impl AnimatedColor {
+    /// Evaluate the color at the given frame context; `None` for an unparseable hex string.
+    pub fn evaluate(&self, ctx: &super::ExpressionContext) -> Option<[f32; 4]> {
+        match self {
+            AnimatedColor::Hex(hex) => parse_color(hex),
+            AnimatedColor::Channels(rgb) => Some([
+                rgb.r.evaluate(ctx).clamp(0.0, 1.0),
+                rgb.g.evaluate(ctx).clamp(0.0, 1.0),
+                rgb.b.evaluate(ctx).clamp(0.0, 1.0),
+                1.0,
+            ]),
+        }
+    }
+}
+
+impl From<&str> for AnimatedColor {
+    fn from(hex: &str) -> Self {
+        AnimatedColor::Hex(hex.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+pub struct AnimatedRgb {
+    #[serde(default)]
+    pub r: AnimatedValue,
+    #[serde(default)]
+    pub g: AnimatedValue,
+    #[serde(default)]
+    pub b: AnimatedValue,
+}
+
+/// Wireframe orientation: Euler angles in degrees, or a single turn of `angle` degrees
+/// about `axis`, e.g. `{ "axis": [1, 1, 0], "angle": "t * 360" }`, which tumbles
+/// smoothly around any axis without gimbal lock.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Rotation {
+    // Listed first because every Euler field defaults, so that form accepts any object
+    AxisAngle { axis: [f32; 3], angle: AnimatedValue },
+    Euler(AnimatedRotation),
+}
+
+impl Default for Rotation {
+    fn default() -> Self {
+        Rotation::Euler(AnimatedRotation::default())
+    }
+}
+
+/// Unknown fields are rejected so a malformed axis-angle such as `{ "axis": [0, 1, 0] }`
+/// fails instead of falling through to an identity Euler rotation.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(deny_unknown_fields)]
 pub struct AnimatedRotation {
     #[serde(default)]
     pub x: AnimatedValue,
@@ -269,11 +815,11 @@ pub struct AnimatedRotation {
     pub z: AnimatedValue,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum AnimatedValue {
     Static(f32),
-    Expression(String),
+    Expression(super::CompiledExpression),
 }
 
 impl Default for AnimatedValue {
@@ -286,31 +832,91 @@ impl AnimatedValue {
     pub fn evaluate(&self, ctx: &super::ExpressionContext) -> f32 {
         match self {
             AnimatedValue::Static(v) => *v,
-            AnimatedValue::Expression(expr) => super::evaluate_expression(expr, ctx).unwrap_or(0.0),
+            AnimatedValue::Expression(expr) => expr.evaluate(ctx).unwrap_or(0.0),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GlyphElement {
     pub text: String,
     #[serde(default = "default_font_size")]
     pub font_size: f32,
     #[serde(default)]
-    pub position: [f32; 3],
+    pub position: AnimatedPoint,
     #[serde(default = "default_color")]
-    pub color: String,
+    pub color: AnimatedColor,
     #[serde(default)]
     pub animation: GlyphAnimation,
     #[serde(default = "default_full_opacity")]
     pub opacity: AnimatedValue,
+    /// Line segments per curved stroke; 1 keeps the low-poly letterforms.
+    #[serde(default = "default_curve_segments")]
+    pub curve_segments: u32,
+    /// Distance between lines of `\n`-separated text, in multiples of `font_size`.
+    #[serde(default = "default_line_spacing")]
+    pub line_spacing: f32,
+    #[serde(default)]
+    pub align: GlyphAlign,
+    /// Per-character color ramp along the text, replacing `color` when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gradient: Option<GlyphGradient>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub blend: BlendMode,
+    /// Lay the text out in the camera's screen plane so it stays legible from any angle.
+    #[serde(default)]
+    pub billboard: bool,
 }
 
 fn default_font_size() -> f32 {
     1.0
 }
+fn default_curve_segments() -> u32 {
+    1
+}
+fn default_line_spacing() -> f32 {
+    1.4
+}
+
+impl Default for GlyphElement {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            font_size: default_font_size(),
+            position: AnimatedPoint::default(),
+            color: default_color(),
+            animation: GlyphAnimation::default(),
+            opacity: default_full_opacity(),
+            curve_segments: default_curve_segments(),
+            line_spacing: default_line_spacing(),
+            align: GlyphAlign::default(),
+            gradient: None,
+            enabled: true,
+            blend: BlendMode::Alpha,
+            billboard: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GlyphGradient {
+    pub from: String,
+    pub to: String,
+}
+
+/// Which point of each text line sits at `position[0]`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GlyphAlign {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum GlyphAnimation {
     #[default]
@@ -319,7 +925,7 @@ pub enum GlyphAnimation {
     Flicker,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LineElement {
     pub points: Vec<[f32; 3]>,
     #[serde(default)]
@@ -329,31 +935,90 @@ pub struct LineElement {
     #[serde(default = "default_glow")]
     pub glow: f32,
     #[serde(default = "default_color")]
-    pub color: String,
+    pub color: AnimatedColor,
     #[serde(default = "default_full_opacity")]
     pub opacity: AnimatedValue,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub blend: BlendMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dash: Option<LineDash>,
+    /// Fraction of the path length revealed from the first point, e.g. `"t"` to draw on.
+    #[serde(default = "default_full_opacity")]
+    pub draw: AnimatedValue,
+    /// Straight sub-segments per span of a Catmull-Rom spline through `points`; 0 keeps
+    /// the exact polyline.
+    #[serde(default)]
+    pub smooth_segments: u32,
+    /// One color per point, blended along each segment; overrides `color` when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub colors: Option<Vec<String>>,
 }
 
 fn default_glow() -> f32 {
     0.5
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Dash pattern in world units, stretched slightly so the path holds whole dashes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub struct LineDash {
+    pub on: f32,
+    pub off: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ParticlesElement {
     #[serde(default = "default_particle_count")]
     pub count: u32,
     #[serde(default = "default_bounds")]
     pub bounds: [f32; 3],
+    /// On-screen extent of each particle in pixels, whatever its distance from the
+    /// camera.
     #[serde(default = "default_particle_size")]
     pub size: f32,
     #[serde(default = "default_depth_fade")]
     pub depth_fade: bool,
     #[serde(default = "default_color")]
-    pub color: String,
+    pub color: AnimatedColor,
     #[serde(default = "default_full_opacity")]
     pub opacity: AnimatedValue,
+    /// Each particle is placed from `hash(seed, index)`, so changing `count` keeps the
+    /// existing particles where they are. 0 picks a fixed default seed.
     #[serde(default)]
     pub seed: u64,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub blend: BlendMode,
+    #[serde(default)]
+    pub shape: ParticleShape,
+    #[serde(default)]
+    pub distribution: ParticleDistribution,
+}
+
+/// Volume particles are scattered in. `box` spans `bounds`; `sphere` fills a ball and
+/// `disk` a flat XZ circle, both of radius `bounds[0]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ParticleDistribution {
+    #[default]
+    Box,
+    Sphere,
+    Disk,
+}
+
+/// Line pattern drawn at each particle, in the camera's screen plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ParticleShape {
+    #[default]
+    Cross,
+    /// Tiny octagon that reads as a round point
+    Dot,
+    Square,
+    /// Asterisk of three crossing lines
+    Star,
 }
 
 fn default_particle_count() -> u32 {
@@ -369,25 +1034,41 @@ fn default_depth_fade() -> bool {
     true
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AxesElement {
     #[serde(default = "default_axis_length")]
     pub length: f32,
     #[serde(default)]
     pub colors: AxisColors,
     #[serde(default)]
-    pub position: [f32; 3],
+    pub position: AnimatedPoint,
     #[serde(default = "default_thickness")]
     pub thickness: f32,
     #[serde(default = "default_full_opacity")]
     pub opacity: AnimatedValue,
+    /// Smallest arrowhead size in world units.
+    #[serde(default = "default_arrow_min")]
+    pub arrow_min: f32,
+    /// Largest arrowhead size in world units.
+    #[serde(default = "default_arrow_max")]
+    pub arrow_max: f32,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub blend: BlendMode,
 }
 
 fn default_axis_length() -> f32 {
     1.0
 }
+fn default_arrow_min() -> f32 {
+    0.05
+}
+fn default_arrow_max() -> f32 {
+    0.5
+}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AxisColors {
     #[serde(default = "default_x_color")]
     pub x: String,
@@ -417,28 +1098,114 @@ impl Default for AxisColors {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PostProcessing {
     #[serde(default)]
     pub bloom: f32,
+    /// Brightness (0-1) a pixel must exceed to contribute to bloom; 0 blooms everything.
+    #[serde(default)]
+    pub bloom_threshold: f32,
     #[serde(default)]
     pub scanlines: Option<Scanlines>,
     #[serde(default)]
     pub chromatic_aberration: f32,
+    /// Direction of the RGB split in degrees counterclockwise; 0 fringes horizontally.
+    #[serde(default)]
+    pub chromatic_aberration_angle: f32,
     #[serde(default)]
     pub noise: f32,
+    /// Offsets the film grain pattern so it is reproducible but distinct per scene.
+    /// Defaults to 0; the grain still moves with `t` for any seed.
+    #[serde(default)]
+    pub noise_seed: f32,
     #[serde(default)]
     pub vignette: f32,
+    /// 0 follows the canvas aspect as an oval; 1 is a true circle on any canvas.
+    #[serde(default)]
+    pub vignette_roundness: f32,
+    /// Falloff width: above 1 darkens further in from the edges, below 1 only near them.
+    #[serde(default = "default_grading")]
+    pub vignette_smoothness: f32,
     #[serde(default)]
     pub crt_curvature: f32,
+    /// Lens warp independent of `crt_curvature`: positive is barrel, negative pincushion.
+    #[serde(default)]
+    pub lens_distortion: f32,
+    /// Strength of an aperture-grille mask of vertical R, G, B phosphor stripes; 0 is off.
+    #[serde(default)]
+    pub dot_mask: f32,
+    /// Width in pixels of each phosphor stripe, so one triad spans 3x this.
+    #[serde(default = "default_dot_mask_scale")]
+    pub dot_mask_scale: f32,
+    /// Chance and strength of a frame shifting horizontal bands sideways with a color
+    /// split. Seeded by the frame's `t`, so a loop glitches the same way every time.
+    #[serde(default)]
+    pub glitch: f32,
+    /// Fraction of the previous frame's output kept each frame, leaving phosphor trails.
+    #[serde(default)]
+    pub persistence: f32,
+    /// Sample in NxN pixel blocks for a chunky retro look; 1 is off.
+    #[serde(default = "default_pixelate")]
+    pub pixelate: u32,
+    /// Color multiplied over the final image; white leaves it unchanged.
+    #[serde(default = "default_tint")]
+    pub tint: String,
+    #[serde(default = "default_grading")]
+    pub gamma: f32,
+    /// 0 is grayscale, 1 unchanged, above 1 more vivid.
+    #[serde(default = "default_grading")]
+    pub saturation: f32,
+}
+
+fn default_pixelate() -> u32 {
+    1
+}
+fn default_dot_mask_scale() -> f32 {
+    1.0
+}
+fn default_tint() -> String {
+    "#ffffff".to_string()
+}
+fn default_grading() -> f32 {
+    1.0
+}
+
+impl Default for PostProcessing {
+    fn default() -> Self {
+        Self {
+            bloom: 0.0,
+            bloom_threshold: 0.0,
+            scanlines: None,
+            chromatic_aberration: 0.0,
+            chromatic_aberration_angle: 0.0,
+            noise: 0.0,
+            noise_seed: 0.0,
+            vignette: 0.0,
+            vignette_roundness: 0.0,
+            vignette_smoothness: default_grading(),
+            crt_curvature: 0.0,
+            lens_distortion: 0.0,
+            dot_mask: 0.0,
+            dot_mask_scale: default_dot_mask_scale(),
+            glitch: 0.0,
+            persistence: 0.0,
+            pixelate: default_pixelate(),
+            tint: default_tint(),
+            gamma: default_grading(),
+            saturation: default_grading(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Scanlines {
     #[serde(default = "default_scanline_intensity")]
     pub intensity: f32,
     #[serde(default = "default_scanline_count")]
     pub count: u32,
+    /// Scanline periods the bands roll over the animation; whole numbers loop seamlessly.
+    #[serde(default)]
+    pub scroll_speed: f32,
 }
 
 fn default_scanline_intensity() -> f32 {
@@ -448,23 +1215,197 @@ fn default_scanline_count() -> u32 {
     300
 }
 
+/// Parse a CSS color name such as `"cyan"` or, failing that, a hex color. The `#` is
+/// optional, as it always has been; a leading `#` skips the name table.
+pub fn parse_color(color: &str) -> Option<[f32; 4]> {
+    if color.starts_with('#') {
+        return parse_hex_color(color);
+    }
+    super::colors::named_color(color).or_else(|| parse_hex_color(color))
+}
+
+/// Parse `#RGB`, `#RRGGBB`, or `#RRGGBBAA` into RGBA; alpha is 1.0 unless given.
+/// The `#` may be left off the long forms.
 pub fn parse_hex_color(hex: &str) -> Option<[f32; 4]> {
+    let prefixed = hex.starts_with('#');
     let hex = hex.trim_start_matches('#');
-    if hex.len() != 6 {
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
         return None;
     }
 
-    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
-    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
-    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    // Expand the #RGB shorthand by doubling each digit. It needs its `#`, so short
+    // words like "bad" stay invalid.
+    let expanded: String;
+    let hex = if hex.len() == 3 && prefixed {
+        expanded = hex.chars().flat_map(|c| [c, c]).collect();
+        expanded.as_str()
+    } else {
+        hex
+    };
+    if hex.len() != 6 && hex.len() != 8 {
+        return None;
+    }
 
-    Some([r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, 1.0])
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let r = channel(0)?;
+    let g = channel(2)?;
+    let b = channel(4)?;
+    let a = if hex.len() == 8 { channel(6)? } else { 255 };
+
+    Some([r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, a as f32 / 255.0])
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_effective_duration_rounds_up() {
+        let scene: Scene = serde_json::from_str(r#"{ "canvas": {}, "duration": 1.99, "fps": 30 }"#)
+            .unwrap();
+        assert_eq!(scene.total_frames(), 60);
+        assert!((scene.effective_duration() - 2.0).abs() < 1e-6);
+
+        let exact: Scene =
+            serde_json::from_str(r#"{ "canvas": {}, "duration": 2.0, "fps": 30 }"#).unwrap();
+        assert!((exact.effective_duration() - exact.duration).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_repeats_from_loop_and_loop_count() {
+        let parse = |json: &str| serde_json::from_str::<Scene>(json).unwrap().repeats();
+        assert_eq!(parse(r#"{ "canvas": {} }"#), Some(0));
+        assert_eq!(parse(r#"{ "canvas": {}, "loop": false }"#), None);
+        assert_eq!(parse(r#"{ "canvas": {}, "loop": false, "loop_count": 3 }"#), Some(3));
+        assert_eq!(parse(r#"{ "canvas": {}, "loop_count": 0 }"#), Some(0));
+    }
+
+    #[test]
+    fn test_element_enabled_default() {
+        let element: Element = serde_json::from_str(r#"{ "type": "axes" }"#).unwrap();
+        assert!(element.is_enabled());
+
+        let element: Element =
+            serde_json::from_str(r#"{ "type": "wireframe", "enabled": false }"#).unwrap();
+        assert!(!element.is_enabled());
+    }
+
+    #[test]
+    fn test_element_blend_default() {
+        let element: Element = serde_json::from_str(r#"{ "type": "grid" }"#).unwrap();
+        assert_eq!(element.blend(), BlendMode::Alpha);
+
+        let element: Element =
+            serde_json::from_str(r#"{ "type": "axes", "blend": "additive" }"#).unwrap();
+        assert_eq!(element.blend(), BlendMode::Additive);
+    }
+
+    #[test]
+    fn test_canvas_hdr_defaults_off() {
+        let canvas: Canvas = serde_json::from_str(r#"{ "width": 320 }"#).unwrap();
+        assert!(!canvas.hdr);
+
+        let canvas: Canvas = serde_json::from_str(r#"{ "hdr": true }"#).unwrap();
+        assert!(canvas.hdr);
+    }
+
+    #[test]
+    fn test_position_static_and_animated() {
+        let element: WireframeElement =
+            serde_json::from_str(r#"{ "position": [1, 2, 3] }"#).unwrap();
+        let ctx = super::super::ExpressionContext::new(29, 30);
+        assert_eq!(element.position.evaluate(&ctx), [1.0, 2.0, 3.0]);
+
+        let element: GlyphElement =
+            serde_json::from_str(r#"{ "text": "HI", "position": { "x": "t * 3", "z": -1 } }"#)
+                .unwrap();
+        assert_eq!(element.position.evaluate(&ctx), [3.0, 0.0, -1.0]);
+    }
+
+    #[test]
+    fn test_rotation_axis_angle_and_euler() {
+        let element: WireframeElement =
+            serde_json::from_str(r#"{ "rotation": { "axis": [1, 1, 0], "angle": "t * 360" } }"#)
+                .unwrap();
+        assert!(matches!(element.rotation, Rotation::AxisAngle { axis: [1.0, 1.0, 0.0], .. }));
+
+        let element: WireframeElement =
+            serde_json::from_str(r#"{ "rotation": { "y": "t * 360" } }"#).unwrap();
+        assert!(matches!(element.rotation, Rotation::Euler(_)));
+    }
+
+    #[test]
+    fn test_rotation_rejects_malformed_axis_angle() {
+        for rotation in [
+            r#"{ "axis": [0, 1, 0] }"#,
+            r#"{ "axis": [0, 1, 0], "angel": "t * 360" }"#,
+            r#"{ "axis": [0, 1], "angle": 90 }"#,
+            r#"{ "y": 90, "angle": 45 }"#,
+        ] {
+            let json = format!(r#"{{ "rotation": {} }}"#, rotation);
+            assert!(
+                serde_json::from_str::<WireframeElement>(&json).is_err(),
+                "{} should not parse",
+                rotation
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_hex_color_lengths() {
+        assert_eq!(parse_hex_color("#00ff00"), Some([0.0, 1.0, 0.0, 1.0]));
+        assert_eq!(parse_hex_color("#0f0"), Some([0.0, 1.0, 0.0, 1.0]));
+        assert_eq!(parse_hex_color("#00ff0000"), Some([0.0, 1.0, 0.0, 0.0]));
+        assert_eq!(parse_hex_color("#ffffff80").map(|c| c[3]), Some(128.0 / 255.0));
+        assert_eq!(parse_hex_color("#00ff0"), None);
+        assert_eq!(parse_hex_color("#0f0f"), None);
+        assert_eq!(parse_hex_color("#ggg"), None);
+        assert_eq!(parse_hex_color("#ééé"), None);
+    }
+
+    #[test]
+    fn test_background_variants() {
+        let canvas: Canvas = serde_json::from_str(r##"{ "background": "#ff0000" }"##).unwrap();
+        assert_eq!(canvas.background.base_color(), Some([1.0, 0.0, 0.0, 1.0]));
+
+        let canvas: Canvas = serde_json::from_str(
+            r##"{ "background": { "gradient": { "from": "#000", "to": "#003" } } }"##,
+        )
+        .unwrap();
+        match &canvas.background {
+            Background::Gradient { gradient } => assert_eq!(gradient.angle, 180.0),
+            _ => panic!("Expected Background::Gradient"),
+        }
+
+        let canvas: Canvas =
+            serde_json::from_str(r#"{ "background": { "image": "bg.png" } }"#).unwrap();
+        assert!(matches!(canvas.background, Background::Image { .. }));
+        assert_eq!(canvas.background.base_color(), None);
+    }
+
+    #[test]
+    fn test_parse_color_names() {
+        assert_eq!(parse_color("cyan"), Some([0.0, 1.0, 1.0, 1.0]));
+        assert_eq!(parse_color("WHITE"), Some([1.0, 1.0, 1.0, 1.0]));
+        assert_eq!(parse_color("#00ff41"), parse_hex_color("#00ff41"));
+        assert_eq!(parse_color("00ff41"), parse_hex_color("#00ff41"));
+        assert_eq!(parse_color("00ff4180"), parse_hex_color("#00ff4180"));
+        assert_eq!(parse_color("fff"), None);
+        assert_eq!(parse_color("#red"), None);
+        assert_eq!(parse_color("chartreuse-ish"), None);
+    }
+
+    #[test]
+    fn test_color_hex_and_channels() {
+        let ctx = super::super::ExpressionContext::new(29, 30);
+        let element: GridElement = serde_json::from_str(r##"{ "color": "#ff0000" }"##).unwrap();
+        assert_eq!(element.color.evaluate(&ctx), Some([1.0, 0.0, 0.0, 1.0]));
+
+        let element: GridElement =
+            serde_json::from_str(r#"{ "color": { "r": "t * 0.5", "g": 2, "b": 0 } }"#).unwrap();
+        assert_eq!(element.color.evaluate(&ctx), Some([0.5, 1.0, 0.0, 1.0]));
+    }
+
     #[test]
     fn test_scale_uniform_evaluate() {
         let scale = Scale::Uniform(2.5);
@@ -481,7 +1422,7 @@ mod tests {
 
     #[test]
     fn test_scale_uniform_expression_evaluate() {
-        let scale = Scale::UniformExpression("t * 4 + 1".to_string());
+        let scale = Scale::UniformExpression("t * 4 + 1".into());
 
         // At frame 0, t = 0, so result = 0 * 4 + 1 = 1
         let ctx_start = super::super::ExpressionContext::new(0, 30);
@@ -495,9 +1436,9 @@ mod tests {
     #[test]
     fn test_scale_per_axis_evaluate() {
         let scale = Scale::PerAxis(AnimatedScale {
-            x: AnimatedValue::Expression("t * 2 + 1".to_string()),
+            x: AnimatedValue::Expression("t * 2 + 1".into()),
             y: AnimatedValue::Static(1.0),
-            z: AnimatedValue::Expression("t * 2 + 1".to_string()),
+            z: AnimatedValue::Expression("t * 2 + 1".into()),
         });
 
         // At t = 0 (frame 0)
@@ -583,4 +1524,54 @@ mod tests {
             _ => panic!("Expected Scale::PerAxis"),
         }
     }
+
+    #[test]
+    fn test_load_assets_resolves_obj_against_base_dir() {
+        let dir = std::env::temp_dir().join(format!("termcad_assets_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("tri.obj"), "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n").unwrap();
+
+        let json = r#"{ "canvas": {}, "duration": 1, "fps": 30, "elements": [
+            { "type": "wireframe", "geometry": { "obj": { "path": "tri.obj" } } }
+        ] }"#;
+        let mut scene: Scene = serde_json::from_str(json).unwrap();
+        scene.load_assets(&dir).unwrap();
+        match &scene.elements[0] {
+            Element::Wireframe(wf) => match &wf.geometry {
+                GeometryType::Obj {
+                    mesh: Some(mesh), ..
+                } => assert_eq!(mesh.edges.len(), 3),
+                _ => panic!("Expected a loaded obj mesh"),
+            },
+            _ => panic!("Expected a wireframe"),
+        }
+
+        let mut missing: Scene = serde_json::from_str(json).unwrap();
+        let err = missing.load_assets(&dir.join("elsewhere")).unwrap_err();
+        assert!(err.to_string().contains("tri.obj"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_assets_decodes_background_against_base_dir() {
+        let dir = std::env::temp_dir().join(format!("termcad_bg_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        image::RgbaImage::new(3, 2).save(dir.join("bg.png")).unwrap();
+
+        let json = r#"{ "canvas": { "background": { "image": "bg.png" } } }"#;
+        let mut scene: Scene = serde_json::from_str(json).unwrap();
+        scene.load_assets(&dir).unwrap();
+        match &scene.canvas.background {
+            Background::Image {
+                pixels: Some(pixels),
+                ..
+            } => assert_eq!(pixels.dimensions(), (3, 2)),
+            _ => panic!("Expected a decoded background image"),
+        }
+
+        let mut missing: Scene = serde_json::from_str(json).unwrap();
+        let err = missing.load_assets(&dir.join("elsewhere")).unwrap_err();
+        assert!(err.to_string().contains("bg.png"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
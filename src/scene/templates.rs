@@ -5,48 +5,89 @@ pub fn spinning_cube() -> Scene {
         canvas: Canvas {
             width: 800,
             height: 600,
-            background: "#0a0a0a".to_string(),
+            background: "#0a0a0a".into(),
+            hdr: false,
+            transparent: false,
+            viewport: None,
         },
         camera: Camera {
-            position: [5.0, 5.0, 5.0],
-            target: [0.0, 0.0, 0.0],
+            position: AnimatedPoint::Static([5.0, 5.0, 5.0]),
+            target: AnimatedPoint::Static([0.0, 0.0, 0.0]),
             fov: 45.0,
+            turntable: None,
         },
         duration: 2.0,
         fps: 30,
         r#loop: true,
+        loop_count: None,
+        playback: Playback::Loop,
         elements: vec![
             Element::Grid(GridElement {
                 divisions: 20,
                 fade_distance: 50.0,
-                color: "#00ff41".to_string(),
+                color: "#00ff41".into(),
                 opacity: AnimatedValue::Static(0.3),
+                enabled: true,
+                blend: BlendMode::Alpha,
+                style: GridStyle::Rect,
+                plane: GridPlane::Xz,
+                jitter: 0.0,
+                seed: 0,
             }),
-            Element::Wireframe(WireframeElement {
+            Element::Wireframe(Box::new(WireframeElement {
                 geometry: GeometryType::Cube,
-                position: [0.0, 0.5, 0.0],
-                rotation: AnimatedRotation {
+                position: AnimatedPoint::Static([0.0, 0.5, 0.0]),
+                rotation: Rotation::Euler(AnimatedRotation {
                     x: AnimatedValue::Static(0.0),
-                    y: AnimatedValue::Expression("t * 360".to_string()),
+                    y: AnimatedValue::Expression("t * 360".into()),
                     z: AnimatedValue::Static(0.0),
-                },
+                }),
+                rotation_order: RotationOrder::Yxz,
                 scale: Scale::Uniform(1.0),
-                color: "#00ff41".to_string(),
+                color: "#00ff41".into(),
                 thickness: 2.0,
                 opacity: AnimatedValue::Static(1.0),
-            }),
+                hidden_line: false,
+                hidden_line_opacity: 0.15,
+                edge_colors: Vec::new(),
+                repeat: None,
+                enabled: true,
+                blend: BlendMode::Alpha,
+                params: GeometryParams::default(),
+            })),
         ],
         post: PostProcessing {
             bloom: 0.3,
+            bloom_threshold: 0.0,
             scanlines: Some(Scanlines {
                 intensity: 0.1,
                 count: 300,
+                scroll_speed: 0.0,
             }),
             chromatic_aberration: 0.002,
+            chromatic_aberration_angle: 0.0,
             noise: 0.02,
+            noise_seed: 0.0,
             vignette: 0.3,
+            vignette_roundness: 0.0,
+            vignette_smoothness: 1.0,
             crt_curvature: 0.0,
+            lens_distortion: 0.0,
+            dot_mask: 0.0,
+            dot_mask_scale: 1.0,
+            glitch: 0.0,
+            persistence: 0.0,
+            pixelate: 1,
+            tint: "#ffffff".to_string(),
+            gamma: 1.0,
+            saturation: 1.0,
         },
+        depth_test: false,
+        depth_bias: 0.0,
+        msaa: 1,
+        max_vertices: 4_000_000,
+        fog: None,
+        sort_transparency: false,
     }
 }
 
@@ -55,42 +96,79 @@ pub fn grid_flythrough() -> Scene {
         canvas: Canvas {
             width: 800,
             height: 600,
-            background: "#0a0a0a".to_string(),
+            background: "#0a0a0a".into(),
+            hdr: false,
+            transparent: false,
+            viewport: None,
         },
         camera: Camera {
-            position: [0.0, 2.0, 10.0],
-            target: [0.0, 0.0, 0.0],
+            position: AnimatedPoint::Static([0.0, 2.0, 10.0]),
+            target: AnimatedPoint::Static([0.0, 0.0, 0.0]),
             fov: 60.0,
+            turntable: None,
         },
         duration: 3.0,
         fps: 30,
         r#loop: true,
+        loop_count: None,
+        playback: Playback::Loop,
         elements: vec![
             Element::Grid(GridElement {
                 divisions: 40,
                 fade_distance: 100.0,
-                color: "#00ff41".to_string(),
+                color: "#00ff41".into(),
                 opacity: AnimatedValue::Static(0.5),
+                enabled: true,
+                blend: BlendMode::Alpha,
+                style: GridStyle::Rect,
+                plane: GridPlane::Xz,
+                jitter: 0.0,
+                seed: 0,
             }),
             Element::Axes(AxesElement {
                 length: 2.0,
                 colors: AxisColors::default(),
-                position: [0.0, 0.0, 0.0],
+                position: AnimatedPoint::Static([0.0, 0.0, 0.0]),
                 thickness: 3.0,
                 opacity: AnimatedValue::Static(1.0),
+                enabled: true,
+                blend: BlendMode::Alpha,
+                arrow_min: 0.05,
+                arrow_max: 0.5,
             }),
         ],
         post: PostProcessing {
             bloom: 0.4,
+            bloom_threshold: 0.0,
             scanlines: Some(Scanlines {
                 intensity: 0.15,
                 count: 400,
+                scroll_speed: 0.0,
             }),
             chromatic_aberration: 0.003,
+            chromatic_aberration_angle: 0.0,
             noise: 0.03,
+            noise_seed: 0.0,
             vignette: 0.4,
+            vignette_roundness: 0.0,
+            vignette_smoothness: 1.0,
             crt_curvature: 0.0,
+            lens_distortion: 0.0,
+            dot_mask: 0.0,
+            dot_mask_scale: 1.0,
+            glitch: 0.0,
+            persistence: 0.0,
+            pixelate: 1,
+            tint: "#ffffff".to_string(),
+            gamma: 1.0,
+            saturation: 1.0,
         },
+        depth_test: false,
+        depth_bias: 0.0,
+        msaa: 1,
+        max_vertices: 4_000_000,
+        fog: None,
+        sort_transparency: false,
     }
 }
 
@@ -99,52 +177,362 @@ pub fn text_terminal() -> Scene {
         canvas: Canvas {
             width: 800,
             height: 600,
-            background: "#0a0a0a".to_string(),
+            background: "#0a0a0a".into(),
+            hdr: false,
+            transparent: false,
+            viewport: None,
         },
         camera: Camera {
-            position: [0.0, 0.0, 5.0],
-            target: [0.0, 0.0, 0.0],
+            position: AnimatedPoint::Static([0.0, 0.0, 5.0]),
+            target: AnimatedPoint::Static([0.0, 0.0, 0.0]),
             fov: 45.0,
+            turntable: None,
         },
         duration: 2.0,
         fps: 30,
         r#loop: true,
+        loop_count: None,
+        playback: Playback::Loop,
         elements: vec![
             Element::Glyph(GlyphElement {
                 text: "SYSTEM ONLINE".to_string(),
                 font_size: 0.5,
-                position: [0.0, 1.0, 0.0],
-                color: "#00ff41".to_string(),
+                position: AnimatedPoint::Static([0.0, 1.0, 0.0]),
+                color: "#00ff41".into(),
                 animation: GlyphAnimation::Type,
                 opacity: AnimatedValue::Static(1.0),
+                enabled: true,
+                blend: BlendMode::Alpha,
+                curve_segments: 1,
+                line_spacing: 1.4,
+                align: GlyphAlign::Center,
+                gradient: None,
+                billboard: false,
             }),
             Element::Glyph(GlyphElement {
                 text: "> READY".to_string(),
                 font_size: 0.3,
-                position: [0.0, 0.0, 0.0],
-                color: "#00ff41".to_string(),
+                position: AnimatedPoint::Static([0.0, 0.0, 0.0]),
+                color: "#00ff41".into(),
                 animation: GlyphAnimation::Flicker,
                 opacity: AnimatedValue::Static(0.8),
+                enabled: true,
+                blend: BlendMode::Alpha,
+                curve_segments: 1,
+                line_spacing: 1.4,
+                align: GlyphAlign::Center,
+                gradient: None,
+                billboard: false,
             }),
             Element::Line(LineElement {
                 points: vec![[-2.0, -1.0, 0.0], [2.0, -1.0, 0.0]],
                 closed: false,
                 thickness: 1.0,
                 glow: 0.5,
-                color: "#00ff41".to_string(),
+                color: "#00ff41".into(),
                 opacity: AnimatedValue::Static(0.5),
+                enabled: true,
+                blend: BlendMode::Alpha,
+                dash: None,
+                draw: AnimatedValue::Static(1.0),
+                smooth_segments: 0,
+                colors: None,
             }),
         ],
         post: PostProcessing {
             bloom: 0.5,
+            bloom_threshold: 0.0,
             scanlines: Some(Scanlines {
                 intensity: 0.2,
                 count: 300,
+                scroll_speed: 0.0,
             }),
             chromatic_aberration: 0.004,
+            chromatic_aberration_angle: 0.0,
             noise: 0.05,
+            noise_seed: 0.0,
             vignette: 0.5,
+            vignette_roundness: 0.0,
+            vignette_smoothness: 1.0,
             crt_curvature: 0.0,
+            lens_distortion: 0.0,
+            dot_mask: 0.0,
+            dot_mask_scale: 1.0,
+            glitch: 0.0,
+            persistence: 0.0,
+            pixelate: 1,
+            tint: "#ffffff".to_string(),
+            gamma: 1.0,
+            saturation: 1.0,
+        },
+        depth_test: false,
+        depth_bias: 0.0,
+        msaa: 1,
+        max_vertices: 4_000_000,
+        fog: None,
+        sort_transparency: false,
+    }
+}
+
+/// Camera position circling the origin once over the animation at the given radius/height.
+fn orbit_position(radius: f32, height: f32) -> AnimatedPoint {
+    AnimatedPoint::PerAxis(Box::new(AnimatedAxes {
+        x: AnimatedValue::Expression(format!("sin(t * TAU) * {}", radius).into()),
+        y: AnimatedValue::Static(height),
+        z: AnimatedValue::Expression(format!("cos(t * TAU) * {}", radius).into()),
+    }))
+}
+
+pub fn spinning_cube_orbit() -> Scene {
+    Scene {
+        canvas: Canvas {
+            width: 800,
+            height: 600,
+            background: "#0a0a0a".into(),
+            hdr: false,
+            transparent: false,
+            viewport: None,
         },
+        camera: Camera {
+            position: orbit_position(7.0, 4.0),
+            target: AnimatedPoint::Static([0.0, 0.5, 0.0]),
+            fov: 45.0,
+            turntable: None,
+        },
+        duration: 3.0,
+        fps: 30,
+        r#loop: true,
+        loop_count: None,
+        playback: Playback::Loop,
+        elements: vec![
+            Element::Grid(GridElement {
+                divisions: 20,
+                fade_distance: 50.0,
+                color: "#00ff41".into(),
+                opacity: AnimatedValue::Static(0.3),
+                enabled: true,
+                blend: BlendMode::Alpha,
+                style: GridStyle::Rect,
+                plane: GridPlane::Xz,
+                jitter: 0.0,
+                seed: 0,
+            }),
+            Element::Wireframe(Box::new(WireframeElement {
+                geometry: GeometryType::Cube,
+                position: AnimatedPoint::Static([0.0, 0.5, 0.0]),
+                rotation: Rotation::default(),
+                rotation_order: RotationOrder::Yxz,
+                scale: Scale::Uniform(1.0),
+                color: "#00ff41".into(),
+                thickness: 2.0,
+                opacity: AnimatedValue::Static(1.0),
+                hidden_line: false,
+                hidden_line_opacity: 0.15,
+                edge_colors: Vec::new(),
+                repeat: None,
+                enabled: true,
+                blend: BlendMode::Alpha,
+                params: GeometryParams::default(),
+            })),
+        ],
+        post: PostProcessing {
+            bloom: 0.3,
+            bloom_threshold: 0.0,
+            scanlines: Some(Scanlines {
+                intensity: 0.1,
+                count: 300,
+                scroll_speed: 0.0,
+            }),
+            chromatic_aberration: 0.002,
+            chromatic_aberration_angle: 0.0,
+            noise: 0.02,
+            noise_seed: 0.0,
+            vignette: 0.3,
+            vignette_roundness: 0.0,
+            vignette_smoothness: 1.0,
+            crt_curvature: 0.0,
+            lens_distortion: 0.0,
+            dot_mask: 0.0,
+            dot_mask_scale: 1.0,
+            glitch: 0.0,
+            persistence: 0.0,
+            pixelate: 1,
+            tint: "#ffffff".to_string(),
+            gamma: 1.0,
+            saturation: 1.0,
+        },
+        depth_test: false,
+        depth_bias: 0.0,
+        msaa: 1,
+        max_vertices: 4_000_000,
+        fog: None,
+        sort_transparency: false,
+    }
+}
+
+pub fn orbit_showcase() -> Scene {
+    Scene {
+        canvas: Canvas {
+            width: 800,
+            height: 600,
+            background: "#0a0a0a".into(),
+            hdr: false,
+            transparent: false,
+            viewport: None,
+        },
+        camera: Camera {
+            position: orbit_position(9.0, 5.0),
+            target: AnimatedPoint::Static([0.0, 1.0, 0.0]),
+            fov: 50.0,
+            turntable: None,
+        },
+        duration: 4.0,
+        fps: 30,
+        r#loop: true,
+        loop_count: None,
+        playback: Playback::Loop,
+        elements: vec![
+            Element::Grid(GridElement {
+                divisions: 8,
+                fade_distance: 20.0,
+                color: "#00ff41".into(),
+                opacity: AnimatedValue::Static(0.3),
+                enabled: true,
+                blend: BlendMode::Alpha,
+                style: GridStyle::Polar,
+                plane: GridPlane::Xz,
+                jitter: 0.0,
+                seed: 0,
+            }),
+            Element::Axes(AxesElement {
+                length: 1.5,
+                colors: AxisColors::default(),
+                position: AnimatedPoint::Static([0.0, 0.0, 0.0]),
+                thickness: 2.0,
+                opacity: AnimatedValue::Static(0.8),
+                enabled: true,
+                blend: BlendMode::Alpha,
+                arrow_min: 0.05,
+                arrow_max: 0.5,
+            }),
+            Element::Wireframe(Box::new(WireframeElement {
+                geometry: GeometryType::Ico,
+                position: AnimatedPoint::Static([0.0, 1.5, 0.0]),
+                rotation: Rotation::Euler(AnimatedRotation {
+                    x: AnimatedValue::Static(0.0),
+                    y: AnimatedValue::Expression("t * 180".into()),
+                    z: AnimatedValue::Static(0.0),
+                }),
+                rotation_order: RotationOrder::Yxz,
+                scale: Scale::Uniform(1.0),
+                color: "#00ffff".into(),
+                thickness: 2.0,
+                opacity: AnimatedValue::Static(1.0),
+                hidden_line: false,
+                hidden_line_opacity: 0.15,
+                edge_colors: Vec::new(),
+                repeat: None,
+                enabled: true,
+                blend: BlendMode::Alpha,
+                params: GeometryParams::default(),
+            })),
+            Element::Line(LineElement {
+                points: vec![
+                    [3.0, 0.2, 0.0],
+                    [0.0, 0.6, 3.0],
+                    [-3.0, 0.2, 0.0],
+                    [0.0, 0.6, -3.0],
+                ],
+                closed: true,
+                thickness: 1.5,
+                glow: 0.5,
+                color: "#00ff41".into(),
+                opacity: AnimatedValue::Static(0.8),
+                enabled: true,
+                blend: BlendMode::Alpha,
+                dash: None,
+                draw: AnimatedValue::Static(1.0),
+                smooth_segments: 8,
+                colors: None,
+            }),
+            Element::Particles(ParticlesElement {
+                count: 150,
+                bounds: [6.0, 6.0, 6.0],
+                size: 2.0,
+                depth_fade: true,
+                color: "#00ff41".into(),
+                opacity: AnimatedValue::Static(0.6),
+                seed: 7,
+                enabled: true,
+                blend: BlendMode::Alpha,
+                shape: ParticleShape::Dot,
+                distribution: ParticleDistribution::Sphere,
+            }),
+            Element::Glyph(GlyphElement {
+                text: "TERMCAD".to_string(),
+                font_size: 0.4,
+                position: AnimatedPoint::Static([0.0, 3.2, 0.0]),
+                color: "#00ff41".into(),
+                animation: GlyphAnimation::None,
+                opacity: AnimatedValue::Static(1.0),
+                enabled: true,
+                blend: BlendMode::Alpha,
+                curve_segments: 1,
+                line_spacing: 1.4,
+                align: GlyphAlign::Center,
+                gradient: None,
+                billboard: true,
+            }),
+        ],
+        post: PostProcessing {
+            bloom: 0.4,
+            bloom_threshold: 0.0,
+            scanlines: Some(Scanlines {
+                intensity: 0.1,
+                count: 300,
+                scroll_speed: 0.0,
+            }),
+            chromatic_aberration: 0.002,
+            chromatic_aberration_angle: 0.0,
+            noise: 0.02,
+            noise_seed: 0.0,
+            vignette: 0.4,
+            vignette_roundness: 0.0,
+            vignette_smoothness: 1.0,
+            crt_curvature: 0.0,
+            lens_distortion: 0.0,
+            dot_mask: 0.0,
+            dot_mask_scale: 1.0,
+            glitch: 0.0,
+            persistence: 0.0,
+            pixelate: 1,
+            tint: "#ffffff".to_string(),
+            gamma: 1.0,
+            saturation: 1.0,
+        },
+        depth_test: false,
+        depth_bias: 0.0,
+        msaa: 1,
+        max_vertices: 4_000_000,
+        fog: None,
+        sort_transparency: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_templates_validate() {
+        for scene in [
+            spinning_cube(),
+            grid_flythrough(),
+            text_terminal(),
+            spinning_cube_orbit(),
+            orbit_showcase(),
+        ] {
+            assert!(scene.validate().is_ok());
+        }
     }
 }
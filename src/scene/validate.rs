@@ -1,4 +1,5 @@
 use super::schema::*;
+use super::{CompiledExpression, ExpressionContext};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -19,9 +20,14 @@ pub enum ValidationError {
     InvalidValue(String),
 }
 
+/// GIF stores extra plays and WebP total plays in 16 bits, so WebP needs one spare
+const MAX_LOOP_COUNT: u32 = u16::MAX as u32 - 1;
+
 pub fn validate_scene(scene: &Scene) -> Result<(), ValidationError> {
+    // Expressions are checked against the frame count the scene will actually render
+    let total_frames = scene.total_frames();
+
     validate_canvas(&scene.canvas)?;
-    validate_camera(&scene.camera)?;
 
     if scene.duration <= 0.0 {
         return Err(ValidationError::InvalidValue(
@@ -35,13 +41,74 @@ pub fn validate_scene(scene: &Scene) -> Result<(), ValidationError> {
         ));
     }
 
+    if scene.loop_count.is_some_and(|count| count > MAX_LOOP_COUNT) {
+        return Err(ValidationError::InvalidValue(format!(
+            "loop_count must be at most {}",
+            MAX_LOOP_COUNT
+        )));
+    }
+
+    // Catches durations like NaN that slip past the sign check but round to no frames
+    if total_frames == 0 {
+        return Err(ValidationError::InvalidValue(format!(
+            "duration * fps must produce at least one frame (got {} * {})",
+            scene.duration, scene.fps
+        )));
+    }
+
+    validate_camera(&scene.camera, total_frames)?;
+
     for (i, element) in scene.elements.iter().enumerate() {
-        validate_element(element)
+        validate_element(element, total_frames)
             .map_err(|e| ValidationError::InvalidElement(format!("Element {}: {}", i, e)))?;
     }
 
+    let vertices: u64 = scene
+        .elements
+        .iter()
+        .filter(|e| e.is_enabled())
+        .map(crate::primitives::estimate_vertices)
+        .fold(0, u64::saturating_add);
+    if vertices > scene.max_vertices {
+        return Err(ValidationError::InvalidValue(format!(
+            "scene would draw about {} vertices per frame, over max_vertices ({}); \
+             reduce counts or subdivisions",
+            vertices, scene.max_vertices
+        )));
+    }
+
     validate_post_processing(&scene.post)?;
 
+    if !(0.0..=0.1).contains(&scene.depth_bias) {
+        return Err(ValidationError::InvalidValue(
+            "depth_bias must be between 0.0 and 0.1".to_string(),
+        ));
+    }
+
+    if !matches!(scene.msaa, 1 | 4) {
+        return Err(ValidationError::InvalidValue(format!(
+            "msaa must be 1 or 4, got {}",
+            scene.msaa
+        )));
+    }
+
+    if let Some(fog) = &scene.fog {
+        validate_fog(fog)?;
+    }
+
+    Ok(())
+}
+
+fn validate_fog(fog: &Fog) -> Result<(), ValidationError> {
+    validate_color(&fog.color)?;
+
+    if fog.near < 0.0 || fog.far <= fog.near {
+        return Err(ValidationError::InvalidValue(format!(
+            "fog needs 0 <= near < far (got near {}, far {})",
+            fog.near, fog.far
+        )));
+    }
+
     Ok(())
 }
 
@@ -58,35 +125,91 @@ fn validate_canvas(canvas: &Canvas) -> Result<(), ValidationError> {
         ));
     }
 
-    validate_color(&canvas.background)?;
+    if let Some(viewport) = &canvas.viewport {
+        validate_viewport(viewport, canvas)?;
+    }
+
+    match &canvas.background {
+        Background::Color(color) => validate_color(color)?,
+        Background::Gradient { gradient } => {
+            validate_color(&gradient.from)?;
+            validate_color(&gradient.to)?;
+            if !gradient.angle.is_finite() {
+                return Err(ValidationError::InvalidValue(
+                    "background gradient angle must be finite".to_string(),
+                ));
+            }
+        }
+        Background::Image {
+            image: path,
+            pixels: None,
+        } => {
+            return Err(ValidationError::InvalidValue(format!(
+                "background image '{}' has not been loaded",
+                path
+            )));
+        }
+        Background::Image { .. } => {}
+    }
 
     Ok(())
 }
 
-fn validate_camera(camera: &Camera) -> Result<(), ValidationError> {
+fn validate_viewport(viewport: &Viewport, canvas: &Canvas) -> Result<(), ValidationError> {
+    if viewport.width == 0 || viewport.height == 0 {
+        return Err(ValidationError::InvalidDimensions(
+            "viewport width and height must be at least 1".to_string(),
+        ));
+    }
+
+    // Widen before adding so huge offsets can't wrap around and pass
+    let right = viewport.x as u64 + viewport.width as u64;
+    let bottom = viewport.y as u64 + viewport.height as u64;
+    if right > canvas.width as u64 || bottom > canvas.height as u64 {
+        return Err(ValidationError::InvalidDimensions(format!(
+            "viewport {}x{} at ({}, {}) extends past the {}x{} canvas",
+            viewport.width, viewport.height, viewport.x, viewport.y, canvas.width, canvas.height
+        )));
+    }
+
+    Ok(())
+}
+
+fn validate_camera(camera: &Camera, total_frames: u32) -> Result<(), ValidationError> {
     if camera.fov <= 0.0 || camera.fov >= 180.0 {
         return Err(ValidationError::InvalidValue(
             "FOV must be between 0 and 180 degrees".to_string(),
         ));
     }
 
+    validate_animated_point(&camera.position, "camera.position", total_frames)?;
+    validate_animated_point(&camera.target, "camera.target", total_frames)?;
+
+    if let Some(turntable) = camera.turntable
+        && (turntable.revolutions == 0.0 || !turntable.revolutions.is_finite())
+    {
+        return Err(ValidationError::InvalidValue(
+            "turntable revolutions must be non-zero".to_string(),
+        ));
+    }
+
     Ok(())
 }
 
-fn validate_element(element: &Element) -> Result<(), ValidationError> {
+fn validate_element(element: &Element, total_frames: u32) -> Result<(), ValidationError> {
     match element {
-        Element::Grid(grid) => validate_grid(grid),
-        Element::Wireframe(wf) => validate_wireframe(wf),
-        Element::Glyph(glyph) => validate_glyph(glyph),
-        Element::Line(line) => validate_line(line),
-        Element::Particles(particles) => validate_particles(particles),
-        Element::Axes(axes) => validate_axes(axes),
+        Element::Grid(grid) => validate_grid(grid, total_frames),
+        Element::Wireframe(wf) => validate_wireframe(wf, total_frames),
+        Element::Glyph(glyph) => validate_glyph(glyph, total_frames),
+        Element::Line(line) => validate_line(line, total_frames),
+        Element::Particles(particles) => validate_particles(particles, total_frames),
+        Element::Axes(axes) => validate_axes(axes, total_frames),
     }
 }
 
-fn validate_grid(grid: &GridElement) -> Result<(), ValidationError> {
-    validate_color(&grid.color)?;
-    validate_opacity(&grid.opacity)?;
+fn validate_grid(grid: &GridElement, total_frames: u32) -> Result<(), ValidationError> {
+    validate_animated_color(&grid.color, total_frames)?;
+    validate_opacity(&grid.opacity, total_frames)?;
 
     if grid.divisions == 0 {
         return Err(ValidationError::InvalidValue(
@@ -100,20 +223,129 @@ fn validate_grid(grid: &GridElement) -> Result<(), ValidationError> {
         ));
     }
 
+    if !(grid.jitter >= 0.0 && grid.jitter.is_finite()) {
+        return Err(ValidationError::InvalidValue(
+            "grid jitter must be zero or positive".to_string(),
+        ));
+    }
+
     Ok(())
 }
 
-fn validate_wireframe(wf: &WireframeElement) -> Result<(), ValidationError> {
-    validate_color(&wf.color)?;
-    validate_opacity(&wf.opacity)?;
+fn validate_wireframe(wf: &WireframeElement, total_frames: u32) -> Result<(), ValidationError> {
+    validate_animated_color(&wf.color, total_frames)?;
+    validate_opacity(&wf.opacity, total_frames)?;
     validate_thickness(wf.thickness)?;
-    validate_animated_rotation(&wf.rotation)?;
-    validate_scale(&wf.scale)?;
+    match &wf.rotation {
+        Rotation::Euler(rotation) => validate_animated_rotation(rotation, total_frames)?,
+        Rotation::AxisAngle { axis, angle } => {
+            if !axis.iter().all(|c| c.is_finite()) || axis.iter().all(|&c| c == 0.0) {
+                return Err(ValidationError::InvalidValue(
+                    "rotation axis must be finite and non-zero".to_string(),
+                ));
+            }
+            validate_animated_value(angle, "rotation.angle", total_frames)?;
+        }
+    }
+    validate_animated_point(&wf.position, "position", total_frames)?;
+    validate_scale(&wf.scale, total_frames)?;
+    validate_geometry_params(&wf.params)?;
+
+    if !(0.0..=1.0).contains(&wf.hidden_line_opacity) {
+        return Err(ValidationError::InvalidValue(
+            "hidden_line_opacity must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+
+    match &wf.geometry {
+        GeometryType::Obj { path, mesh: None } => {
+            return Err(ValidationError::InvalidValue(format!(
+                "obj '{}' has not been loaded",
+                path
+            )));
+        }
+        GeometryType::Custom { vertices, edges } => validate_custom_geometry(vertices, edges)?,
+        _ => {}
+    }
+
+    if let Some(repeat) = &wf.repeat {
+        if repeat.count == 0 {
+            return Err(ValidationError::InvalidValue(
+                "repeat count must be at least 1".to_string(),
+            ));
+        }
+        if !repeat.offset.iter().all(|c| c.is_finite()) {
+            return Err(ValidationError::InvalidValue(
+                "repeat offset must be finite".to_string(),
+            ));
+        }
+        validate_animated_rotation(&repeat.rotation_step, total_frames)?;
+    }
+
+    if !wf.edge_colors.is_empty() {
+        let edge_count = crate::primitives::generate_geometry(&wf.geometry, &wf.params)
+            .edges
+            .len();
+        for accent in &wf.edge_colors {
+            validate_color(&accent.color)?;
+            if accent.edge >= edge_count {
+                return Err(ValidationError::InvalidValue(format!(
+                    "edge_colors: edge {} out of range for {} edges",
+                    accent.edge, edge_count
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_custom_geometry(
+    vertices: &[[f32; 3]],
+    edges: &[[usize; 2]],
+) -> Result<(), ValidationError> {
+    if vertices.len() < 2 || edges.is_empty() {
+        return Err(ValidationError::InvalidValue(
+            "custom geometry needs at least 2 vertices and 1 edge".to_string(),
+        ));
+    }
+
+    if let Some(edge) = edges.iter().find(|e| e.iter().any(|&i| i >= vertices.len())) {
+        return Err(ValidationError::InvalidValue(format!(
+            "custom edge {:?} references a vertex beyond the {} defined",
+            edge,
+            vertices.len()
+        )));
+    }
 
     Ok(())
 }
 
-fn validate_scale(scale: &Scale) -> Result<(), ValidationError> {
+fn validate_geometry_params(params: &GeometryParams) -> Result<(), ValidationError> {
+    for (name, count) in [
+        ("segments", params.segments),
+        ("rings", params.rings),
+        ("tube_segments", params.tube_segments),
+        ("radial_segments", params.radial_segments),
+    ] {
+        if count < 3 {
+            return Err(ValidationError::InvalidValue(format!(
+                "{} must be at least 3",
+                name
+            )));
+        }
+    }
+
+    if params.major_radius <= 0.0 || params.minor_radius <= 0.0 {
+        return Err(ValidationError::InvalidValue(
+            "major_radius and minor_radius must be positive".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_scale(scale: &Scale, total_frames: u32) -> Result<(), ValidationError> {
     match scale {
         Scale::Uniform(s) => {
             if *s <= 0.0 {
@@ -131,23 +363,28 @@ fn validate_scale(scale: &Scale) -> Result<(), ValidationError> {
             }
         }
         Scale::UniformExpression(expr) => {
-            let ctx = super::ExpressionContext::new(0, 30);
-            super::evaluate_expression(expr, &ctx).map_err(|e| {
+            check_expression(expr, total_frames).map_err(|e| {
                 ValidationError::InvalidExpression(format!("scale '{}': {}", expr, e))
             })?;
         }
         Scale::PerAxis(animated) => {
-            validate_animated_value(&animated.x, "scale.x")?;
-            validate_animated_value(&animated.y, "scale.y")?;
-            validate_animated_value(&animated.z, "scale.z")?;
+            validate_animated_value(&animated.x, "scale.x", total_frames)?;
+            validate_animated_value(&animated.y, "scale.y", total_frames)?;
+            validate_animated_value(&animated.z, "scale.z", total_frames)?;
         }
     }
     Ok(())
 }
 
-fn validate_glyph(glyph: &GlyphElement) -> Result<(), ValidationError> {
-    validate_color(&glyph.color)?;
-    validate_opacity(&glyph.opacity)?;
+fn validate_glyph(glyph: &GlyphElement, total_frames: u32) -> Result<(), ValidationError> {
+    validate_animated_color(&glyph.color, total_frames)?;
+    validate_opacity(&glyph.opacity, total_frames)?;
+    validate_animated_point(&glyph.position, "position", total_frames)?;
+
+    if let Some(gradient) = &glyph.gradient {
+        validate_color(&gradient.from)?;
+        validate_color(&gradient.to)?;
+    }
 
     if glyph.text.is_empty() {
         return Err(ValidationError::InvalidValue(
@@ -161,12 +398,24 @@ fn validate_glyph(glyph: &GlyphElement) -> Result<(), ValidationError> {
         ));
     }
 
+    if glyph.curve_segments == 0 || glyph.curve_segments > 32 {
+        return Err(ValidationError::InvalidValue(
+            "curve_segments must be between 1 and 32".to_string(),
+        ));
+    }
+
+    if glyph.line_spacing <= 0.0 {
+        return Err(ValidationError::InvalidValue(
+            "line_spacing must be positive".to_string(),
+        ));
+    }
+
     Ok(())
 }
 
-fn validate_line(line: &LineElement) -> Result<(), ValidationError> {
-    validate_color(&line.color)?;
-    validate_opacity(&line.opacity)?;
+fn validate_line(line: &LineElement, total_frames: u32) -> Result<(), ValidationError> {
+    validate_animated_color(&line.color, total_frames)?;
+    validate_opacity(&line.opacity, total_frames)?;
     validate_thickness(line.thickness)?;
 
     if line.points.len() < 2 {
@@ -181,12 +430,50 @@ fn validate_line(line: &LineElement) -> Result<(), ValidationError> {
         ));
     }
 
+    if let Some(ref colors) = line.colors {
+        if colors.len() != line.points.len() {
+            return Err(ValidationError::InvalidValue(format!(
+                "line colors has {} entries but there are {} points",
+                colors.len(),
+                line.points.len()
+            )));
+        }
+        for color in colors {
+            validate_color(color)?;
+        }
+    }
+
+    validate_animated_value(&line.draw, "draw", total_frames)?;
+    if let AnimatedValue::Static(draw) = line.draw
+        && !(0.0..=1.0).contains(&draw)
+    {
+        return Err(ValidationError::InvalidValue(
+            "draw must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+
+    if let Some(dash) = line.dash {
+        if !(dash.on > 0.0 && dash.on.is_finite()) {
+            return Err(ValidationError::InvalidValue(
+                "dash on length must be positive".to_string(),
+            ));
+        }
+        if !(dash.off >= 0.0 && dash.off.is_finite()) {
+            return Err(ValidationError::InvalidValue(
+                "dash off length must be zero or positive".to_string(),
+            ));
+        }
+    }
+
     Ok(())
 }
 
-fn validate_particles(particles: &ParticlesElement) -> Result<(), ValidationError> {
-    validate_color(&particles.color)?;
-    validate_opacity(&particles.opacity)?;
+fn validate_particles(
+    particles: &ParticlesElement,
+    total_frames: u32,
+) -> Result<(), ValidationError> {
+    validate_animated_color(&particles.color, total_frames)?;
+    validate_opacity(&particles.opacity, total_frames)?;
 
     if particles.count == 0 {
         return Err(ValidationError::InvalidValue(
@@ -203,12 +490,13 @@ fn validate_particles(particles: &ParticlesElement) -> Result<(), ValidationErro
     Ok(())
 }
 
-fn validate_axes(axes: &AxesElement) -> Result<(), ValidationError> {
+fn validate_axes(axes: &AxesElement, total_frames: u32) -> Result<(), ValidationError> {
     validate_color(&axes.colors.x)?;
     validate_color(&axes.colors.y)?;
     validate_color(&axes.colors.z)?;
-    validate_opacity(&axes.opacity)?;
+    validate_opacity(&axes.opacity, total_frames)?;
     validate_thickness(axes.thickness)?;
+    validate_animated_point(&axes.position, "position", total_frames)?;
 
     if axes.length <= 0.0 {
         return Err(ValidationError::InvalidValue(
@@ -216,6 +504,12 @@ fn validate_axes(axes: &AxesElement) -> Result<(), ValidationError> {
         ));
     }
 
+    if axes.arrow_min < 0.0 || axes.arrow_max < axes.arrow_min {
+        return Err(ValidationError::InvalidValue(
+            "arrow_min must be non-negative and no greater than arrow_max".to_string(),
+        ));
+    }
+
     Ok(())
 }
 
@@ -226,30 +520,108 @@ fn validate_post_processing(post: &PostProcessing) -> Result<(), ValidationError
         ));
     }
 
+    if !(0.0..=1.0).contains(&post.bloom_threshold) {
+        return Err(ValidationError::InvalidValue(
+            "bloom_threshold must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+
     if post.chromatic_aberration < 0.0 || post.chromatic_aberration > 0.1 {
         return Err(ValidationError::InvalidValue(
             "chromatic_aberration must be between 0.0 and 0.1".to_string(),
         ));
     }
 
+    if !post.chromatic_aberration_angle.is_finite() {
+        return Err(ValidationError::InvalidValue(
+            "chromatic_aberration_angle must be a finite number".to_string(),
+        ));
+    }
+
     if post.noise < 0.0 || post.noise > 1.0 {
         return Err(ValidationError::InvalidValue(
             "noise must be between 0.0 and 1.0".to_string(),
         ));
     }
 
+    if !post.noise_seed.is_finite() {
+        return Err(ValidationError::InvalidValue(
+            "noise_seed must be a finite number".to_string(),
+        ));
+    }
+
     if post.vignette < 0.0 || post.vignette > 1.0 {
         return Err(ValidationError::InvalidValue(
             "vignette must be between 0.0 and 1.0".to_string(),
         ));
     }
 
+    if !(0.0..=1.0).contains(&post.vignette_roundness) {
+        return Err(ValidationError::InvalidValue(
+            "vignette_roundness must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+
+    if !(post.vignette_smoothness > 0.0 && post.vignette_smoothness.is_finite()) {
+        return Err(ValidationError::InvalidValue(
+            "vignette_smoothness must be positive".to_string(),
+        ));
+    }
+
     if post.crt_curvature < 0.0 || post.crt_curvature > 1.0 {
         return Err(ValidationError::InvalidValue(
             "crt_curvature must be between 0.0 and 1.0".to_string(),
         ));
     }
 
+    if !(-0.5..=0.5).contains(&post.lens_distortion) {
+        return Err(ValidationError::InvalidValue(
+            "lens_distortion must be between -0.5 and 0.5".to_string(),
+        ));
+    }
+
+    if !(0.0..=1.0).contains(&post.dot_mask) {
+        return Err(ValidationError::InvalidValue(
+            "dot_mask must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+
+    if !(post.dot_mask_scale >= 1.0 && post.dot_mask_scale.is_finite()) {
+        return Err(ValidationError::InvalidValue(
+            "dot_mask_scale must be at least 1 pixel".to_string(),
+        ));
+    }
+
+    if !(0.0..=1.0).contains(&post.glitch) {
+        return Err(ValidationError::InvalidValue(
+            "glitch must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+
+    if !(0.0..=1.0).contains(&post.persistence) {
+        return Err(ValidationError::InvalidValue(
+            "persistence must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+
+    if post.pixelate == 0 {
+        return Err(ValidationError::InvalidValue(
+            "pixelate must be at least 1 (1 = off)".to_string(),
+        ));
+    }
+
+    validate_color(&post.tint)?;
+
+    if !(post.gamma > 0.0 && post.gamma.is_finite()) {
+        return Err(ValidationError::InvalidValue("gamma must be positive".to_string()));
+    }
+
+    if !(post.saturation >= 0.0 && post.saturation.is_finite()) {
+        return Err(ValidationError::InvalidValue(
+            "saturation must be zero or positive".to_string(),
+        ));
+    }
+
     if let Some(ref scanlines) = post.scanlines {
         if scanlines.intensity < 0.0 || scanlines.intensity > 1.0 {
             return Err(ValidationError::InvalidValue(
@@ -261,22 +633,41 @@ fn validate_post_processing(post: &PostProcessing) -> Result<(), ValidationError
                 "scanline count must be positive".to_string(),
             ));
         }
+        if !scanlines.scroll_speed.is_finite() {
+            return Err(ValidationError::InvalidValue(
+                "scanline scroll_speed must be finite".to_string(),
+            ));
+        }
     }
 
     Ok(())
 }
 
 fn validate_color(color: &str) -> Result<(), ValidationError> {
-    if parse_hex_color(color).is_none() {
+    if parse_color(color).is_none() {
         return Err(ValidationError::InvalidColor(format!(
-            "'{}' is not a valid hex color (expected #RRGGBB)",
+            "'{}' is not a valid color (expected #RGB, #RRGGBB, #RRGGBBAA, or a CSS name)",
             color
         )));
     }
     Ok(())
 }
 
-fn validate_opacity(opacity: &AnimatedValue) -> Result<(), ValidationError> {
+fn validate_animated_color(
+    color: &AnimatedColor,
+    total_frames: u32,
+) -> Result<(), ValidationError> {
+    match color {
+        AnimatedColor::Hex(hex) => validate_color(hex),
+        AnimatedColor::Channels(rgb) => {
+            validate_animated_value(&rgb.r, "color.r", total_frames)?;
+            validate_animated_value(&rgb.g, "color.g", total_frames)?;
+            validate_animated_value(&rgb.b, "color.b", total_frames)
+        }
+    }
+}
+
+fn validate_opacity(opacity: &AnimatedValue, total_frames: u32) -> Result<(), ValidationError> {
     match opacity {
         AnimatedValue::Static(v) => {
             if *v < 0.0 || *v > 1.0 {
@@ -286,9 +677,7 @@ fn validate_opacity(opacity: &AnimatedValue) -> Result<(), ValidationError> {
             }
         }
         AnimatedValue::Expression(expr) => {
-            // Validate expression syntax by evaluating at t=0
-            let ctx = super::ExpressionContext::new(0, 30);
-            super::evaluate_expression(expr, &ctx).map_err(|e| {
+            check_expression(expr, total_frames).map_err(|e| {
                 ValidationError::InvalidExpression(format!("opacity '{}': {}", expr, e))
             })?;
             // Note: We cannot validate that runtime values stay in 0-1 range,
@@ -307,20 +696,38 @@ fn validate_thickness(thickness: f32) -> Result<(), ValidationError> {
     Ok(())
 }
 
-fn validate_animated_rotation(rotation: &AnimatedRotation) -> Result<(), ValidationError> {
-    validate_animated_value(&rotation.x, "rotation.x")?;
-    validate_animated_value(&rotation.y, "rotation.y")?;
-    validate_animated_value(&rotation.z, "rotation.z")?;
+fn validate_animated_rotation(
+    rotation: &AnimatedRotation,
+    total_frames: u32,
+) -> Result<(), ValidationError> {
+    validate_animated_value(&rotation.x, "rotation.x", total_frames)?;
+    validate_animated_value(&rotation.y, "rotation.y", total_frames)?;
+    validate_animated_value(&rotation.z, "rotation.z", total_frames)?;
+    Ok(())
+}
+
+fn validate_animated_point(
+    point: &AnimatedPoint,
+    name: &str,
+    total_frames: u32,
+) -> Result<(), ValidationError> {
+    if let AnimatedPoint::PerAxis(axes) = point {
+        validate_animated_value(&axes.x, &format!("{}.x", name), total_frames)?;
+        validate_animated_value(&axes.y, &format!("{}.y", name), total_frames)?;
+        validate_animated_value(&axes.z, &format!("{}.z", name), total_frames)?;
+    }
     Ok(())
 }
 
-fn validate_animated_value(value: &AnimatedValue, _name: &str) -> Result<(), ValidationError> {
+fn validate_animated_value(
+    value: &AnimatedValue,
+    _name: &str,
+    total_frames: u32,
+) -> Result<(), ValidationError> {
     match value {
         AnimatedValue::Static(_) => Ok(()),
         AnimatedValue::Expression(expr) => {
-            // Try to evaluate the expression with t=0 to check validity
-            let ctx = super::ExpressionContext::new(0, 30);
-            super::evaluate_expression(expr, &ctx).map_err(|e| {
+            check_expression(expr, total_frames).map_err(|e| {
                 ValidationError::InvalidExpression(format!("'{}': {}", expr, e))
             })?;
             Ok(())
@@ -328,10 +735,34 @@ fn validate_animated_value(value: &AnimatedValue, _name: &str) -> Result<(), Val
     }
 }
 
+/// Points along the timeline, as `t`, where validation evaluates every expression.
+const SAMPLE_TIMES: [f32; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+/// Evaluate an expression at each of `SAMPLE_TIMES`, failing on errors and on NaN or
+/// infinite results that the renderer would otherwise silently replace.
+fn check_expression(expr: &CompiledExpression, total_frames: u32) -> Result<(), String> {
+    let last_frame = total_frames.saturating_sub(1);
+    for t in SAMPLE_TIMES {
+        let frame = (t * last_frame as f32).round() as u32;
+        let ctx = ExpressionContext {
+            t,
+            ..ExpressionContext::new(frame, total_frames)
+        };
+        let value = expr.evaluate(&ctx).map_err(|e| e.to_string())?;
+        if !value.is_finite() {
+            return Err(format!("evaluates to {} at t = {}", value, t));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Frame count used when validating elements outside of a scene
+    const TOTAL_FRAMES: u32 = 30;
+
     // ===========================================
     // Test Helpers
     // ===========================================
@@ -340,15 +771,19 @@ mod tests {
         Canvas {
             width,
             height,
-            background: background.to_string(),
+            background: background.into(),
+            hdr: false,
+            transparent: false,
+            viewport: None,
         }
     }
 
     fn make_camera(fov: f32) -> Camera {
         Camera {
-            position: [5.0, 5.0, 5.0],
-            target: [0.0, 0.0, 0.0],
+            position: AnimatedPoint::Static([5.0, 5.0, 5.0]),
+            target: AnimatedPoint::Static([0.0, 0.0, 0.0]),
             fov,
+            turntable: None,
         }
     }
 
@@ -356,14 +791,20 @@ mod tests {
         GridElement {
             divisions,
             fade_distance,
-            color: color.to_string(),
+            color: color.into(),
             opacity: AnimatedValue::Static(0.5),
+            enabled: true,
+            blend: BlendMode::Alpha,
+            style: GridStyle::Rect,
+            plane: GridPlane::Xz,
+            jitter: 0.0,
+            seed: 0,
         }
     }
 
     fn make_wireframe(color: &str, thickness: f32) -> WireframeElement {
         WireframeElement {
-            color: color.to_string(),
+            color: color.into(),
             thickness,
             ..Default::default()
         }
@@ -373,10 +814,17 @@ mod tests {
         GlyphElement {
             text: text.to_string(),
             font_size,
-            position: [0.0, 0.0, 0.0],
-            color: color.to_string(),
+            position: AnimatedPoint::Static([0.0, 0.0, 0.0]),
+            color: color.into(),
             animation: GlyphAnimation::None,
             opacity: AnimatedValue::Static(1.0),
+            enabled: true,
+            blend: BlendMode::Alpha,
+            curve_segments: 1,
+            line_spacing: 1.4,
+            align: GlyphAlign::Center,
+            gradient: None,
+            billboard: false,
         }
     }
 
@@ -386,8 +834,14 @@ mod tests {
             closed: false,
             thickness,
             glow,
-            color: color.to_string(),
+            color: color.into(),
             opacity: AnimatedValue::Static(1.0),
+            enabled: true,
+            blend: BlendMode::Alpha,
+            dash: None,
+            draw: AnimatedValue::Static(1.0),
+            smooth_segments: 0,
+            colors: None,
         }
     }
 
@@ -397,9 +851,13 @@ mod tests {
             bounds: [10.0, 10.0, 10.0],
             size,
             depth_fade: true,
-            color: color.to_string(),
+            color: color.into(),
             opacity: AnimatedValue::Static(1.0),
+            enabled: true,
+            blend: BlendMode::Alpha,
             seed: 0,
+            shape: ParticleShape::Cross,
+            distribution: ParticleDistribution::Box,
         }
     }
 
@@ -407,19 +865,37 @@ mod tests {
         AxesElement {
             length,
             colors,
-            position: [0.0, 0.0, 0.0],
+            position: AnimatedPoint::Static([0.0, 0.0, 0.0]),
             thickness,
             opacity: AnimatedValue::Static(1.0),
+            enabled: true,
+            blend: BlendMode::Alpha,
+            arrow_min: 0.05,
+            arrow_max: 0.5,
         }
     }
 
     fn make_post(bloom: f32, chromatic_aberration: f32) -> PostProcessing {
         PostProcessing {
             bloom,
+            bloom_threshold: 0.0,
             chromatic_aberration,
+            chromatic_aberration_angle: 0.0,
             noise: 0.0,
+            noise_seed: 0.0,
             vignette: 0.0,
+            vignette_roundness: 0.0,
+            vignette_smoothness: 1.0,
             crt_curvature: 0.0,
+            lens_distortion: 0.0,
+            dot_mask: 0.0,
+            dot_mask_scale: 1.0,
+            glitch: 0.0,
+            persistence: 0.0,
+            pixelate: 1,
+            tint: "#ffffff".to_string(),
+            gamma: 1.0,
+            saturation: 1.0,
             scanlines: None,
         }
     }
@@ -431,8 +907,16 @@ mod tests {
             duration,
             fps,
             r#loop: true,
+            loop_count: None,
+            playback: Playback::Loop,
             elements: vec![],
             post: PostProcessing::default(),
+            depth_test: false,
+            depth_bias: 0.0,
+            msaa: 1,
+            max_vertices: 4_000_000,
+            fog: None,
+            sort_transparency: false,
         }
     }
 
@@ -448,9 +932,22 @@ mod tests {
         assert!(validate_color("#aAbBcC").is_ok());
     }
 
+    #[test]
+    fn test_validate_color_css_names() {
+        assert!(validate_color("red").is_ok());
+        assert!(validate_color("Cyan").is_ok());
+        assert!(validate_color("reddish").is_err());
+    }
+
+    #[test]
+    fn test_validate_color_shorthand_and_alpha() {
+        assert!(validate_color("#FFF").is_ok());
+        assert!(validate_color("#00ff4180").is_ok());
+    }
+
     #[test]
     fn test_validate_color_invalid_short() {
-        let result = validate_color("#FFF");
+        let result = validate_color("#FF");
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidColor(_)) => {}
@@ -458,6 +955,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_line_colors() {
+        let mut line = make_line(
+            vec![[0.0, 0.0, 0.0], [1.0, 1.0, 1.0]],
+            0.5,
+            "#00ff41",
+            2.0,
+        );
+        line.colors = Some(vec!["#ff0000".to_string(), "cyan".to_string()]);
+        assert!(validate_line(&line, TOTAL_FRAMES).is_ok());
+
+        line.colors = Some(vec!["#ff0000".to_string()]);
+        match validate_line(&line, TOTAL_FRAMES) {
+            Err(ValidationError::InvalidValue(msg)) => {
+                assert!(msg.contains("colors"));
+            }
+            _ => panic!("Expected InvalidValue error about colors"),
+        }
+
+        line.colors = Some(vec!["#ff0000".to_string(), "bad".to_string()]);
+        assert!(matches!(
+            validate_line(&line, TOTAL_FRAMES),
+            Err(ValidationError::InvalidColor(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_line_draw() {
+        let mut line = make_line(
+            vec![[0.0, 0.0, 0.0], [1.0, 1.0, 1.0]],
+            0.5,
+            "#00ff41",
+            2.0,
+        );
+        line.draw = AnimatedValue::Expression("t".into());
+        assert!(validate_line(&line, TOTAL_FRAMES).is_ok());
+
+        line.draw = AnimatedValue::Static(1.5);
+        match validate_line(&line, TOTAL_FRAMES) {
+            Err(ValidationError::InvalidValue(msg)) => {
+                assert!(msg.contains("draw"));
+            }
+            _ => panic!("Expected InvalidValue error about draw"),
+        }
+    }
+
+    #[test]
+    fn test_validate_line_dash() {
+        let mut line = make_line(
+            vec![[0.0, 0.0, 0.0], [1.0, 1.0, 1.0]],
+            0.5,
+            "#00ff41",
+            2.0,
+        );
+        line.dash = Some(LineDash { on: 0.2, off: 0.0 });
+        assert!(validate_line(&line, TOTAL_FRAMES).is_ok());
+
+        line.dash = Some(LineDash { on: 0.0, off: 0.1 });
+        match validate_line(&line, TOTAL_FRAMES) {
+            Err(ValidationError::InvalidValue(msg)) => {
+                assert!(msg.contains("dash on"));
+            }
+            _ => panic!("Expected InvalidValue error about dash on length"),
+        }
+
+        line.dash = Some(LineDash { on: 0.2, off: -0.1 });
+        match validate_line(&line, TOTAL_FRAMES) {
+            Err(ValidationError::InvalidValue(msg)) => {
+                assert!(msg.contains("dash off"));
+            }
+            _ => panic!("Expected InvalidValue error about dash off length"),
+        }
+    }
+
     #[test]
     fn test_validate_color_invalid_char() {
         let result = validate_color("#12345G");
@@ -477,7 +1048,7 @@ mod tests {
 
     #[test]
     fn test_validate_color_wrong_length_no_hash() {
-        // 5 chars without hash = invalid (not 6)
+        // Without a hash only the 6 and 8 digit forms are hex, and 5 isn't a color name
         let result = validate_color("12345");
         assert!(result.is_err());
         match result {
@@ -545,6 +1116,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_canvas_viewport() {
+        let with_viewport = |x, y, width, height| Canvas {
+            viewport: Some(Viewport {
+                x,
+                y,
+                width,
+                height,
+            }),
+            ..make_canvas(800, 600, "#000000")
+        };
+
+        assert!(validate_canvas(&with_viewport(0, 0, 800, 600)).is_ok());
+        assert!(validate_canvas(&with_viewport(600, 400, 200, 200)).is_ok());
+        for canvas in [
+            with_viewport(601, 400, 200, 200),
+            with_viewport(0, 0, 0, 100),
+            with_viewport(u32::MAX, 0, 2, 2),
+        ] {
+            match validate_canvas(&canvas) {
+                Err(ValidationError::InvalidDimensions(_)) => {}
+                _ => panic!("Expected InvalidDimensions error"),
+            }
+        }
+    }
+
     #[test]
     fn test_validate_canvas_invalid_color() {
         let canvas = make_canvas(800, 600, "invalid");
@@ -562,19 +1159,19 @@ mod tests {
 
     #[test]
     fn test_validate_camera_valid_fov() {
-        assert!(validate_camera(&make_camera(45.0)).is_ok());
-        assert!(validate_camera(&make_camera(90.0)).is_ok());
+        assert!(validate_camera(&make_camera(45.0), TOTAL_FRAMES).is_ok());
+        assert!(validate_camera(&make_camera(90.0), TOTAL_FRAMES).is_ok());
     }
 
     #[test]
     fn test_validate_camera_fov_boundary() {
-        assert!(validate_camera(&make_camera(0.01)).is_ok());
-        assert!(validate_camera(&make_camera(179.99)).is_ok());
+        assert!(validate_camera(&make_camera(0.01), TOTAL_FRAMES).is_ok());
+        assert!(validate_camera(&make_camera(179.99), TOTAL_FRAMES).is_ok());
     }
 
     #[test]
     fn test_validate_camera_fov_zero() {
-        let result = validate_camera(&make_camera(0.0));
+        let result = validate_camera(&make_camera(0.0), TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(_)) => {}
@@ -584,7 +1181,7 @@ mod tests {
 
     #[test]
     fn test_validate_camera_fov_180() {
-        let result = validate_camera(&make_camera(180.0));
+        let result = validate_camera(&make_camera(180.0), TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(_)) => {}
@@ -594,7 +1191,7 @@ mod tests {
 
     #[test]
     fn test_validate_camera_fov_negative() {
-        let result = validate_camera(&make_camera(-10.0));
+        let result = validate_camera(&make_camera(-10.0), TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(_)) => {}
@@ -602,14 +1199,69 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_camera_turntable() {
+        let mut camera = make_camera(45.0);
+        camera.turntable = Some(Turntable::default());
+        assert!(validate_camera(&camera, TOTAL_FRAMES).is_ok());
+
+        camera.turntable = Some(Turntable {
+            axis: TurntableAxis::X,
+            revolutions: 0.0,
+        });
+        match validate_camera(&camera, TOTAL_FRAMES) {
+            Err(ValidationError::InvalidValue(msg)) => {
+                assert!(msg.contains("revolutions"));
+            }
+            _ => panic!("Expected InvalidValue error about revolutions"),
+        }
+    }
+
+    #[test]
+    fn test_validate_camera_animated_position() {
+        let mut camera = make_camera(45.0);
+        camera.position = AnimatedPoint::PerAxis(Box::new(AnimatedAxes {
+            x: AnimatedValue::Expression("sin(t * TAU) * 10".into()),
+            y: AnimatedValue::Static(5.0),
+            z: AnimatedValue::Expression("cos(t * TAU) * 10".into()),
+        }));
+        assert!(validate_camera(&camera, TOTAL_FRAMES).is_ok());
+
+        camera.target = AnimatedPoint::PerAxis(Box::new(AnimatedAxes {
+            x: AnimatedValue::Expression("sin(t *".into()),
+            ..AnimatedAxes::default()
+        }));
+        assert!(matches!(
+            validate_camera(&camera, TOTAL_FRAMES),
+            Err(ValidationError::InvalidExpression(_))
+        ));
+    }
+
     // ===========================================
     // Scene Timing Validation Tests
     // ===========================================
 
     #[test]
-    fn test_validate_scene_valid_timing() {
-        let scene = make_scene(Canvas::default(), Camera::default(), 2.0, 30);
+    fn test_validate_scene_valid_timing() {
+        let scene = make_scene(Canvas::default(), Camera::default(), 2.0, 30);
+        assert!(validate_scene(&scene).is_ok());
+    }
+
+    #[test]
+    fn test_validate_scene_uses_real_total_frames() {
+        // Only evaluates to a number when total_frames is the scene's 2s * 60fps = 120
+        let mut grid = make_grid(20, 50.0, "#00ff41");
+        grid.opacity = AnimatedValue::Expression("if(total_frames == 120, 0.5, true)".into());
+
+        let mut scene = make_scene(Canvas::default(), Camera::default(), 2.0, 60);
+        scene.elements.push(Element::Grid(grid));
         assert!(validate_scene(&scene).is_ok());
+
+        scene.fps = 30;
+        assert!(matches!(
+            validate_scene(&scene),
+            Err(ValidationError::InvalidElement(_))
+        ));
     }
 
     #[test]
@@ -647,6 +1299,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_scene_requires_a_frame() {
+        let scene = make_scene(Canvas::default(), Camera::default(), f32::NAN, 30);
+        match validate_scene(&scene) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("at least one frame")),
+            _ => panic!("Expected InvalidValue error about the frame count"),
+        }
+    }
+
+    #[test]
+    fn test_validate_scene_loop_count() {
+        let mut scene = make_scene(Canvas::default(), Camera::default(), 1.0, 30);
+        scene.loop_count = Some(3);
+        assert!(validate_scene(&scene).is_ok());
+
+        scene.loop_count = Some(70_000);
+        match validate_scene(&scene) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("loop_count")),
+            _ => panic!("Expected InvalidValue error about loop_count"),
+        }
+    }
+
     #[test]
     fn test_validate_scene_zero_fps() {
         let scene = make_scene(Canvas::default(), Camera::default(), 2.0, 0);
@@ -673,6 +1347,136 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_canvas_background_variants() {
+        let mut canvas = make_canvas(640, 480, "#000000");
+        canvas.background = Background::Gradient {
+            gradient: BackgroundGradient {
+                from: "#000".to_string(),
+                to: "#003".to_string(),
+                angle: 90.0,
+            },
+        };
+        assert!(validate_canvas(&canvas).is_ok());
+
+        canvas.background = Background::Gradient {
+            gradient: BackgroundGradient {
+                from: "#000".to_string(),
+                to: "nope".to_string(),
+                angle: 90.0,
+            },
+        };
+        assert!(matches!(validate_canvas(&canvas), Err(ValidationError::InvalidColor(_))));
+
+        canvas.background = Background::Image {
+            image: "does/not/exist.png".to_string(),
+            pixels: None,
+        };
+        match validate_canvas(&canvas) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("exist.png")),
+            _ => panic!("Expected InvalidValue error about the background image"),
+        }
+    }
+
+    #[test]
+    fn test_validate_scene_msaa() {
+        let mut scene = make_scene(Canvas::default(), Camera::default(), 2.0, 30);
+        scene.msaa = 4;
+        assert!(validate_scene(&scene).is_ok());
+
+        scene.msaa = 2;
+        match validate_scene(&scene) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("msaa")),
+            _ => panic!("Expected InvalidValue error about msaa"),
+        }
+    }
+
+    #[test]
+    fn test_validate_scene_max_vertices() {
+        let mut scene = make_scene(Canvas::default(), Camera::default(), 2.0, 30);
+        scene.elements = vec![Element::Particles(make_particles(10_000_000, 2.0, "#00ff41"))];
+        match validate_scene(&scene) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("max_vertices")),
+            _ => panic!("Expected InvalidValue error about max_vertices"),
+        }
+
+        // Disabled elements draw nothing, and the cap is the scene's to raise
+        if let Element::Particles(particles) = &mut scene.elements[0] {
+            particles.enabled = false;
+        }
+        assert!(validate_scene(&scene).is_ok());
+        if let Element::Particles(particles) = &mut scene.elements[0] {
+            particles.enabled = true;
+        }
+        scene.max_vertices = 50_000_000;
+        assert!(validate_scene(&scene).is_ok());
+    }
+
+    #[test]
+    fn test_validate_scene_max_vertices_saturates() {
+        // A dash period far below the path length saturates the estimate instead of
+        // overflowing it
+        let mut scene = make_scene(Canvas::default(), Camera::default(), 2.0, 30);
+        let mut line = make_line(vec![[0.0, 0.0, 0.0], [1e30, 0.0, 0.0]], 0.0, "#00ff41", 2.0);
+        line.dash = Some(LineDash { on: 1e-30, off: 0.0 });
+        scene.elements = vec![Element::Line(line.clone()), Element::Line(line)];
+        match validate_scene(&scene) {
+            Err(ValidationError::InvalidValue(msg)) => {
+                assert!(msg.contains(&u64::MAX.to_string()))
+            }
+            _ => panic!("Expected InvalidValue error about max_vertices"),
+        }
+    }
+
+    #[test]
+    fn test_validate_scene_fog() {
+        let mut scene = make_scene(Canvas::default(), Camera::default(), 2.0, 30);
+        scene.fog = Some(Fog {
+            color: "#000000".to_string(),
+            near: 2.0,
+            far: 10.0,
+        });
+        assert!(validate_scene(&scene).is_ok());
+
+        for (near, far) in [(-1.0, 10.0), (5.0, 5.0), (8.0, 4.0)] {
+            scene.fog = Some(Fog {
+                color: "#000000".to_string(),
+                near,
+                far,
+            });
+            match validate_scene(&scene) {
+                Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("fog")),
+                _ => panic!("Expected InvalidValue error for fog {}..{}", near, far),
+            }
+        }
+
+        scene.fog = Some(Fog {
+            color: "fog".to_string(),
+            near: 0.0,
+            far: 10.0,
+        });
+        assert!(matches!(
+            validate_scene(&scene),
+            Err(ValidationError::InvalidColor(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_scene_depth_bias() {
+        let mut scene = make_scene(Canvas::default(), Camera::default(), 2.0, 30);
+        scene.depth_test = true;
+        scene.depth_bias = 0.001;
+        assert!(validate_scene(&scene).is_ok());
+
+        scene.depth_bias = -0.001;
+        match validate_scene(&scene) {
+            Err(ValidationError::InvalidValue(msg)) => {
+                assert!(msg.contains("depth_bias"));
+            }
+            _ => panic!("Expected InvalidValue error about depth_bias"),
+        }
+    }
+
     // ===========================================
     // Grid Validation Tests
     // ===========================================
@@ -680,13 +1484,13 @@ mod tests {
     #[test]
     fn test_validate_grid_valid() {
         let grid = make_grid(20, 50.0, "#00ff41");
-        assert!(validate_grid(&grid).is_ok());
+        assert!(validate_grid(&grid, TOTAL_FRAMES).is_ok());
     }
 
     #[test]
     fn test_validate_grid_zero_divisions() {
         let grid = make_grid(0, 50.0, "#00ff41");
-        let result = validate_grid(&grid);
+        let result = validate_grid(&grid, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -699,7 +1503,7 @@ mod tests {
     #[test]
     fn test_validate_grid_zero_fade() {
         let grid = make_grid(20, 0.0, "#00ff41");
-        let result = validate_grid(&grid);
+        let result = validate_grid(&grid, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -712,7 +1516,7 @@ mod tests {
     #[test]
     fn test_validate_grid_negative_fade() {
         let grid = make_grid(20, -10.0, "#00ff41");
-        let result = validate_grid(&grid);
+        let result = validate_grid(&grid, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -725,7 +1529,7 @@ mod tests {
     #[test]
     fn test_validate_grid_invalid_color() {
         let grid = make_grid(20, 50.0, "bad");
-        let result = validate_grid(&grid);
+        let result = validate_grid(&grid, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidColor(_)) => {}
@@ -740,13 +1544,13 @@ mod tests {
     #[test]
     fn test_validate_wireframe_valid() {
         let wf = make_wireframe("#00ff41", 2.0);
-        assert!(validate_wireframe(&wf).is_ok());
+        assert!(validate_wireframe(&wf, TOTAL_FRAMES).is_ok());
     }
 
     #[test]
     fn test_validate_wireframe_zero_thickness() {
         let wf = make_wireframe("#00ff41", 0.0);
-        let result = validate_wireframe(&wf);
+        let result = validate_wireframe(&wf, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -759,8 +1563,11 @@ mod tests {
     #[test]
     fn test_validate_wireframe_invalid_rotation() {
         let mut wf = make_wireframe("#00ff41", 2.0);
-        wf.rotation.y = AnimatedValue::Expression("invalid syntax".to_string());
-        let result = validate_wireframe(&wf);
+        wf.rotation = Rotation::Euler(AnimatedRotation {
+            y: AnimatedValue::Expression("invalid syntax".into()),
+            ..AnimatedRotation::default()
+        });
+        let result = validate_wireframe(&wf, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidExpression(_)) => {}
@@ -768,10 +1575,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_wireframe_axis_angle() {
+        let mut wf = make_wireframe("#00ff41", 2.0);
+        wf.rotation = Rotation::AxisAngle {
+            axis: [1.0, 1.0, 0.0],
+            angle: AnimatedValue::Expression("t * 360".into()),
+        };
+        assert!(validate_wireframe(&wf, TOTAL_FRAMES).is_ok());
+
+        wf.rotation = Rotation::AxisAngle {
+            axis: [0.0, 0.0, 0.0],
+            angle: AnimatedValue::Static(45.0),
+        };
+        match validate_wireframe(&wf, TOTAL_FRAMES) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("axis")),
+            _ => panic!("Expected InvalidValue error about the axis"),
+        }
+    }
+
     #[test]
     fn test_validate_wireframe_invalid_color() {
         let wf = make_wireframe("notacolor", 2.0);
-        let result = validate_wireframe(&wf);
+        let result = validate_wireframe(&wf, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidColor(_)) => {}
@@ -779,6 +1605,106 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_wireframe_subdivisions() {
+        let mut wf = make_wireframe("#00ff41", 2.0);
+        wf.params.segments = 3;
+        assert!(validate_wireframe(&wf, TOTAL_FRAMES).is_ok());
+
+        wf.params.rings = 2;
+        match validate_wireframe(&wf, TOTAL_FRAMES) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("rings")),
+            _ => panic!("Expected InvalidValue error about rings"),
+        }
+    }
+
+    #[test]
+    fn test_validate_wireframe_missing_obj() {
+        let mut wf = make_wireframe("#00ff41", 2.0);
+        wf.geometry = GeometryType::Obj {
+            path: "does/not/exist.obj".to_string(),
+            mesh: None,
+        };
+        match validate_wireframe(&wf, TOTAL_FRAMES) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("exist.obj")),
+            _ => panic!("Expected InvalidValue error about the obj file"),
+        }
+    }
+
+    #[test]
+    fn test_validate_wireframe_custom_geometry() {
+        let mut wf = make_wireframe("#00ff41", 2.0);
+        wf.geometry = GeometryType::Custom {
+            vertices: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]],
+            edges: vec![[0, 1]],
+        };
+        assert!(validate_wireframe(&wf, TOTAL_FRAMES).is_ok());
+
+        wf.geometry = GeometryType::Custom {
+            vertices: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]],
+            edges: vec![[0, 2]],
+        };
+        match validate_wireframe(&wf, TOTAL_FRAMES) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("[0, 2]")),
+            _ => panic!("Expected InvalidValue error about the edge index"),
+        }
+
+        wf.geometry = GeometryType::Custom {
+            vertices: vec![[0.0, 0.0, 0.0]],
+            edges: vec![],
+        };
+        assert!(matches!(
+            validate_wireframe(&wf, TOTAL_FRAMES),
+            Err(ValidationError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_wireframe_repeat() {
+        let mut wf = make_wireframe("#00ff41", 2.0);
+        wf.repeat = Some(WireframeRepeat {
+            count: 4,
+            offset: [0.0, 0.0, -2.0],
+            rotation_step: AnimatedRotation {
+                z: AnimatedValue::Expression("instance * 15".into()),
+                ..AnimatedRotation::default()
+            },
+        });
+        assert!(validate_wireframe(&wf, TOTAL_FRAMES).is_ok());
+
+        wf.repeat.as_mut().unwrap().count = 0;
+        match validate_wireframe(&wf, TOTAL_FRAMES) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("count")),
+            _ => panic!("Expected InvalidValue error about the repeat count"),
+        }
+    }
+
+    #[test]
+    fn test_validate_wireframe_edge_colors() {
+        let mut wf = make_wireframe("#00ff41", 2.0);
+        wf.edge_colors = vec![EdgeColor {
+            edge: 11,
+            color: "#ff0000".to_string(),
+        }];
+        assert!(validate_wireframe(&wf, TOTAL_FRAMES).is_ok());
+
+        // A cube has 12 edges
+        wf.edge_colors[0].edge = 12;
+        match validate_wireframe(&wf, TOTAL_FRAMES) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("edge 12")),
+            _ => panic!("Expected InvalidValue error about the edge index"),
+        }
+
+        wf.edge_colors[0] = EdgeColor {
+            edge: 0,
+            color: "notacolor".to_string(),
+        };
+        assert!(matches!(
+            validate_wireframe(&wf, TOTAL_FRAMES),
+            Err(ValidationError::InvalidColor(_))
+        ));
+    }
+
     // ===========================================
     // Glyph Validation Tests
     // ===========================================
@@ -786,13 +1712,13 @@ mod tests {
     #[test]
     fn test_validate_glyph_valid() {
         let glyph = make_glyph("HELLO", 1.0, "#00ff41");
-        assert!(validate_glyph(&glyph).is_ok());
+        assert!(validate_glyph(&glyph, TOTAL_FRAMES).is_ok());
     }
 
     #[test]
     fn test_validate_glyph_empty_text() {
         let glyph = make_glyph("", 1.0, "#00ff41");
-        let result = validate_glyph(&glyph);
+        let result = validate_glyph(&glyph, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -805,7 +1731,7 @@ mod tests {
     #[test]
     fn test_validate_glyph_zero_font_size() {
         let glyph = make_glyph("HELLO", 0.0, "#00ff41");
-        let result = validate_glyph(&glyph);
+        let result = validate_glyph(&glyph, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -818,7 +1744,7 @@ mod tests {
     #[test]
     fn test_validate_glyph_negative_font_size() {
         let glyph = make_glyph("HELLO", -1.0, "#00ff41");
-        let result = validate_glyph(&glyph);
+        let result = validate_glyph(&glyph, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -828,10 +1754,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_glyph_curve_segments() {
+        let mut glyph = make_glyph("OK", 1.0, "#00ff41");
+        glyph.curve_segments = 8;
+        assert!(validate_glyph(&glyph, TOTAL_FRAMES).is_ok());
+
+        glyph.curve_segments = 0;
+        match validate_glyph(&glyph, TOTAL_FRAMES) {
+            Err(ValidationError::InvalidValue(msg)) => {
+                assert!(msg.contains("curve_segments"));
+            }
+            _ => panic!("Expected InvalidValue error about curve_segments"),
+        }
+    }
+
+    #[test]
+    fn test_validate_glyph_gradient() {
+        let mut glyph = make_glyph("FADE", 1.0, "#00ff41");
+        glyph.gradient = Some(GlyphGradient {
+            from: "#00ff41".to_string(),
+            to: "nope".to_string(),
+        });
+        assert!(matches!(
+            validate_glyph(&glyph, TOTAL_FRAMES),
+            Err(ValidationError::InvalidColor(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_glyph_line_spacing() {
+        let mut glyph = make_glyph("TWO\nLINES", 1.0, "#00ff41");
+        assert!(validate_glyph(&glyph, TOTAL_FRAMES).is_ok());
+
+        glyph.line_spacing = 0.0;
+        match validate_glyph(&glyph, TOTAL_FRAMES) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("line_spacing")),
+            _ => panic!("Expected InvalidValue error about line_spacing"),
+        }
+    }
+
     #[test]
     fn test_validate_glyph_invalid_color() {
         let glyph = make_glyph("HELLO", 1.0, "bad");
-        let result = validate_glyph(&glyph);
+        let result = validate_glyph(&glyph, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidColor(_)) => {}
@@ -851,13 +1817,13 @@ mod tests {
             "#00ff41",
             2.0,
         );
-        assert!(validate_line(&line).is_ok());
+        assert!(validate_line(&line, TOTAL_FRAMES).is_ok());
     }
 
     #[test]
     fn test_validate_line_one_point() {
         let line = make_line(vec![[0.0, 0.0, 0.0]], 0.5, "#00ff41", 2.0);
-        let result = validate_line(&line);
+        let result = validate_line(&line, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -870,7 +1836,7 @@ mod tests {
     #[test]
     fn test_validate_line_zero_points() {
         let line = make_line(vec![], 0.5, "#00ff41", 2.0);
-        let result = validate_line(&line);
+        let result = validate_line(&line, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -889,7 +1855,7 @@ mod tests {
             "#00ff41",
             2.0,
         );
-        assert!(validate_line(&line_zero).is_ok());
+        assert!(validate_line(&line_zero, TOTAL_FRAMES).is_ok());
 
         let line_one = make_line(
             vec![[0.0, 0.0, 0.0], [1.0, 1.0, 1.0]],
@@ -897,7 +1863,7 @@ mod tests {
             "#00ff41",
             2.0,
         );
-        assert!(validate_line(&line_one).is_ok());
+        assert!(validate_line(&line_one, TOTAL_FRAMES).is_ok());
     }
 
     #[test]
@@ -908,7 +1874,7 @@ mod tests {
             "#00ff41",
             2.0,
         );
-        let result = validate_line(&line);
+        let result = validate_line(&line, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -926,7 +1892,7 @@ mod tests {
             "#00ff41",
             2.0,
         );
-        let result = validate_line(&line);
+        let result = validate_line(&line, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -944,7 +1910,7 @@ mod tests {
             "#00ff41",
             0.0,
         );
-        let result = validate_line(&line);
+        let result = validate_line(&line, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -962,7 +1928,7 @@ mod tests {
             "bad",
             2.0,
         );
-        let result = validate_line(&line);
+        let result = validate_line(&line, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidColor(_)) => {}
@@ -977,13 +1943,13 @@ mod tests {
     #[test]
     fn test_validate_particles_valid() {
         let particles = make_particles(100, 2.0, "#00ff41");
-        assert!(validate_particles(&particles).is_ok());
+        assert!(validate_particles(&particles, TOTAL_FRAMES).is_ok());
     }
 
     #[test]
     fn test_validate_particles_zero_count() {
         let particles = make_particles(0, 2.0, "#00ff41");
-        let result = validate_particles(&particles);
+        let result = validate_particles(&particles, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -996,7 +1962,7 @@ mod tests {
     #[test]
     fn test_validate_particles_zero_size() {
         let particles = make_particles(100, 0.0, "#00ff41");
-        let result = validate_particles(&particles);
+        let result = validate_particles(&particles, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -1009,7 +1975,7 @@ mod tests {
     #[test]
     fn test_validate_particles_negative_size() {
         let particles = make_particles(100, -1.0, "#00ff41");
-        let result = validate_particles(&particles);
+        let result = validate_particles(&particles, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -1022,7 +1988,7 @@ mod tests {
     #[test]
     fn test_validate_particles_invalid_color() {
         let particles = make_particles(100, 2.0, "bad");
-        let result = validate_particles(&particles);
+        let result = validate_particles(&particles, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidColor(_)) => {}
@@ -1037,13 +2003,13 @@ mod tests {
     #[test]
     fn test_validate_axes_valid() {
         let axes = make_axes(1.0, 2.0, AxisColors::default());
-        assert!(validate_axes(&axes).is_ok());
+        assert!(validate_axes(&axes, TOTAL_FRAMES).is_ok());
     }
 
     #[test]
     fn test_validate_axes_zero_length() {
         let axes = make_axes(0.0, 2.0, AxisColors::default());
-        let result = validate_axes(&axes);
+        let result = validate_axes(&axes, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -1056,7 +2022,7 @@ mod tests {
     #[test]
     fn test_validate_axes_negative_length() {
         let axes = make_axes(-1.0, 2.0, AxisColors::default());
-        let result = validate_axes(&axes);
+        let result = validate_axes(&axes, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -1069,7 +2035,7 @@ mod tests {
     #[test]
     fn test_validate_axes_zero_thickness() {
         let axes = make_axes(1.0, 0.0, AxisColors::default());
-        let result = validate_axes(&axes);
+        let result = validate_axes(&axes, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidValue(msg)) => {
@@ -1079,6 +2045,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_axes_arrow_range() {
+        let mut axes = make_axes(1.0, 2.0, AxisColors::default());
+        axes.arrow_min = 0.0;
+        axes.arrow_max = 0.0;
+        assert!(validate_axes(&axes, TOTAL_FRAMES).is_ok());
+
+        axes.arrow_min = 0.5;
+        axes.arrow_max = 0.1;
+        let result = validate_axes(&axes, TOTAL_FRAMES);
+        match result {
+            Err(ValidationError::InvalidValue(msg)) => {
+                assert!(msg.contains("arrow_min"));
+            }
+            _ => panic!("Expected InvalidValue error about arrow_min"),
+        }
+
+        axes.arrow_min = -0.1;
+        axes.arrow_max = 0.1;
+        assert!(validate_axes(&axes, TOTAL_FRAMES).is_err());
+    }
+
     #[test]
     fn test_validate_axes_invalid_x_color() {
         let colors = AxisColors {
@@ -1087,7 +2075,7 @@ mod tests {
             z: "#0000ff".to_string(),
         };
         let axes = make_axes(1.0, 2.0, colors);
-        let result = validate_axes(&axes);
+        let result = validate_axes(&axes, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidColor(_)) => {}
@@ -1103,7 +2091,7 @@ mod tests {
             z: "#0000ff".to_string(),
         };
         let axes = make_axes(1.0, 2.0, colors);
-        let result = validate_axes(&axes);
+        let result = validate_axes(&axes, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidColor(_)) => {}
@@ -1119,7 +2107,7 @@ mod tests {
             z: "bad".to_string(),
         };
         let axes = make_axes(1.0, 2.0, colors);
-        let result = validate_axes(&axes);
+        let result = validate_axes(&axes, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidColor(_)) => {}
@@ -1135,13 +2123,28 @@ mod tests {
     fn test_validate_post_valid_all() {
         let post = PostProcessing {
             bloom: 0.5,
+            bloom_threshold: 0.0,
             chromatic_aberration: 0.05,
+            chromatic_aberration_angle: 0.0,
             noise: 0.1,
+            noise_seed: 0.0,
             vignette: 0.3,
+            vignette_roundness: 0.0,
+            vignette_smoothness: 1.0,
             crt_curvature: 0.2,
+            lens_distortion: 0.0,
+            dot_mask: 0.0,
+            dot_mask_scale: 1.0,
+            glitch: 0.0,
+            persistence: 0.0,
+            pixelate: 1,
+            tint: "#ffffff".to_string(),
+            gamma: 1.0,
+            saturation: 1.0,
             scanlines: Some(Scanlines {
                 intensity: 0.1,
                 count: 300,
+                scroll_speed: 0.0,
             }),
         };
         assert!(validate_post_processing(&post).is_ok());
@@ -1224,6 +2227,174 @@ mod tests {
         assert!(validate_post_processing(&post).is_ok());
     }
 
+    #[test]
+    fn test_validate_post_bloom_threshold_range() {
+        let mut post = make_post(0.5, 0.0);
+        post.bloom_threshold = 0.7;
+        assert!(validate_post_processing(&post).is_ok());
+
+        post.bloom_threshold = -0.1;
+        match validate_post_processing(&post) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("bloom_threshold")),
+            _ => panic!("Expected InvalidValue error about bloom_threshold"),
+        }
+    }
+
+    #[test]
+    fn test_validate_post_color_grading() {
+        let mut post = make_post(0.0, 0.0);
+        post.tint = "#ffe0c0".to_string();
+        post.gamma = 2.2;
+        post.saturation = 0.0;
+        assert!(validate_post_processing(&post).is_ok());
+
+        post.gamma = 0.0;
+        assert!(matches!(
+            validate_post_processing(&post),
+            Err(ValidationError::InvalidValue(msg)) if msg.contains("gamma")
+        ));
+
+        post.gamma = 1.0;
+        post.saturation = -1.0;
+        assert!(matches!(
+            validate_post_processing(&post),
+            Err(ValidationError::InvalidValue(msg)) if msg.contains("saturation")
+        ));
+
+        post.saturation = 1.0;
+        post.tint = "warm".to_string();
+        assert!(matches!(
+            validate_post_processing(&post),
+            Err(ValidationError::InvalidColor(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_post_vignette_shape() {
+        let mut post = make_post(0.0, 0.0);
+        post.vignette = 0.5;
+        post.vignette_roundness = 1.0;
+        post.vignette_smoothness = 2.5;
+        assert!(validate_post_processing(&post).is_ok());
+
+        post.vignette_roundness = 1.5;
+        assert!(matches!(
+            validate_post_processing(&post),
+            Err(ValidationError::InvalidValue(msg)) if msg.contains("vignette_roundness")
+        ));
+
+        post.vignette_roundness = 0.0;
+        post.vignette_smoothness = 0.0;
+        assert!(matches!(
+            validate_post_processing(&post),
+            Err(ValidationError::InvalidValue(msg)) if msg.contains("vignette_smoothness")
+        ));
+    }
+
+    #[test]
+    fn test_validate_post_lens_distortion() {
+        let mut post = make_post(0.0, 0.0);
+        for distortion in [-0.5, 0.0, 0.5] {
+            post.lens_distortion = distortion;
+            assert!(validate_post_processing(&post).is_ok());
+        }
+
+        for distortion in [-0.6, 0.6, f32::NAN] {
+            post.lens_distortion = distortion;
+            assert!(matches!(
+                validate_post_processing(&post),
+                Err(ValidationError::InvalidValue(msg)) if msg.contains("lens_distortion")
+            ));
+        }
+    }
+
+    #[test]
+    fn test_validate_post_dot_mask() {
+        let mut post = make_post(0.0, 0.0);
+        post.dot_mask = 0.6;
+        post.dot_mask_scale = 2.0;
+        assert!(validate_post_processing(&post).is_ok());
+
+        post.dot_mask = 1.5;
+        assert!(matches!(
+            validate_post_processing(&post),
+            Err(ValidationError::InvalidValue(msg)) if msg.contains("dot_mask")
+        ));
+
+        post.dot_mask = 0.6;
+        post.dot_mask_scale = 0.5;
+        assert!(matches!(
+            validate_post_processing(&post),
+            Err(ValidationError::InvalidValue(msg)) if msg.contains("dot_mask_scale")
+        ));
+    }
+
+    #[test]
+    fn test_validate_post_glitch() {
+        let mut post = make_post(0.0, 0.0);
+        post.glitch = 1.0;
+        assert!(validate_post_processing(&post).is_ok());
+
+        post.glitch = -0.1;
+        assert!(matches!(
+            validate_post_processing(&post),
+            Err(ValidationError::InvalidValue(msg)) if msg.contains("glitch")
+        ));
+    }
+
+    #[test]
+    fn test_validate_post_chromatic_aberration_angle() {
+        let mut post = make_post(0.0, 0.0);
+        post.chromatic_aberration = 0.01;
+        post.chromatic_aberration_angle = 90.0;
+        assert!(validate_post_processing(&post).is_ok());
+
+        post.chromatic_aberration_angle = f32::INFINITY;
+        assert!(matches!(
+            validate_post_processing(&post),
+            Err(ValidationError::InvalidValue(msg)) if msg.contains("chromatic_aberration_angle")
+        ));
+    }
+
+    #[test]
+    fn test_validate_post_pixelate_minimum() {
+        let mut post = make_post(0.0, 0.0);
+        post.pixelate = 4;
+        assert!(validate_post_processing(&post).is_ok());
+
+        post.pixelate = 0;
+        match validate_post_processing(&post) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("pixelate")),
+            _ => panic!("Expected InvalidValue error about pixelate"),
+        }
+    }
+
+    #[test]
+    fn test_validate_post_persistence_range() {
+        let mut post = make_post(0.0, 0.0);
+        post.persistence = 0.6;
+        assert!(validate_post_processing(&post).is_ok());
+
+        post.persistence = 1.5;
+        match validate_post_processing(&post) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("persistence")),
+            _ => panic!("Expected InvalidValue error about persistence"),
+        }
+    }
+
+    #[test]
+    fn test_validate_post_noise_seed_finite() {
+        let mut post = make_post(0.0, 0.0);
+        post.noise_seed = 42.0;
+        assert!(validate_post_processing(&post).is_ok());
+
+        post.noise_seed = f32::NAN;
+        match validate_post_processing(&post) {
+            Err(ValidationError::InvalidValue(msg)) => assert!(msg.contains("noise_seed")),
+            _ => panic!("Expected InvalidValue error about noise_seed"),
+        }
+    }
+
     #[test]
     fn test_validate_post_noise_exceeds() {
         let mut post = make_post(0.0, 0.0);
@@ -1292,8 +2463,32 @@ mod tests {
         post.scanlines = Some(Scanlines {
             intensity: 0.5,
             count: 300,
+            scroll_speed: 0.0,
+        });
+        assert!(validate_post_processing(&post).is_ok());
+    }
+
+    #[test]
+    fn test_validate_post_scanlines_scroll_speed() {
+        let mut post = make_post(0.0, 0.0);
+        post.scanlines = Some(Scanlines {
+            intensity: 0.5,
+            count: 300,
+            scroll_speed: -2.0,
         });
         assert!(validate_post_processing(&post).is_ok());
+
+        post.scanlines = Some(Scanlines {
+            intensity: 0.5,
+            count: 300,
+            scroll_speed: f32::NAN,
+        });
+        match validate_post_processing(&post) {
+            Err(ValidationError::InvalidValue(msg)) => {
+                assert!(msg.contains("scroll_speed"));
+            }
+            _ => panic!("Expected InvalidValue error about scroll_speed"),
+        }
     }
 
     #[test]
@@ -1303,12 +2498,14 @@ mod tests {
         post.scanlines = Some(Scanlines {
             intensity: 0.0,
             count: 300,
+            scroll_speed: 0.0,
         });
         assert!(validate_post_processing(&post).is_ok());
 
         post.scanlines = Some(Scanlines {
             intensity: 1.0,
             count: 300,
+            scroll_speed: 0.0,
         });
         assert!(validate_post_processing(&post).is_ok());
     }
@@ -1319,6 +2516,7 @@ mod tests {
         post.scanlines = Some(Scanlines {
             intensity: 1.1,
             count: 300,
+            scroll_speed: 0.0,
         });
         let result = validate_post_processing(&post);
         assert!(result.is_err());
@@ -1336,6 +2534,7 @@ mod tests {
         post.scanlines = Some(Scanlines {
             intensity: 0.1,
             count: 0,
+            scroll_speed: 0.0,
         });
         let result = validate_post_processing(&post);
         assert!(result.is_err());
@@ -1388,27 +2587,29 @@ mod tests {
 
     #[test]
     fn test_validate_opacity_static_valid() {
-        assert!(validate_opacity(&AnimatedValue::Static(0.0)).is_ok());
-        assert!(validate_opacity(&AnimatedValue::Static(0.5)).is_ok());
-        assert!(validate_opacity(&AnimatedValue::Static(1.0)).is_ok());
+        assert!(validate_opacity(&AnimatedValue::Static(0.0), TOTAL_FRAMES).is_ok());
+        assert!(validate_opacity(&AnimatedValue::Static(0.5), TOTAL_FRAMES).is_ok());
+        assert!(validate_opacity(&AnimatedValue::Static(1.0), TOTAL_FRAMES).is_ok());
     }
 
     #[test]
     fn test_validate_opacity_static_invalid() {
-        assert!(validate_opacity(&AnimatedValue::Static(-0.1)).is_err());
-        assert!(validate_opacity(&AnimatedValue::Static(1.1)).is_err());
+        assert!(validate_opacity(&AnimatedValue::Static(-0.1), TOTAL_FRAMES).is_err());
+        assert!(validate_opacity(&AnimatedValue::Static(1.1), TOTAL_FRAMES).is_err());
     }
 
     #[test]
     fn test_validate_opacity_expression_valid() {
-        assert!(validate_opacity(&AnimatedValue::Expression("t".to_string())).is_ok());
-        assert!(validate_opacity(&AnimatedValue::Expression("1 - t".to_string())).is_ok());
-        assert!(validate_opacity(&AnimatedValue::Expression("sin(t * PI) * 0.5 + 0.5".to_string())).is_ok());
+        for expr in ["t", "1 - t", "sin(t * PI) * 0.5 + 0.5"] {
+            let opacity = AnimatedValue::Expression(expr.into());
+            assert!(validate_opacity(&opacity, TOTAL_FRAMES).is_ok());
+        }
     }
 
     #[test]
     fn test_validate_opacity_expression_invalid_syntax() {
-        let result = validate_opacity(&AnimatedValue::Expression("invalid syntax here".to_string()));
+        let opacity = AnimatedValue::Expression("invalid syntax here".into());
+        let result = validate_opacity(&opacity, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidExpression(_)) => {}
@@ -1416,41 +2617,71 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_animated_value_non_finite_mid_timeline() {
+        // Fine at t = 0 but divides by zero half way through
+        let spike = AnimatedValue::Expression("1 / (t - 0.5)".into());
+        match validate_animated_value(&spike, "opacity", TOTAL_FRAMES) {
+            Err(ValidationError::InvalidExpression(msg)) => {
+                assert!(msg.contains("t = 0.5"));
+            }
+            _ => panic!("Expected InvalidExpression error for a non-finite value"),
+        }
+
+        let nan = AnimatedValue::Expression("sqrt(t - 0.9)".into());
+        assert!(validate_animated_value(&nan, "opacity", TOTAL_FRAMES).is_err());
+    }
+
+    #[test]
+    fn test_validate_animated_value_conditional() {
+        let hard_cut = AnimatedValue::Expression("if(t < 0.5, 0, 1)".into());
+        assert!(validate_animated_value(&hard_cut, "opacity", TOTAL_FRAMES).is_ok());
+
+        let not_boolean = AnimatedValue::Expression("if(t, 0, 1)".into());
+        assert!(matches!(
+            validate_animated_value(&not_boolean, "opacity", TOTAL_FRAMES),
+            Err(ValidationError::InvalidExpression(_))
+        ));
+    }
+
     #[test]
     fn test_validate_scale_uniform_valid() {
-        assert!(validate_scale(&Scale::Uniform(1.0)).is_ok());
-        assert!(validate_scale(&Scale::Uniform(0.5)).is_ok());
-        assert!(validate_scale(&Scale::Uniform(10.0)).is_ok());
+        assert!(validate_scale(&Scale::Uniform(1.0), TOTAL_FRAMES).is_ok());
+        assert!(validate_scale(&Scale::Uniform(0.5), TOTAL_FRAMES).is_ok());
+        assert!(validate_scale(&Scale::Uniform(10.0), TOTAL_FRAMES).is_ok());
     }
 
     #[test]
     fn test_validate_scale_uniform_invalid() {
-        assert!(validate_scale(&Scale::Uniform(0.0)).is_err());
-        assert!(validate_scale(&Scale::Uniform(-1.0)).is_err());
+        assert!(validate_scale(&Scale::Uniform(0.0), TOTAL_FRAMES).is_err());
+        assert!(validate_scale(&Scale::Uniform(-1.0), TOTAL_FRAMES).is_err());
     }
 
     #[test]
     fn test_validate_scale_non_uniform_valid() {
-        assert!(validate_scale(&Scale::NonUniform([1.0, 2.0, 3.0])).is_ok());
-        assert!(validate_scale(&Scale::NonUniform([0.1, 0.1, 0.1])).is_ok());
+        assert!(validate_scale(&Scale::NonUniform([1.0, 2.0, 3.0]), TOTAL_FRAMES).is_ok());
+        assert!(validate_scale(&Scale::NonUniform([0.1, 0.1, 0.1]), TOTAL_FRAMES).is_ok());
     }
 
     #[test]
     fn test_validate_scale_non_uniform_invalid() {
-        assert!(validate_scale(&Scale::NonUniform([0.0, 1.0, 1.0])).is_err());
-        assert!(validate_scale(&Scale::NonUniform([1.0, -1.0, 1.0])).is_err());
-        assert!(validate_scale(&Scale::NonUniform([1.0, 1.0, 0.0])).is_err());
+        assert!(validate_scale(&Scale::NonUniform([0.0, 1.0, 1.0]), TOTAL_FRAMES).is_err());
+        assert!(validate_scale(&Scale::NonUniform([1.0, -1.0, 1.0]), TOTAL_FRAMES).is_err());
+        assert!(validate_scale(&Scale::NonUniform([1.0, 1.0, 0.0]), TOTAL_FRAMES).is_err());
     }
 
     #[test]
     fn test_validate_scale_uniform_expression_valid() {
-        assert!(validate_scale(&Scale::UniformExpression("t * 4 + 1".to_string())).is_ok());
-        assert!(validate_scale(&Scale::UniformExpression("1 + sin(t * PI) * 0.5".to_string())).is_ok());
+        for expr in ["t * 4 + 1", "1 + sin(t * PI) * 0.5"] {
+            let scale = Scale::UniformExpression(expr.into());
+            assert!(validate_scale(&scale, TOTAL_FRAMES).is_ok());
+        }
     }
 
     #[test]
     fn test_validate_scale_uniform_expression_invalid() {
-        let result = validate_scale(&Scale::UniformExpression("invalid syntax".to_string()));
+        let scale = Scale::UniformExpression("invalid syntax".into());
+        let result = validate_scale(&scale, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidExpression(_)) => {}
@@ -1461,25 +2692,55 @@ mod tests {
     #[test]
     fn test_validate_scale_per_axis_valid() {
         let scale = Scale::PerAxis(AnimatedScale {
-            x: AnimatedValue::Expression("t * 2 + 1".to_string()),
+            x: AnimatedValue::Expression("t * 2 + 1".into()),
             y: AnimatedValue::Static(1.0),
-            z: AnimatedValue::Expression("1 + sin(t * PI)".to_string()),
+            z: AnimatedValue::Expression("1 + sin(t * PI)".into()),
         });
-        assert!(validate_scale(&scale).is_ok());
+        assert!(validate_scale(&scale, TOTAL_FRAMES).is_ok());
     }
 
     #[test]
     fn test_validate_scale_per_axis_invalid_expression() {
         let scale = Scale::PerAxis(AnimatedScale {
-            x: AnimatedValue::Expression("invalid".to_string()),
+            x: AnimatedValue::Expression("invalid".into()),
             y: AnimatedValue::Static(1.0),
             z: AnimatedValue::Static(1.0),
         });
-        let result = validate_scale(&scale);
+        let result = validate_scale(&scale, TOTAL_FRAMES);
         assert!(result.is_err());
         match result {
             Err(ValidationError::InvalidExpression(_)) => {}
             _ => panic!("Expected InvalidExpression error"),
         }
     }
+
+    #[test]
+    fn test_validate_animated_color() {
+        assert!(validate_animated_color(&"#00ff41".into(), TOTAL_FRAMES).is_ok());
+        assert!(matches!(
+            validate_animated_color(&"#00ff4".into(), TOTAL_FRAMES),
+            Err(ValidationError::InvalidColor(_))
+        ));
+
+        let color = AnimatedColor::Channels(Box::new(AnimatedRgb {
+            r: AnimatedValue::Expression("invalid".into()),
+            ..Default::default()
+        }));
+        assert!(matches!(
+            validate_animated_color(&color, TOTAL_FRAMES),
+            Err(ValidationError::InvalidExpression(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_easing_names() {
+        let known = AnimatedValue::Expression("ease_out_back(t) * 360".into());
+        assert!(validate_animated_value(&known, "rotation", TOTAL_FRAMES).is_ok());
+
+        let unknown = AnimatedValue::Expression("ease_out_wobble(t) * 360".into());
+        assert!(matches!(
+            validate_animated_value(&unknown, "rotation", TOTAL_FRAMES),
+            Err(ValidationError::InvalidExpression(_))
+        ));
+    }
 }
@@ -0,0 +1,50 @@
+struct BackgroundUniforms {
+    from_color: vec4<f32>,
+    to_color: vec4<f32>,
+    direction: vec2<f32>,
+    resolution: vec2<f32>,
+    gradient_length: f32,
+    use_image: f32,
+    _padding: vec2<f32>,
+}
+
+@group(0) @binding(0)
+var image_texture: texture_2d<f32>;
+
+@group(0) @binding(1)
+var image_sampler: sampler;
+
+@group(0) @binding(2)
+var<uniform> uniforms: BackgroundUniforms;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+// Fullscreen triangle
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+
+    let x = f32((vertex_index << 1u) & 2u);
+    let y = f32(vertex_index & 2u);
+
+    // Far plane, so depth-tested lines always draw over the background
+    out.position = vec4<f32>(x * 2.0 - 1.0, y * 2.0 - 1.0, 1.0, 1.0);
+    out.uv = vec2<f32>(x, 1.0 - y);
+
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    if uniforms.use_image > 0.0 {
+        return textureSample(image_texture, image_sampler, in.uv);
+    }
+
+    // Project the pixel onto the gradient line, centered on the canvas
+    let p = (in.uv - 0.5) * uniforms.resolution;
+    let t = clamp(dot(p, uniforms.direction) / uniforms.gradient_length + 0.5, 0.0, 1.0);
+    return mix(uniforms.from_color, uniforms.to_color, t);
+}
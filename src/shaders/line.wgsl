@@ -10,6 +10,7 @@ var<uniform> uniforms: Uniforms;
 struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) color: vec4<f32>,
+    @location(2) depth_offset: f32,
 }
 
 struct VertexOutput {
@@ -21,6 +22,15 @@ struct VertexOutput {
 fn vs_main(in: VertexInput) -> VertexOutput {
     var out: VertexOutput;
     out.clip_position = uniforms.view_proj * vec4<f32>(in.position, 1.0);
+    // Pull each element toward the camera by its own offset so coincident edges from
+    // different elements don't z-fight. Stop at the near plane rather than clipping
+    // lines that were visible, but leave lines already in front of it clipped.
+    if out.clip_position.z >= 0.0 {
+        out.clip_position.z = max(
+            out.clip_position.z - in.depth_offset * out.clip_position.w,
+            0.0,
+        );
+    }
     out.color = in.color;
     return out;
 }
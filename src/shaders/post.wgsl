@@ -8,7 +8,25 @@ struct PostUniforms {
     noise: f32,
     vignette: f32,
     crt_curvature: f32,
-    _padding: vec2<f32>,
+    tonemap: f32,
+    transparent: f32,
+    noise_seed: f32,
+    persistence: f32,
+    bloom_threshold: f32,
+    pixelate: f32,
+    tint: vec4<f32>,
+    gamma: f32,
+    saturation: f32,
+    scanline_scroll: f32,
+    vignette_roundness: f32,
+    vignette_smoothness: f32,
+    lens_distortion: f32,
+    dot_mask: f32,
+    dot_mask_scale: f32,
+    glitch: f32,
+    chromatic_aberration_angle: f32,
+    _padding0: f32,
+    _padding1: f32,
 }
 
 @group(0) @binding(0)
@@ -20,6 +38,9 @@ var input_sampler: sampler;
 @group(0) @binding(2)
 var<uniform> uniforms: PostUniforms;
 
+@group(0) @binding(3)
+var history_texture: texture_2d<f32>;
+
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) uv: vec2<f32>,
@@ -60,19 +81,53 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         uv = uv + center * dist;
     }
 
-    // Check bounds after curvature
+    // Lens distortion: barrel pulls the edges in, pincushion pushes them out
+    if uniforms.lens_distortion != 0.0 {
+        let center = uv - 0.5;
+        uv = 0.5 + center * (1.0 + dot(center, center) * uniforms.lens_distortion);
+    }
+
+    // Check bounds after curvature and distortion; barrel corners fall outside the image
     if uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0 {
-        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0 - uniforms.transparent);
+    }
+
+    // Snap to the center of an NxN block; done after curvature so the grid bends too
+    if uniforms.pixelate > 1.0 {
+        let block = uniforms.pixelate / uniforms.resolution;
+        uv = (floor(uv / block) + 0.5) * block;
+    }
+
+    // Glitch: some frames shift random horizontal bands sideways and split their channels.
+    // Everything is hashed from `time`, so each frame glitches identically on every render.
+    var glitch_split = 0.0;
+    if uniforms.glitch > 0.0 {
+        let frame_seed = uniforms.time * 1000.0;
+        if hash(vec2<f32>(frame_seed, 3.7)) < uniforms.glitch {
+            let band_count = mix(8.0, 32.0, hash(vec2<f32>(frame_seed, 9.1)));
+            let band = floor(uv.y * band_count);
+            if hash(vec2<f32>(band, frame_seed)) > 0.6 {
+                let shift = (hash(vec2<f32>(band, frame_seed + 1.0)) - 0.5) * uniforms.glitch * 0.2;
+                uv.x = fract(uv.x + shift);
+                glitch_split = shift * 0.25;
+            }
+        }
     }
 
+    // Alpha passes through untouched; it is always 1.0 unless the canvas is transparent
+    let alpha = textureSample(input_texture, input_sampler, uv).a;
+
     var color: vec3<f32>;
 
-    // Apply chromatic aberration
-    if uniforms.chromatic_aberration > 0.0 {
-        let offset = uniforms.chromatic_aberration;
-        let r = textureSample(input_texture, input_sampler, uv + vec2<f32>(offset, 0.0)).r;
+    // Apply chromatic aberration, plus any split from a glitched band
+    if uniforms.chromatic_aberration > 0.0 || uniforms.glitch > 0.0 {
+        // uv.y points down, so flip the angle's y to keep it counterclockwise on screen
+        let angle = uniforms.chromatic_aberration_angle;
+        let direction = vec2<f32>(cos(angle), -sin(angle));
+        let offset = direction * uniforms.chromatic_aberration + vec2<f32>(glitch_split, 0.0);
+        let r = textureSample(input_texture, input_sampler, uv + offset).r;
         let g = textureSample(input_texture, input_sampler, uv).g;
-        let b = textureSample(input_texture, input_sampler, uv - vec2<f32>(offset, 0.0)).b;
+        let b = textureSample(input_texture, input_sampler, uv - offset).b;
         color = vec3<f32>(r, g, b);
     } else {
         color = textureSample(input_texture, input_sampler, uv).rgb;
@@ -87,7 +142,11 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         for (var i = 0; i < bloom_samples; i++) {
             let angle = f32(i) * 3.14159 * 2.0 / f32(bloom_samples);
             let offset = vec2<f32>(cos(angle), sin(angle)) * bloom_radius;
-            bloom_color += textureSample(input_texture, input_sampler, uv + offset).rgb;
+            let sample = textureSample(input_texture, input_sampler, uv + offset).rgb;
+            // Only the part of each sample brighter than the threshold glows
+            let brightness = max(sample.r, max(sample.g, sample.b));
+            let weight = max(brightness - uniforms.bloom_threshold, 0.0) / max(brightness, 0.0001);
+            bloom_color += sample * weight;
         }
         bloom_color /= f32(bloom_samples);
 
@@ -97,23 +156,64 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
     // Apply scanlines
     if uniforms.scanline_intensity > 0.0 && uniforms.scanline_count > 0.0 {
-        let scanline = sin(uv.y * uniforms.scanline_count * 3.14159) * 0.5 + 0.5;
+        // Each sine period is one band; scrolling shifts whole periods over the loop
+        let phase = uv.y * uniforms.scanline_count * 3.14159
+            - uniforms.time * uniforms.scanline_scroll * 6.28318;
+        let scanline = sin(phase) * 0.5 + 0.5;
         let scanline_factor = 1.0 - uniforms.scanline_intensity * (1.0 - scanline);
         color *= scanline_factor;
     }
 
+    // Apply aperture-grille mask: repeating vertical R, G, B phosphor stripes
+    if uniforms.dot_mask > 0.0 {
+        let stripe = u32(floor(uv.x * uniforms.resolution.x / uniforms.dot_mask_scale)) % 3u;
+        var phosphor = vec3<f32>(0.0);
+        phosphor[stripe] = 1.0;
+        // Fade the mask in as triads shrink relative to the frame, so small renders stay legible
+        let triads = uniforms.resolution.x / (uniforms.dot_mask_scale * 3.0);
+        let strength = uniforms.dot_mask * clamp(triads / 320.0, 0.25, 1.0);
+        color *= mix(vec3<f32>(1.0), phosphor, strength);
+    }
+
     // Apply noise
     if uniforms.noise > 0.0 {
-        let noise_value = hash(uv * uniforms.resolution + vec2<f32>(uniforms.time * 1000.0, 0.0));
+        // Fold the seed into a bounded offset so large seeds don't lose hash precision
+        let seed_offset = fract(uniforms.noise_seed * 0.6180339) * 1000.0;
+        let noise_offset = vec2<f32>(uniforms.time * 1000.0, seed_offset);
+        let noise_value = hash(uv * uniforms.resolution + noise_offset);
         color = mix(color, vec3<f32>(noise_value), uniforms.noise * 0.5);
     }
 
     // Apply vignette
     if uniforms.vignette > 0.0 {
-        let center = uv - 0.5;
-        let vignette_factor = 1.0 - dot(center, center) * uniforms.vignette * 2.0;
-        color *= max(vignette_factor, 0.0);
+        // Stretch x toward pixel units as roundness goes to 1 so wide canvases get a circle
+        let aspect = uniforms.resolution.x / uniforms.resolution.y;
+        let center = (uv - 0.5) * vec2<f32>(mix(1.0, aspect, uniforms.vignette_roundness), 1.0);
+        let distance = dot(center, center) * uniforms.vignette * 2.0;
+        let falloff = pow(distance, 1.0 / uniforms.vignette_smoothness);
+        color *= max(1.0 - falloff, 0.0);
+    }
+
+    // Tonemap HDR input down to displayable range. Below the knee colors pass through;
+    // above it they are Reinhard-compressed into the headroom left, so 1.0 lands at 0.9
+    // rather than the 0.5 plain Reinhard gives, and brighter values approach 1.0
+    if uniforms.tonemap > 0.0 {
+        let knee = 0.8;
+        let over = max(color - vec3<f32>(knee), vec3<f32>(0.0)) / (1.0 - knee);
+        color = min(color, vec3<f32>(knee)) + (1.0 - knee) * over / (over + vec3<f32>(1.0));
+    }
+
+    // Color grading: tint, then saturation around Rec. 709 luma, then gamma
+    color *= uniforms.tint.rgb;
+    let luma = dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+    color = mix(vec3<f32>(luma), color, uniforms.saturation);
+    color = pow(max(color, vec3<f32>(0.0)), vec3<f32>(1.0 / uniforms.gamma));
+
+    // Phosphor persistence: decay the previous frame's output into this one
+    if uniforms.persistence > 0.0 {
+        let history = textureSample(history_texture, input_sampler, in.uv);
+        return mix(vec4<f32>(color, alpha), history, uniforms.persistence);
     }
 
-    return vec4<f32>(color, 1.0);
+    return vec4<f32>(color, alpha);
 }
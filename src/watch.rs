@@ -0,0 +1,69 @@
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum WatchError {
+    #[error("Failed to watch scene file: {0}")]
+    Notify(#[from] notify::Error),
+
+    #[error("Failed to install Ctrl-C handler: {0}")]
+    Signal(#[from] ctrlc::Error),
+}
+
+enum WatchEvent {
+    Changed,
+    Stop,
+}
+
+/// Block until Ctrl-C, calling `on_change` whenever `path` is modified.
+///
+/// Bursts of filesystem events (editors often write, truncate and rename in
+/// quick succession) are coalesced into one call once `debounce` passes quietly.
+pub fn watch_file<F: FnMut()>(
+    path: &Path,
+    debounce: Duration,
+    mut on_change: F,
+) -> Result<(), WatchError> {
+    use notify::{RecursiveMode, Watcher};
+
+    let (tx, rx) = mpsc::channel();
+
+    // Watch the parent directory so atomic save-by-rename is still seen
+    let file_name = path.file_name().map(|name| name.to_os_string());
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => Path::new(".").to_path_buf(),
+    };
+
+    let change_tx = tx.clone();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else { return };
+        if !(event.kind.is_modify() || event.kind.is_create()) {
+            return;
+        }
+        if event.paths.iter().any(|p| p.file_name() == file_name.as_deref()) {
+            let _ = change_tx.send(WatchEvent::Changed);
+        }
+    })?;
+    watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+    ctrlc::set_handler(move || {
+        let _ = tx.send(WatchEvent::Stop);
+    })?;
+
+    while let Ok(WatchEvent::Changed) = rx.recv() {
+        // Debounce: wait until no further events arrive for the full interval
+        loop {
+            match rx.recv_timeout(debounce) {
+                Ok(WatchEvent::Changed) => continue,
+                Ok(WatchEvent::Stop) => return Ok(()),
+                Err(_) => break,
+            }
+        }
+        on_change();
+    }
+
+    Ok(())
+}